@@ -4,79 +4,98 @@
 ///
 /// Fields are private to ensure invariant is never violated.
 ///
+/// `T` is the token amount type, either `u64` or `u128`.
+///
 /// Use [`BefFee`] to build this struct
 #[derive(Debug, Copy, Clone, Default, Eq, PartialEq, PartialOrd, Ord, Hash)]
-pub struct AftFee {
-    rem: u64,
-    fee: u64,
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AftFee<T = u64> {
+    rem: T,
+    fee: T,
 }
 
-impl AftFee {
-    /// The remaining token amount after fees have been levied
-    #[inline]
-    pub const fn rem(&self) -> u64 {
-        self.rem
-    }
+/// A token amount before the levying of fees
+///
+/// `T` is the token amount type, either `u64` or `u128`.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct BefFee<T = u64>(pub T);
 
-    /// The fee amount that was levied
-    #[inline]
-    pub const fn fee(&self) -> u64 {
-        self.fee
-    }
+macro_rules! impl_aft_bef_fee {
+    ($T:ty) => {
+        impl AftFee<$T> {
+            /// The remaining token amount after fees have been levied
+            #[inline]
+            pub const fn rem(&self) -> $T {
+                self.rem
+            }
 
-    /// The original token amount before levying of fees.
-    ///
-    /// `self.rem() + self.fee()`
-    #[inline]
-    pub const fn bef_fee(&self) -> u64 {
-        self.rem + self.fee
-    }
+            /// The fee amount that was levied
+            #[inline]
+            pub const fn fee(&self) -> $T {
+                self.fee
+            }
 
-    /// # Safety
-    /// - `rem + fee` must not overflow
-    #[inline]
-    pub const unsafe fn new_unchecked(rem: u64, fee: u64) -> Self {
-        Self { rem, fee }
-    }
-}
+            /// The original token amount before levying of fees.
+            ///
+            /// `self.rem() + self.fee()`
+            #[inline]
+            pub const fn bef_fee(&self) -> $T {
+                self.rem + self.fee
+            }
 
-/// A token amount before the levying of fees
-#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, PartialOrd, Ord, Hash)]
-#[repr(transparent)]
-pub struct BefFee(pub u64);
+            /// # Safety
+            /// - `rem + fee` must not overflow
+            #[inline]
+            pub const unsafe fn new_unchecked(rem: $T, fee: $T) -> Self {
+                Self { rem, fee }
+            }
+        }
 
-impl BefFee {
-    /// # Params
-    /// - `fee`: the fee amount charged to be subtracted
-    ///    from the encapsulated token amount
-    ///
-    /// # Returns
-    /// The constructed [`AftFee`] or `None` if `fee > self.0`
-    #[inline]
-    pub const fn with_fee(self, fee: u64) -> Option<AftFee> {
-        let rem = match self.0.checked_sub(fee) {
-            None => return None,
-            Some(r) => r,
-        };
-        Some(AftFee { rem, fee })
-    }
+        impl BefFee<$T> {
+            /// # Params
+            /// - `fee`: the fee amount charged to be subtracted
+            ///    from the encapsulated token amount
+            ///
+            /// # Returns
+            /// The constructed [`AftFee`] or `None` if `fee > self.0`
+            #[inline]
+            pub const fn with_fee(self, fee: $T) -> Option<AftFee<$T>> {
+                let rem = match self.0.checked_sub(fee) {
+                    None => return None,
+                    Some(r) => r,
+                };
+                Some(AftFee { rem, fee })
+            }
 
-    /// # Params
-    /// - `rem`: the remaining amount after subtracting the fee charged
-    ///   from the encapsulated token amount
-    ///
-    /// # Returns
-    /// The constructed [`AftFee`] or `None` if `rem > self.0`.
-    #[inline]
-    pub const fn with_rem(self, rem: u64) -> Option<AftFee> {
-        let fee = match self.0.checked_sub(rem) {
-            None => return None,
-            Some(r) => r,
-        };
-        Some(AftFee { rem, fee })
-    }
+            /// # Params
+            /// - `rem`: the remaining amount after subtracting the fee charged
+            ///   from the encapsulated token amount
+            ///
+            /// # Returns
+            /// The constructed [`AftFee`] or `None` if `rem > self.0`.
+            #[inline]
+            pub const fn with_rem(self, rem: $T) -> Option<AftFee<$T>> {
+                let fee = match self.0.checked_sub(rem) {
+                    None => return None,
+                    Some(r) => r,
+                };
+                Some(AftFee { rem, fee })
+            }
+        }
+    };
 }
 
+impl_aft_bef_fee!(u64);
+impl_aft_bef_fee!(u128);
+
 #[cfg(test)]
 mod tests {
     use proptest::prelude::*;
@@ -108,6 +127,22 @@ mod tests {
         }
     }
 
+    proptest! {
+        #[test]
+        fn aft_bef_fee_invariant_u128(bef_fee: u128, sample: u128) {
+            let sample = sample.min(bef_fee);
+            let bef = BefFee(bef_fee);
+            for a in [bef.with_fee(sample).unwrap(), bef.with_rem(sample).unwrap()] {
+                prop_assert_eq!(a.fee() + a.rem(), a.bef_fee());
+                prop_assert_eq!(a.bef_fee(), bef_fee);
+            }
+            if let Some(oor) = bef_fee.checked_add(1) {
+                prop_assert!(bef.with_fee(oor).is_none());
+                prop_assert!(bef.with_rem(oor).is_none());
+            }
+        }
+    }
+
     proptest! {
         #[test]
         fn out_of_range_none(
@@ -0,0 +1,325 @@
+use core::ops::RangeInclusive;
+
+use crate::{
+    ratio::{AnyRatio, Ceil, Floor, Ratio, TryFromRatioError},
+    AftFee, Fee,
+};
+
+/// A [`Fee`] of any rounding mode and [`Ratio`] type,
+/// for when they are only known at runtime, e.g. when loaded from config,
+/// or for keeping fees of different types in the same collection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AnyFee {
+    Ceil(Fee<Ceil<AnyRatio>>),
+    Floor(Fee<Floor<AnyRatio>>),
+}
+
+macro_rules! impl_any_fee {
+    (@fee $R:ident, [$($V:ident($N:ty, $D:ty)),*]) => {
+        impl Fee<$R<AnyRatio>> {
+            /// # Returns
+            /// - `None` if `fee_ratio` is not valid (`>1.0`)
+            /// - `None` if `fee_ratio`'s `denominator = 0`.
+            ///
+            /// See [`Fee::new`] for [`Ratio`]s
+            #[inline]
+            pub const fn new(fee_ratio: AnyRatio) -> Option<Self> {
+                let (n, d) = fee_ratio.to_u128_parts();
+                if d == 0 || n > d {
+                    None
+                } else {
+                    Some(Self($R(fee_ratio)))
+                }
+            }
+
+            /// # Safety
+            /// - `fee_ratio` must be valid (`<= 1.0`)
+            #[inline]
+            pub const unsafe fn new_unchecked(fee_ratio: AnyRatio) -> Self {
+                Self($R(fee_ratio))
+            }
+
+            /// See [`Fee::apply`] for [`Ratio`]s
+            #[inline]
+            pub const fn apply(&self, amount: u64) -> Option<AftFee> {
+                match self.0 .0 {
+                    $(AnyRatio::$V(r) => Fee($R(r)).apply(amount),)*
+                }
+            }
+
+            /// See [`Fee::reverse_from_rem`] for [`Ratio`]s
+            #[inline]
+            pub const fn reverse_from_rem(&self, rem: u64) -> Option<RangeInclusive<u64>> {
+                match self.0 .0 {
+                    $(AnyRatio::$V(r) => Fee($R(r)).reverse_from_rem(rem),)*
+                }
+            }
+
+            /// See [`Fee::reverse_from_fee`] for [`Ratio`]s
+            #[inline]
+            pub const fn reverse_from_fee(&self, fee: u64) -> Option<RangeInclusive<u64>> {
+                match self.0 .0 {
+                    $(AnyRatio::$V(r) => Fee($R(r)).reverse_from_fee(fee),)*
+                }
+            }
+
+            /// See [`Fee::apply_u128`] for [`Ratio`]s
+            #[inline]
+            pub const fn apply_u128(&self, amount: u128) -> Option<AftFee<u128>> {
+                match self.0 .0 {
+                    $(AnyRatio::$V(r) => Fee($R(r)).apply_u128(amount),)*
+                }
+            }
+
+            /// See [`Fee::reverse_from_rem_u128`] for [`Ratio`]s
+            #[inline]
+            pub const fn reverse_from_rem_u128(&self, rem: u128) -> Option<RangeInclusive<u128>> {
+                match self.0 .0 {
+                    $(AnyRatio::$V(r) => Fee($R(r)).reverse_from_rem_u128(rem),)*
+                }
+            }
+
+            /// See [`Fee::reverse_from_fee_u128`] for [`Ratio`]s
+            #[inline]
+            pub const fn reverse_from_fee_u128(&self, fee: u128) -> Option<RangeInclusive<u128>> {
+                match self.0 .0 {
+                    $(AnyRatio::$V(r) => Fee($R(r)).reverse_from_fee_u128(fee),)*
+                }
+            }
+        }
+
+        impl From<Fee<$R<AnyRatio>>> for AnyFee {
+            #[inline]
+            fn from(f: Fee<$R<AnyRatio>>) -> Self {
+                Self::$R(f)
+            }
+        }
+
+        $(
+            impl From<Fee<$R<Ratio<$N, $D>>>> for Fee<$R<AnyRatio>> {
+                #[inline]
+                fn from(Fee($R(r)): Fee<$R<Ratio<$N, $D>>>) -> Self {
+                    Self($R(r.into()))
+                }
+            }
+
+            impl From<Fee<$R<Ratio<$N, $D>>>> for AnyFee {
+                #[inline]
+                fn from(f: Fee<$R<Ratio<$N, $D>>>) -> Self {
+                    Self::$R(f.into())
+                }
+            }
+
+            /// The fee ratio is converted to its lowest form, with zero fees converted to `Self::ZERO`.
+            ///
+            /// # Errors
+            ///
+            /// [`TryFromRatioError`] if the fee ratio's lowest form does not fit
+            impl TryFrom<Fee<$R<AnyRatio>>> for Fee<$R<Ratio<$N, $D>>> {
+                type Error = TryFromRatioError;
+
+                #[inline]
+                fn try_from(Fee(r): Fee<$R<AnyRatio>>) -> Result<Self, Self::Error> {
+                    let r: $R<Ratio<$N, $D>> = r.try_into()?;
+                    // lowest_form() of zero is 0/0, which violates the nonzero denominator invariant
+                    Ok(if r.0.is_zero() { Self::ZERO } else { Self(r) })
+                }
+            }
+        )*
+    };
+    ($($t:tt)*) => {
+        impl_any_fee!(@fee Ceil, [$($t)*]);
+        impl_any_fee!(@fee Floor, [$($t)*]);
+    };
+}
+
+impl_any_fee!(
+    U8U8(u8, u8),
+    U8U16(u8, u16),
+    U8U32(u8, u32),
+    U8U64(u8, u64),
+    U8U128(u8, u128),
+    U16U8(u16, u8),
+    U16U16(u16, u16),
+    U16U32(u16, u32),
+    U16U64(u16, u64),
+    U16U128(u16, u128),
+    U32U8(u32, u8),
+    U32U16(u32, u16),
+    U32U32(u32, u32),
+    U32U64(u32, u64),
+    U32U128(u32, u128),
+    U64U8(u64, u8),
+    U64U16(u64, u16),
+    U64U32(u64, u32),
+    U64U64(u64, u64),
+    U64U128(u64, u128),
+    U128U8(u128, u8),
+    U128U16(u128, u16),
+    U128U32(u128, u32),
+    U128U64(u128, u64),
+    U128U128(u128, u128)
+);
+
+impl AnyFee {
+    /// See [`Fee::apply`] for [`Ratio`]s
+    #[inline]
+    pub const fn apply(&self, amount: u64) -> Option<AftFee> {
+        match self {
+            Self::Ceil(f) => f.apply(amount),
+            Self::Floor(f) => f.apply(amount),
+        }
+    }
+
+    /// See [`Fee::reverse_from_rem`] for [`Ratio`]s
+    #[inline]
+    pub const fn reverse_from_rem(&self, rem: u64) -> Option<RangeInclusive<u64>> {
+        match self {
+            Self::Ceil(f) => f.reverse_from_rem(rem),
+            Self::Floor(f) => f.reverse_from_rem(rem),
+        }
+    }
+
+    /// See [`Fee::reverse_from_fee`] for [`Ratio`]s
+    #[inline]
+    pub const fn reverse_from_fee(&self, fee: u64) -> Option<RangeInclusive<u64>> {
+        match self {
+            Self::Ceil(f) => f.reverse_from_fee(fee),
+            Self::Floor(f) => f.reverse_from_fee(fee),
+        }
+    }
+
+    /// See [`Fee::apply_u128`] for [`Ratio`]s
+    #[inline]
+    pub const fn apply_u128(&self, amount: u128) -> Option<AftFee<u128>> {
+        match self {
+            Self::Ceil(f) => f.apply_u128(amount),
+            Self::Floor(f) => f.apply_u128(amount),
+        }
+    }
+
+    /// See [`Fee::reverse_from_rem_u128`] for [`Ratio`]s
+    #[inline]
+    pub const fn reverse_from_rem_u128(&self, rem: u128) -> Option<RangeInclusive<u128>> {
+        match self {
+            Self::Ceil(f) => f.reverse_from_rem_u128(rem),
+            Self::Floor(f) => f.reverse_from_rem_u128(rem),
+        }
+    }
+
+    /// See [`Fee::reverse_from_fee_u128`] for [`Ratio`]s
+    #[inline]
+    pub const fn reverse_from_fee_u128(&self, fee: u128) -> Option<RangeInclusive<u128>> {
+        match self {
+            Self::Ceil(f) => f.reverse_from_fee_u128(fee),
+            Self::Floor(f) => f.reverse_from_fee_u128(fee),
+        }
+    }
+
+    /// The fee ratio, regardless of rounding mode
+    #[inline]
+    pub const fn fee_ratio(&self) -> AnyRatio {
+        match self {
+            Self::Ceil(f) => f.0 .0,
+            Self::Floor(f) => f.0 .0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    macro_rules! check {
+        ($f:expr, $any:expr, $amt:expr, $rem:expr, $fee:expr) => {{
+            let (f, any) = ($f, $any);
+            prop_assert_eq!(any.apply($amt), f.apply($amt));
+            prop_assert_eq!(any.reverse_from_rem($rem), f.reverse_from_rem($rem));
+            prop_assert_eq!(any.reverse_from_fee($fee), f.reverse_from_fee($fee));
+            prop_assert_eq!(any.apply_u128($amt as u128), f.apply_u128($amt as u128));
+            prop_assert_eq!(
+                any.reverse_from_rem_u128($rem as u128),
+                f.reverse_from_rem_u128($rem as u128)
+            );
+            prop_assert_eq!(
+                any.reverse_from_fee_u128($fee as u128),
+                f.reverse_from_fee_u128($fee as u128)
+            );
+        }};
+    }
+
+    macro_rules! test_suite {
+        ($N:ty, $D:ty, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(n: $N, d: $D, amt: u64, rem: u64, fee: u64) {
+                    type R = Ratio<$N, $D>;
+
+                    let r = R::new(n, d);
+                    prop_assert_eq!(
+                        Fee::<Ceil<AnyRatio>>::new(r.into()).is_some(),
+                        Fee::<Ceil<R>>::new(r).is_some(),
+                    );
+                    prop_assert_eq!(
+                        Fee::<Floor<AnyRatio>>::new(r.into()).is_some(),
+                        Fee::<Floor<R>>::new(r).is_some(),
+                    );
+                    if let Some(f) = Fee::<Ceil<R>>::new(r) {
+                        check!(f, Fee::<Ceil<AnyRatio>>::from(f), amt, rem, fee);
+                        check!(f, AnyFee::from(f), amt, rem, fee);
+                        prop_assert_eq!(Fee::<Ceil<R>>::try_from(Fee::<Ceil<AnyRatio>>::from(f)), Ok(f));
+                    }
+                    if let Some(f) = Fee::<Floor<R>>::new(r) {
+                        check!(f, Fee::<Floor<AnyRatio>>::from(f), amt, rem, fee);
+                        check!(f, AnyFee::from(f), amt, rem, fee);
+                        prop_assert_eq!(Fee::<Floor<R>>::try_from(Fee::<Floor<AnyRatio>>::from(f)), Ok(f));
+                    }
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, u8, any_fee_u8_u8);
+    test_suite!(u8, u16, any_fee_u8_u16);
+    test_suite!(u16, u16, any_fee_u16_u16);
+    test_suite!(u32, u64, any_fee_u32_u64);
+    test_suite!(u64, u64, any_fee_u64_u64);
+    test_suite!(u64, u128, any_fee_u64_u128);
+    test_suite!(u128, u128, any_fee_u128_u128);
+
+    #[test]
+    fn any_fee_heterogeneous() {
+        let fees: [AnyFee; 3] = [
+            Fee::<Ceil<Ratio<u16, u16>>>::new(Ratio::new(25, 10_000))
+                .unwrap()
+                .into(),
+            Fee::<Floor<Ratio<u64, u64>>>::new(Ratio::new(1, 400))
+                .unwrap()
+                .into(),
+            Fee::<Ceil<Ratio<u8, u128>>>::ZERO.into(),
+        ];
+        let fees_levied = fees.map(|f| f.apply(1_001).unwrap().fee());
+        assert_eq!(fees_levied, [3, 2, 0]);
+        assert_eq!(fees[0].fee_ratio(), fees[1].fee_ratio());
+        assert_ne!(fees[0], fees[1]);
+
+        let narrowed: Result<Fee<Ceil<Ratio<u8, u16>>>, _> = match fees[0] {
+            AnyFee::Ceil(f) => f.try_into(),
+            AnyFee::Floor(_) => unreachable!(),
+        };
+        assert_eq!(
+            narrowed,
+            Fee::<Ceil<Ratio<u8, u16>>>::new(Ratio::new(1, 400)).ok_or(TryFromRatioError)
+        );
+        assert_eq!(
+            Fee::<Ceil<Ratio<u8, u8>>>::try_from(
+                Fee::<Ceil<AnyRatio>>::new(AnyRatio::U16U16(Ratio::new(1, 400))).unwrap()
+            ),
+            Err(TryFromRatioError)
+        );
+        assert!(Fee::<Ceil<AnyRatio>>::new(AnyRatio::U8U8(Ratio::new(2, 1))).is_none());
+        assert!(Fee::<Floor<AnyRatio>>::new(AnyRatio::U64U64(Ratio::new(0, 0))).is_none());
+    }
+}
@@ -0,0 +1,163 @@
+use core::ops::RangeInclusive;
+
+use crate::{
+    ratio::{AnyRatio, Ceil, ConstRatio, Floor, Ratio},
+    AftFee, AnyFee, Fee, Rounded,
+};
+
+/// Common interface over the different [`Fee`] types,
+/// e.g. `Fee<Ceil<Ratio<u16, u16>>>`, `Fee<Floor<Ratio<u64, u64>>>` or [`AnyFee`].
+///
+/// Allows code to be generic over the fee type, e.g. [`crate::FeeStack`].
+///
+/// The inherent methods of the same name should be preferred
+/// in non-generic contexts since they are `const`.
+pub trait FeeApply {
+    /// See [`Fee::apply`]
+    fn apply(&self, amount: u64) -> Option<AftFee>;
+
+    /// See [`Fee::reverse_from_rem`]
+    fn reverse_from_rem(&self, rem: u64) -> Option<RangeInclusive<u64>>;
+
+    /// See [`Fee::reverse_from_fee`]
+    fn reverse_from_fee(&self, fee: u64) -> Option<RangeInclusive<u64>>;
+
+    /// The fee ratio, widened to `u128`s
+    fn fee_ratio_u128(&self) -> Ratio<u128, u128>;
+}
+
+impl<T: FeeApply + ?Sized> FeeApply for &T {
+    #[inline]
+    fn apply(&self, amount: u64) -> Option<AftFee> {
+        (**self).apply(amount)
+    }
+
+    #[inline]
+    fn reverse_from_rem(&self, rem: u64) -> Option<RangeInclusive<u64>> {
+        (**self).reverse_from_rem(rem)
+    }
+
+    #[inline]
+    fn reverse_from_fee(&self, fee: u64) -> Option<RangeInclusive<u64>> {
+        (**self).reverse_from_fee(fee)
+    }
+
+    #[inline]
+    fn fee_ratio_u128(&self) -> Ratio<u128, u128> {
+        (**self).fee_ratio_u128()
+    }
+}
+
+/// Implements [`FeeApply`] for `$F` by delegating to its inherent methods,
+/// given a closure-like `|$f| $ratio` that returns its fee ratio as `(n, d)`
+macro_rules! impl_fee_apply {
+    ($F:ty, |$f:ident| $ratio:expr) => {
+        impl FeeApply for $F {
+            #[inline]
+            fn apply(&self, amount: u64) -> Option<AftFee> {
+                Self::apply(self, amount)
+            }
+
+            #[inline]
+            fn reverse_from_rem(&self, rem: u64) -> Option<RangeInclusive<u64>> {
+                Self::reverse_from_rem(self, rem)
+            }
+
+            #[inline]
+            fn reverse_from_fee(&self, fee: u64) -> Option<RangeInclusive<u64>> {
+                Self::reverse_from_fee(self, fee)
+            }
+
+            #[inline]
+            fn fee_ratio_u128(&self) -> Ratio<u128, u128> {
+                let $f = self;
+                let (n, d) = $ratio;
+                Ratio { n, d }
+            }
+        }
+    };
+}
+
+macro_rules! impl_fee_apply_ratio {
+    ($N:ty, $D:ty) => {
+        impl_fee_apply!(Fee<Ceil<Ratio<$N, $D>>>, |f| {
+            let Ratio { n, d } = f.0 .0;
+            (n as u128, d as u128)
+        });
+        impl_fee_apply!(Fee<Floor<Ratio<$N, $D>>>, |f| {
+            let Ratio { n, d } = f.0 .0;
+            (n as u128, d as u128)
+        });
+        impl_fee_apply!(Fee<Rounded<Ratio<$N, $D>>>, |f| {
+            let Ratio { n, d } = f.0.ratio;
+            (n as u128, d as u128)
+        });
+    };
+}
+
+impl_fee_apply_ratio!(u8, u8);
+impl_fee_apply_ratio!(u8, u16);
+impl_fee_apply_ratio!(u8, u32);
+impl_fee_apply_ratio!(u8, u64);
+
+impl_fee_apply_ratio!(u16, u8);
+impl_fee_apply_ratio!(u16, u16);
+impl_fee_apply_ratio!(u16, u32);
+impl_fee_apply_ratio!(u16, u64);
+
+impl_fee_apply_ratio!(u32, u8);
+impl_fee_apply_ratio!(u32, u16);
+impl_fee_apply_ratio!(u32, u32);
+impl_fee_apply_ratio!(u32, u64);
+
+impl_fee_apply_ratio!(u64, u8);
+impl_fee_apply_ratio!(u64, u16);
+impl_fee_apply_ratio!(u64, u32);
+impl_fee_apply_ratio!(u64, u64);
+
+impl_fee_apply_ratio!(u8, u128);
+impl_fee_apply_ratio!(u16, u128);
+impl_fee_apply_ratio!(u32, u128);
+impl_fee_apply_ratio!(u64, u128);
+
+impl_fee_apply_ratio!(u128, u8);
+impl_fee_apply_ratio!(u128, u16);
+impl_fee_apply_ratio!(u128, u32);
+impl_fee_apply_ratio!(u128, u64);
+impl_fee_apply_ratio!(u128, u128);
+
+impl_fee_apply!(Fee<Ceil<AnyRatio>>, |f| f.0 .0.to_u128_parts());
+impl_fee_apply!(Fee<Floor<AnyRatio>>, |f| f.0 .0.to_u128_parts());
+impl_fee_apply!(AnyFee, |f| f.fee_ratio().to_u128_parts());
+
+macro_rules! impl_fee_apply_const {
+    ($R:ident) => {
+        impl<const N: u64, const D: u64> FeeApply for Fee<$R<ConstRatio<N, D>>> {
+            #[inline]
+            fn apply(&self, amount: u64) -> Option<AftFee> {
+                Self::apply(self, amount)
+            }
+
+            #[inline]
+            fn reverse_from_rem(&self, rem: u64) -> Option<RangeInclusive<u64>> {
+                Self::reverse_from_rem(self, rem)
+            }
+
+            #[inline]
+            fn reverse_from_fee(&self, fee: u64) -> Option<RangeInclusive<u64>> {
+                Self::reverse_from_fee(self, fee)
+            }
+
+            #[inline]
+            fn fee_ratio_u128(&self) -> Ratio<u128, u128> {
+                Ratio {
+                    n: N as u128,
+                    d: D as u128,
+                }
+            }
+        }
+    };
+}
+
+impl_fee_apply_const!(Ceil);
+impl_fee_apply_const!(Floor);
@@ -0,0 +1,173 @@
+//! Deserialization of types with invariants, which are checked
+//! so that invalid data is rejected at load time
+
+use borsh::{
+    io::{Error, ErrorKind, Read, Result},
+    BorshDeserialize,
+};
+
+use crate::{
+    ratio::{Ceil, Floor, Ratio},
+    AftFee, BefFee, Fee,
+};
+
+macro_rules! impl_fee_deserialize {
+    ($N:ty, $D:ty) => {
+        impl_fee_deserialize!($N, $D, Ceil);
+        impl_fee_deserialize!($N, $D, Floor);
+    };
+    ($N:ty, $D:ty, $R:ident) => {
+        /// Fails with [`ErrorKind::InvalidData`] if the fee ratio is invalid, see [`Fee::new`]
+        impl BorshDeserialize for Fee<$R<Ratio<$N, $D>>> {
+            #[inline]
+            fn deserialize_reader<Rd: Read>(reader: &mut Rd) -> Result<Self> {
+                let $R(ratio) = $R::<Ratio<$N, $D>>::deserialize_reader(reader)?;
+                Self::new(ratio).ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::InvalidData,
+                        "invalid fee ratio: must have nonzero denominator and be <= 1",
+                    )
+                })
+            }
+        }
+    };
+}
+
+impl_fee_deserialize!(u8, u8);
+impl_fee_deserialize!(u8, u16);
+impl_fee_deserialize!(u8, u32);
+impl_fee_deserialize!(u8, u64);
+impl_fee_deserialize!(u8, u128);
+
+impl_fee_deserialize!(u16, u8);
+impl_fee_deserialize!(u16, u16);
+impl_fee_deserialize!(u16, u32);
+impl_fee_deserialize!(u16, u64);
+impl_fee_deserialize!(u16, u128);
+
+impl_fee_deserialize!(u32, u8);
+impl_fee_deserialize!(u32, u16);
+impl_fee_deserialize!(u32, u32);
+impl_fee_deserialize!(u32, u64);
+impl_fee_deserialize!(u32, u128);
+
+impl_fee_deserialize!(u64, u8);
+impl_fee_deserialize!(u64, u16);
+impl_fee_deserialize!(u64, u32);
+impl_fee_deserialize!(u64, u64);
+impl_fee_deserialize!(u64, u128);
+
+impl_fee_deserialize!(u128, u8);
+impl_fee_deserialize!(u128, u16);
+impl_fee_deserialize!(u128, u32);
+impl_fee_deserialize!(u128, u64);
+impl_fee_deserialize!(u128, u128);
+
+macro_rules! impl_aft_fee_deserialize {
+    ($T:ty) => {
+        /// Fails with [`ErrorKind::InvalidData`] if `rem + fee` overflows
+        impl BorshDeserialize for AftFee<$T> {
+            #[inline]
+            fn deserialize_reader<Rd: Read>(reader: &mut Rd) -> Result<Self> {
+                let rem = <$T>::deserialize_reader(reader)?;
+                let fee = <$T>::deserialize_reader(reader)?;
+                rem.checked_add(fee)
+                    .and_then(|bef_fee| BefFee(bef_fee).with_fee(fee))
+                    .ok_or_else(|| Error::new(ErrorKind::InvalidData, "AftFee rem + fee overflows"))
+            }
+        }
+    };
+}
+
+impl_aft_fee_deserialize!(u64);
+impl_aft_fee_deserialize!(u128);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    macro_rules! test_suite {
+        ($N:ty, $D:ty, $test_mod:ident) => {
+            mod $test_mod {
+                use super::*;
+
+                proptest! {
+                    #[test]
+                    fn fee_deserialize_validates(n: $N, d: $D) {
+                        let bytes = borsh::to_vec(&Ratio::<$N, $D>::new(n, d)).unwrap();
+                        let valid = d != 0 && n as u128 <= d as u128;
+
+                        let ceil = borsh::from_slice::<Fee<Ceil<Ratio<$N, $D>>>>(&bytes);
+                        let floor = borsh::from_slice::<Fee<Floor<Ratio<$N, $D>>>>(&bytes);
+                        prop_assert_eq!(ceil.is_ok(), valid);
+                        prop_assert_eq!(floor.is_ok(), valid);
+                        if let (Ok(ceil), Ok(floor)) = (ceil, floor) {
+                            prop_assert_eq!(&borsh::to_vec(&ceil).unwrap(), &bytes);
+                            prop_assert_eq!(&borsh::to_vec(&floor).unwrap(), &bytes);
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, u8, fee_borsh_u8_u8_tests);
+    test_suite!(u8, u16, fee_borsh_u8_u16_tests);
+    test_suite!(u8, u32, fee_borsh_u8_u32_tests);
+    test_suite!(u8, u64, fee_borsh_u8_u64_tests);
+    test_suite!(u8, u128, fee_borsh_u8_u128_tests);
+    test_suite!(u16, u8, fee_borsh_u16_u8_tests);
+    test_suite!(u16, u16, fee_borsh_u16_u16_tests);
+    test_suite!(u16, u32, fee_borsh_u16_u32_tests);
+    test_suite!(u16, u64, fee_borsh_u16_u64_tests);
+    test_suite!(u16, u128, fee_borsh_u16_u128_tests);
+    test_suite!(u32, u8, fee_borsh_u32_u8_tests);
+    test_suite!(u32, u16, fee_borsh_u32_u16_tests);
+    test_suite!(u32, u32, fee_borsh_u32_u32_tests);
+    test_suite!(u32, u64, fee_borsh_u32_u64_tests);
+    test_suite!(u32, u128, fee_borsh_u32_u128_tests);
+    test_suite!(u64, u8, fee_borsh_u64_u8_tests);
+    test_suite!(u64, u16, fee_borsh_u64_u16_tests);
+    test_suite!(u64, u32, fee_borsh_u64_u32_tests);
+    test_suite!(u64, u64, fee_borsh_u64_u64_tests);
+    test_suite!(u64, u128, fee_borsh_u64_u128_tests);
+    test_suite!(u128, u8, fee_borsh_u128_u8_tests);
+    test_suite!(u128, u16, fee_borsh_u128_u16_tests);
+    test_suite!(u128, u32, fee_borsh_u128_u32_tests);
+    test_suite!(u128, u64, fee_borsh_u128_u64_tests);
+    test_suite!(u128, u128, fee_borsh_u128_u128_tests);
+
+    proptest! {
+        #[test]
+        fn aft_fee_deserialize_validates(rem: u128, fee: u128) {
+            let bytes = [rem.to_le_bytes(), fee.to_le_bytes()].concat();
+            let res = borsh::from_slice::<AftFee<u128>>(&bytes);
+            match rem.checked_add(fee) {
+                None => prop_assert_eq!(res.unwrap_err().kind(), ErrorKind::InvalidData),
+                Some(bef) => {
+                    let aft = res.unwrap();
+                    prop_assert_eq!(aft, BefFee(bef).with_fee(fee).unwrap());
+                    prop_assert_eq!(borsh::to_vec(&aft).unwrap(), bytes);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn borsh_cases() {
+        let fee: Fee<Ceil<Ratio<u8, u16>>> = borsh::from_slice(&[1, 144, 1]).unwrap();
+        assert_eq!(fee.to_inner().0, Ratio::new(1, 400));
+
+        for bytes in [[2, 1, 0], [0, 0, 0]] {
+            let err = borsh::from_slice::<Fee<Floor<Ratio<u8, u16>>>>(&bytes).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidData);
+        }
+
+        let bef: BefFee = borsh::from_slice(&u64::MAX.to_le_bytes()).unwrap();
+        assert_eq!(bef, BefFee(u64::MAX));
+        let aft: AftFee = borsh::from_slice(&[[7; 8], [0; 8]].concat()).unwrap();
+        assert_eq!(borsh::to_vec(&aft).unwrap(), [[7; 8], [0; 8]].concat());
+    }
+}
@@ -0,0 +1,146 @@
+use core::ops::RangeInclusive;
+
+use crate::{
+    ratio::{Ceil, ConstRatio, Floor, Ratio},
+    AftFee, Fee,
+};
+
+macro_rules! impl_const_fee {
+    ($R:ident) => {
+        impl<const N: u64, const D: u64> Fee<$R<ConstRatio<N, D>>> {
+            /// The runtime equivalent of this fee
+            ///
+            /// Fails to compile if `N > D` or `D == 0`
+            pub const FEE: Fee<$R<Ratio<u64, u64>>> =
+                match Fee::<$R<Ratio<u64, u64>>>::new(ConstRatio::<N, D>::RATIO) {
+                    Some(f) => f,
+                    None => panic!("fee ratio must be <= 1.0"),
+                };
+
+            /// Fails to compile if `N > D` or `D == 0`
+            #[inline]
+            pub const fn new(fee_ratio: ConstRatio<N, D>) -> Self {
+                let _ = Self::FEE;
+                Self($R(fee_ratio))
+            }
+
+            /// [`Self::FEE`]
+            #[inline]
+            pub const fn to_ratio_fee(&self) -> Fee<$R<Ratio<u64, u64>>> {
+                Self::FEE
+            }
+
+            /// See [`Fee::apply`] for [`Ratio`]s
+            #[inline]
+            pub const fn apply(&self, amount: u64) -> Option<AftFee> {
+                Self::FEE.apply(amount)
+            }
+
+            /// See [`Fee::reverse_from_rem`] for [`Ratio`]s
+            #[inline]
+            pub const fn reverse_from_rem(&self, rem: u64) -> Option<RangeInclusive<u64>> {
+                Self::FEE.reverse_from_rem(rem)
+            }
+
+            /// See [`Fee::reverse_from_fee`] for [`Ratio`]s
+            #[inline]
+            pub const fn reverse_from_fee(&self, fee: u64) -> Option<RangeInclusive<u64>> {
+                Self::FEE.reverse_from_fee(fee)
+            }
+
+            /// See [`Fee::apply_u128`] for [`Ratio`]s
+            #[inline]
+            pub const fn apply_u128(&self, amount: u128) -> Option<AftFee<u128>> {
+                Self::FEE.apply_u128(amount)
+            }
+
+            /// See [`Fee::reverse_from_rem_u128`] for [`Ratio`]s
+            #[inline]
+            pub const fn reverse_from_rem_u128(&self, rem: u128) -> Option<RangeInclusive<u128>> {
+                Self::FEE.reverse_from_rem_u128(rem)
+            }
+
+            /// See [`Fee::reverse_from_fee_u128`] for [`Ratio`]s
+            #[inline]
+            pub const fn reverse_from_fee_u128(&self, fee: u128) -> Option<RangeInclusive<u128>> {
+                Self::FEE.reverse_from_fee_u128(fee)
+            }
+
+            /// # Returns
+            /// `1.0` - self's ratio
+            #[inline]
+            pub const fn one_minus_fee_ratio(&self) -> Ratio<u64, u64> {
+                Self::FEE.one_minus_fee_ratio()
+            }
+        }
+
+        impl<const N: u64, const D: u64> From<Fee<$R<ConstRatio<N, D>>>>
+            for Fee<$R<Ratio<u64, u64>>>
+        {
+            #[inline]
+            fn from(f: Fee<$R<ConstRatio<N, D>>>) -> Self {
+                f.to_ratio_fee()
+            }
+        }
+    };
+}
+
+impl_const_fee!(Ceil);
+impl_const_fee!(Floor);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn const_fee_zero_sized() {
+        type F = Fee<Ceil<ConstRatio<1, 1_000>>>;
+        assert_eq!(core::mem::size_of::<F>(), 0);
+        assert_eq!(
+            Fee::<Ceil<Ratio<u64, u64>>>::from(F::new(ConstRatio::new())),
+            Fee::<Ceil<Ratio<u64, u64>>>::new(Ratio { n: 1, d: 1_000 }).unwrap()
+        );
+    }
+
+    macro_rules! check {
+        ($c:expr, $amt:expr, $rem:expr, $fee:expr) => {{
+            let c = $c;
+            let r = c.to_ratio_fee();
+            prop_assert_eq!(c.apply($amt), r.apply($amt));
+            prop_assert_eq!(c.reverse_from_rem($rem), r.reverse_from_rem($rem));
+            prop_assert_eq!(c.reverse_from_fee($fee), r.reverse_from_fee($fee));
+            prop_assert_eq!(c.apply_u128($amt as u128), r.apply_u128($amt as u128));
+            prop_assert_eq!(
+                c.reverse_from_rem_u128($rem as u128),
+                r.reverse_from_rem_u128($rem as u128)
+            );
+            prop_assert_eq!(
+                c.reverse_from_fee_u128($fee as u128),
+                r.reverse_from_fee_u128($fee as u128)
+            );
+            prop_assert_eq!(c.one_minus_fee_ratio(), r.one_minus_fee_ratio());
+        }};
+    }
+
+    macro_rules! test_suite {
+        ($N:expr, $D:expr, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(amt: u64, rem: u64, fee: u64) {
+                    type C = ConstRatio<{ $N }, { $D }>;
+                    check!(Fee::<Ceil<C>>::new(C::new()), amt, rem, fee);
+                    check!(Fee::<Floor<C>>::new(C::new()), amt, rem, fee);
+                }
+            }
+        };
+    }
+
+    test_suite!(0, 1, const_fee_zero);
+    test_suite!(1, 1, const_fee_one);
+    test_suite!(1, 1_000, const_fee_10_bps);
+    test_suite!(3, 7, const_fee_3_7);
+    test_suite!(1, u64::MAX, const_fee_1_max);
+    test_suite!(u64::MAX - 1, u64::MAX, const_fee_max_minus_1_max);
+}
@@ -0,0 +1,241 @@
+use crate::{
+    ratio::{Ceil, Floor, Ratio, TryFromRatioError},
+    Fee,
+};
+
+/// Conversion from `Fee<Ratio<$N1, $D1>>` where `$N2` and `$D2` are at least as wide as `$N1` and `$D1`
+macro_rules! impl_from {
+    ($N1:ty, $D1:ty, $N2:ty, $D2:ty) => {
+        impl_from!($N1, $D1, $N2, $D2, Ceil);
+        impl_from!($N1, $D1, $N2, $D2, Floor);
+    };
+    ($N1:ty, $D1:ty, $N2:ty, $D2:ty, $R:ident) => {
+        impl From<Fee<$R<Ratio<$N1, $D1>>>> for Fee<$R<Ratio<$N2, $D2>>> {
+            #[inline]
+            fn from(Fee(r): Fee<$R<Ratio<$N1, $D1>>>) -> Self {
+                // widening preserves both the value and the nonzero denominator
+                Self(r.into())
+            }
+        }
+    };
+}
+
+/// Conversion from `Fee<Ratio<$N1, $D1>>` where at least one of `$N2` and `$D2` is narrower than
+/// `$N1` and `$D1` respectively.
+macro_rules! impl_try_from {
+    ($N1:ty, $D1:ty, $N2:ty, $D2:ty) => {
+        impl_try_from!($N1, $D1, $N2, $D2, Ceil);
+        impl_try_from!($N1, $D1, $N2, $D2, Floor);
+    };
+    ($N1:ty, $D1:ty, $N2:ty, $D2:ty, $R:ident) => {
+        /// The fee ratio is converted to its lowest form, with zero fees converted to `Self::ZERO`.
+        ///
+        /// # Errors
+        ///
+        /// [`TryFromRatioError`] if the fee ratio's lowest form does not fit
+        impl TryFrom<Fee<$R<Ratio<$N1, $D1>>>> for Fee<$R<Ratio<$N2, $D2>>> {
+            type Error = TryFromRatioError;
+
+            #[inline]
+            fn try_from(Fee(r): Fee<$R<Ratio<$N1, $D1>>>) -> Result<Self, Self::Error> {
+                let r: $R<Ratio<$N2, $D2>> = r.try_into()?;
+                // lowest_form() of zero is 0/0, which violates the nonzero denominator invariant
+                Ok(if r.0.is_zero() { Self::ZERO } else { Self(r) })
+            }
+        }
+    };
+}
+
+/// Invokes `$m!($N1, $D1, $N2, $D2)` for every `$N2`, `$D2` combination
+macro_rules! for_each_target {
+    (@d $m:ident, $N1:ty, $D1:ty, $N2:ty, [$($D2:ty),*]) => {
+        $($m!($N1, $D1, $N2, $D2);)*
+    };
+    ($m:ident, $N1:ty, $D1:ty, [$($N2:ty),*], $D2s:tt) => {
+        $(for_each_target!(@d $m, $N1, $D1, $N2, $D2s);)*
+    };
+}
+
+/// Implements all conversions from `Fee<Ratio<$N1, $D1>>` given
+/// the types narrower and wider than `$N1` and `$D1`
+macro_rules! impl_conversions {
+    (
+        ($N1:ty, [$($NN:ty),*], [$($WN:ty),*]),
+        ($D1:ty, [$($ND:ty),*], [$($WD:ty),*])
+    ) => {
+        for_each_target!(impl_from, $N1, $D1, [$N1], [$($WD),*]);
+        for_each_target!(impl_from, $N1, $D1, [$($WN),*], [$D1 $(, $WD)*]);
+
+        for_each_target!(impl_try_from, $N1, $D1, [$($NN),*], [$($ND,)* $D1 $(, $WD)*]);
+        for_each_target!(impl_try_from, $N1, $D1, [$N1 $(, $WN)*], [$($ND),*]);
+    };
+}
+
+impl_conversions!(
+    (u8, [], [u16, u32, u64, u128]),
+    (u8, [], [u16, u32, u64, u128])
+);
+impl_conversions!(
+    (u8, [], [u16, u32, u64, u128]),
+    (u16, [u8], [u32, u64, u128])
+);
+impl_conversions!(
+    (u8, [], [u16, u32, u64, u128]),
+    (u32, [u8, u16], [u64, u128])
+);
+impl_conversions!(
+    (u8, [], [u16, u32, u64, u128]),
+    (u64, [u8, u16, u32], [u128])
+);
+impl_conversions!(
+    (u8, [], [u16, u32, u64, u128]),
+    (u128, [u8, u16, u32, u64], [])
+);
+
+impl_conversions!(
+    (u16, [u8], [u32, u64, u128]),
+    (u8, [], [u16, u32, u64, u128])
+);
+impl_conversions!((u16, [u8], [u32, u64, u128]), (u16, [u8], [u32, u64, u128]));
+impl_conversions!((u16, [u8], [u32, u64, u128]), (u32, [u8, u16], [u64, u128]));
+impl_conversions!((u16, [u8], [u32, u64, u128]), (u64, [u8, u16, u32], [u128]));
+impl_conversions!(
+    (u16, [u8], [u32, u64, u128]),
+    (u128, [u8, u16, u32, u64], [])
+);
+
+impl_conversions!(
+    (u32, [u8, u16], [u64, u128]),
+    (u8, [], [u16, u32, u64, u128])
+);
+impl_conversions!((u32, [u8, u16], [u64, u128]), (u16, [u8], [u32, u64, u128]));
+impl_conversions!((u32, [u8, u16], [u64, u128]), (u32, [u8, u16], [u64, u128]));
+impl_conversions!((u32, [u8, u16], [u64, u128]), (u64, [u8, u16, u32], [u128]));
+impl_conversions!(
+    (u32, [u8, u16], [u64, u128]),
+    (u128, [u8, u16, u32, u64], [])
+);
+
+impl_conversions!(
+    (u64, [u8, u16, u32], [u128]),
+    (u8, [], [u16, u32, u64, u128])
+);
+impl_conversions!((u64, [u8, u16, u32], [u128]), (u16, [u8], [u32, u64, u128]));
+impl_conversions!((u64, [u8, u16, u32], [u128]), (u32, [u8, u16], [u64, u128]));
+impl_conversions!((u64, [u8, u16, u32], [u128]), (u64, [u8, u16, u32], [u128]));
+impl_conversions!(
+    (u64, [u8, u16, u32], [u128]),
+    (u128, [u8, u16, u32, u64], [])
+);
+
+impl_conversions!(
+    (u128, [u8, u16, u32, u64], []),
+    (u8, [], [u16, u32, u64, u128])
+);
+impl_conversions!(
+    (u128, [u8, u16, u32, u64], []),
+    (u16, [u8], [u32, u64, u128])
+);
+impl_conversions!(
+    (u128, [u8, u16, u32, u64], []),
+    (u32, [u8, u16], [u64, u128])
+);
+impl_conversions!(
+    (u128, [u8, u16, u32, u64], []),
+    (u64, [u8, u16, u32], [u128])
+);
+impl_conversions!(
+    (u128, [u8, u16, u32, u64], []),
+    (u128, [u8, u16, u32, u64], [])
+);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    macro_rules! test_suite {
+        ($N:ty, $D:ty, $test_mod:ident) => {
+            mod $test_mod {
+                use super::*;
+
+                type R = Ratio<$N, $D>;
+
+                proptest! {
+                    #[test]
+                    fn conversions(d in 1..=<$D>::MAX, n: $N, k in 1..=u8::MAX) {
+                        let n = if n as u128 > d as u128 { d as $N } else { n };
+                        let ratio = R::new(n, d);
+                        let (ceil, floor) = (
+                            Fee::<Ceil<R>>::new(ratio).unwrap(),
+                            Fee::<Floor<R>>::new(ratio).unwrap(),
+                        );
+
+                        let wide_ceil: Fee<Ceil<Ratio<u128, u128>>> = ceil.into();
+                        let wide_floor: Fee<Floor<Ratio<u128, u128>>> = floor.into();
+                        prop_assert_eq!(Fee::<Ceil<R>>::try_from(wide_ceil), Ok(ceil));
+                        prop_assert_eq!(Fee::<Floor<R>>::try_from(wide_floor), Ok(floor));
+
+                        // narrowing a scaled up fee should result in a valid fee of the same value
+                        let k = if (d as u128).checked_mul(k.into()).is_some() { k.into() } else { 1 };
+                        let scaled = Ratio::<u128, u128>::new(n as u128 * k, d as u128 * k);
+                        let scaled = Fee::<Ceil<Ratio<u128, u128>>>::new(scaled).unwrap();
+                        let narrowed = Fee::<Ceil<R>>::try_from(scaled).unwrap();
+                        prop_assert_eq!(narrowed.as_inner_ref().0, ratio);
+                        prop_assert!(Fee::<Ceil<R>>::new(narrowed.as_inner_ref().0).is_some());
+
+                        for amt in [0, 1, u64::MAX / 2, u64::MAX] {
+                            prop_assert_eq!(narrowed.apply(amt), ceil.apply(amt));
+                            prop_assert_eq!(wide_floor.apply(amt), floor.apply(amt));
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, u8, fee_convert_u8_u8_tests);
+    test_suite!(u8, u16, fee_convert_u8_u16_tests);
+    test_suite!(u8, u32, fee_convert_u8_u32_tests);
+    test_suite!(u8, u64, fee_convert_u8_u64_tests);
+    test_suite!(u8, u128, fee_convert_u8_u128_tests);
+    test_suite!(u16, u8, fee_convert_u16_u8_tests);
+    test_suite!(u16, u16, fee_convert_u16_u16_tests);
+    test_suite!(u16, u32, fee_convert_u16_u32_tests);
+    test_suite!(u16, u64, fee_convert_u16_u64_tests);
+    test_suite!(u16, u128, fee_convert_u16_u128_tests);
+    test_suite!(u32, u8, fee_convert_u32_u8_tests);
+    test_suite!(u32, u16, fee_convert_u32_u16_tests);
+    test_suite!(u32, u32, fee_convert_u32_u32_tests);
+    test_suite!(u32, u64, fee_convert_u32_u64_tests);
+    test_suite!(u32, u128, fee_convert_u32_u128_tests);
+    test_suite!(u64, u8, fee_convert_u64_u8_tests);
+    test_suite!(u64, u16, fee_convert_u64_u16_tests);
+    test_suite!(u64, u32, fee_convert_u64_u32_tests);
+    test_suite!(u64, u64, fee_convert_u64_u64_tests);
+    test_suite!(u64, u128, fee_convert_u64_u128_tests);
+    test_suite!(u128, u8, fee_convert_u128_u8_tests);
+    test_suite!(u128, u16, fee_convert_u128_u16_tests);
+    test_suite!(u128, u32, fee_convert_u128_u32_tests);
+    test_suite!(u128, u64, fee_convert_u128_u64_tests);
+    test_suite!(u128, u128, fee_convert_u128_u128_tests);
+
+    #[test]
+    fn conversion_cases() {
+        let fee = Fee::<Ceil<Ratio<u64, u64>>>::new(Ratio::new(1_000, 400_000)).unwrap();
+        assert_eq!(
+            Fee::<Ceil<Ratio<u8, u16>>>::try_from(fee).map(|f| f.to_inner().0),
+            Ok(Ratio::new(1, 400))
+        );
+        assert_eq!(
+            Fee::<Ceil<Ratio<u8, u8>>>::try_from(fee),
+            Err(TryFromRatioError)
+        );
+
+        let zero = Fee::<Floor<Ratio<u64, u64>>>::new(Ratio::new(0, 1_000)).unwrap();
+        let zero_narrow = Fee::<Floor<Ratio<u8, u8>>>::try_from(zero).unwrap();
+        assert_eq!(zero_narrow, Fee::<Floor<Ratio<u8, u8>>>::ZERO);
+        assert_eq!(zero_narrow.as_inner_ref().0.d, 1);
+    }
+}
@@ -0,0 +1,259 @@
+use core::ops::RangeInclusive;
+
+use crate::{ratio::Ratio, AftFee, BefFee, FeeApply};
+
+/// The result of applying a [`FeeStack`] of `N` fees to a token amount:
+/// the remaining token amount and the fee amount levied by each fee of the stack, in order.
+///
+/// invariant: `self.rem() + sum(self.fees()) = self.bef_fee()`.
+///
+/// Fields are private to ensure invariant is never violated.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct AftFees<const N: usize> {
+    rem: u64,
+    fees: [u64; N],
+}
+
+impl<const N: usize> AftFees<N> {
+    /// The remaining token amount after all fees have been levied
+    #[inline]
+    pub const fn rem(&self) -> u64 {
+        self.rem
+    }
+
+    /// The fee amount levied by each fee of the stack, in order
+    #[inline]
+    pub const fn fees(&self) -> &[u64; N] {
+        &self.fees
+    }
+
+    /// The total fee amount levied by all fees of the stack.
+    ///
+    /// `sum(self.fees())`
+    #[inline]
+    pub const fn fee(&self) -> u64 {
+        let mut sum = 0;
+        let mut i = 0;
+        while i < N {
+            // unchecked-arith: sum <= self.bef_fee() <= u64::MAX by invariant
+            sum += self.fees[i];
+            i += 1;
+        }
+        sum
+    }
+
+    /// The original token amount before levying of fees.
+    ///
+    /// `self.rem() + self.fee()`
+    #[inline]
+    pub const fn bef_fee(&self) -> u64 {
+        self.rem + self.fee()
+    }
+
+    /// Collapses the breakdown into a single [`AftFee`] with the total fee amount
+    #[inline]
+    pub const fn to_aft_fee(&self) -> AftFee {
+        match BefFee(self.bef_fee()).with_rem(self.rem) {
+            Some(a) => a,
+            // rem <= bef_fee by invariant, so this is always Some
+            None => unreachable!(),
+        }
+    }
+}
+
+impl<const N: usize> From<AftFees<N>> for AftFee {
+    #[inline]
+    fn from(a: AftFees<N>) -> Self {
+        a.to_aft_fee()
+    }
+}
+
+/// An ordered stack of `N` fees that are levied one after another,
+/// each on the amount remaining after the previous ones,
+/// e.g. a protocol fee followed by an LP fee followed by a referral fee.
+///
+/// Use [`crate::AnyFee`] as `F` for stacks of fees of different types.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct FeeStack<F, const N: usize>(pub [F; N]);
+
+impl<F, const N: usize> FeeStack<F, N> {
+    /// Convenience constructor for better compatibility with type aliases
+    #[inline]
+    pub const fn new(fees: [F; N]) -> Self {
+        Self(fees)
+    }
+}
+
+impl<F: FeeApply, const N: usize> FeeStack<F, N> {
+    /// # Params
+    /// - `amount`: the token amount before fees
+    ///
+    /// # Returns
+    /// The fee amount levied by each fee and the final remaining amount.
+    ///
+    /// `None` on overflow
+    #[inline]
+    pub fn apply(&self, amount: u64) -> Option<AftFees<N>> {
+        let mut rem = amount;
+        let mut fees = [0; N];
+        for (fee, levied) in self.0.iter().zip(fees.iter_mut()) {
+            let aft = fee.apply(rem)?;
+            *levied = aft.fee();
+            rem = aft.rem();
+        }
+        Some(AftFees { rem, fees })
+    }
+
+    /// # Params
+    /// - `rem`: the remaining token amount after all fees were levied
+    ///
+    /// # Returns
+    /// The range of possible `amount` values that was fed into [`Self::apply`]
+    /// to output a [`AftFees`] with the same `rem`.
+    ///
+    /// Returns `None` if `rem` is greater than the remaining amount of applying to `u64::MAX`
+    #[inline]
+    pub fn reverse_from_rem(&self, rem: u64) -> Option<RangeInclusive<u64>> {
+        // The remaining amount of each fee is nondecreasing in its input
+        // and increases by at most 1 per unit of input since each fee ratio is <= 1.0,
+        // so the preimage of a range is a range, and every value up to the
+        // remaining amount of u64::MAX is attainable.
+        let mut range = rem..=rem;
+        for fee in self.0.iter().rev() {
+            let start = *fee.reverse_from_rem(*range.start())?.start();
+            // `None` means end is unattainable, so every input maps to <= end
+            let end = fee
+                .reverse_from_rem(*range.end())
+                .map_or(u64::MAX, |r| *r.end());
+            range = start..=end;
+        }
+        Some(range)
+    }
+
+    /// # Returns
+    /// The fee ratio equivalent to levying all the fees of the stack in sequence,
+    /// `1.0 - Π(1.0 - fᵢ)`, in its lowest form.
+    ///
+    /// Applying it to an amount may differ from [`Self::apply`]'s total fee
+    /// because of the rounding of each intermediate fee.
+    ///
+    /// `None` if the exact result does not fit into a `Ratio<u128, u128>`
+    #[inline]
+    pub fn combined_fee_ratio(&self) -> Option<Ratio<u128, u128>> {
+        const ONE: Ratio<u128, u128> = Ratio::<u128, u128>::ONE;
+
+        let mut rem_ratio = ONE;
+        for fee in self.0.iter() {
+            let one_minus = ONE.checked_sub(&fee.fee_ratio_u128())?;
+            rem_ratio = rem_ratio.checked_mul(&one_minus)?;
+        }
+        ONE.checked_sub(&rem_ratio)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{
+        ratio::{Ceil, Floor},
+        AnyFee, Fee,
+    };
+
+    use super::*;
+
+    prop_compose! {
+        fn fee_ratio()
+            (d in 1..=u16::MAX)
+            (n in 0..=d, d in Just(d)) -> Ratio<u16, u16> {
+                Ratio { n, d }
+            }
+    }
+
+    prop_compose! {
+        fn any_fee()
+            (r in fee_ratio(), ceil: bool) -> AnyFee {
+                if ceil {
+                    Fee::<Ceil<Ratio<u16, u16>>>::new(r).unwrap().into()
+                } else {
+                    Fee::<Floor<Ratio<u16, u16>>>::new(r).unwrap().into()
+                }
+            }
+    }
+
+    proptest! {
+        #[test]
+        fn fee_stack_apply_reverse(fees in [any_fee(), any_fee(), any_fee()], amt: u64, rem: u64) {
+            let stack = FeeStack(fees);
+            let aft = stack.apply(amt).unwrap();
+
+            // invariant and equivalence with chaining by hand
+            prop_assert_eq!(aft.bef_fee(), amt);
+            prop_assert_eq!(aft.rem() + aft.fees().iter().sum::<u64>(), amt);
+            prop_assert_eq!(aft.to_aft_fee().fee(), aft.fee());
+            let mut r = amt;
+            for (f, levied) in fees.iter().zip(aft.fees()) {
+                let a = f.apply(r).unwrap();
+                prop_assert_eq!(a.fee(), *levied);
+                r = a.rem();
+            }
+            prop_assert_eq!(r, aft.rem());
+
+            let range = stack.reverse_from_rem(aft.rem()).unwrap();
+            prop_assert!(range.contains(&amt), "{amt} {range:?}");
+            for bound in [*range.start(), *range.end()] {
+                prop_assert_eq!(stack.apply(bound).unwrap().rem(), aft.rem());
+            }
+            if let Some(before) = range.start().checked_sub(1) {
+                prop_assert!(stack.apply(before).unwrap().rem() < aft.rem());
+            }
+            if let Some(after) = range.end().checked_add(1) {
+                prop_assert!(stack.apply(after).unwrap().rem() > aft.rem());
+            }
+
+            // every rem up to that of u64::MAX is attainable
+            let max_rem = stack.apply(u64::MAX).unwrap().rem();
+            prop_assert_eq!(stack.reverse_from_rem(rem).is_some(), rem <= max_rem);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn fee_stack_combined_ratio(fees in [fee_ratio(), fee_ratio(), fee_ratio()], amt: u64) {
+            let stack = FeeStack(fees.map(|r| Fee::<Ceil<Ratio<u16, u16>>>::new(r).unwrap()));
+            let combined = stack.combined_fee_ratio().unwrap();
+
+            let rem_ratio = fees.iter().fold((1u128, 1u128), |(n, d), r| {
+                (n * u128::from(r.d - r.n), d * u128::from(r.d))
+            });
+            prop_assert_eq!(
+                combined,
+                Ratio::<u128, u128>::ONE
+                    .checked_sub(&Ratio { n: rem_ratio.0, d: rem_ratio.1 })
+                    .unwrap()
+            );
+
+            // each of the 3 roundings is off by < 1 and rounds the fee up
+            let total = stack.apply(amt).unwrap().fee();
+            let exact_floor = Floor(combined).apply(amt).unwrap();
+            prop_assert!(total >= exact_floor && total - exact_floor <= 3, "{total} {exact_floor}");
+        }
+    }
+
+    #[test]
+    fn fee_stack_edge_cases() {
+        let empty: FeeStack<Fee<Ceil<Ratio<u8, u8>>>, 0> = FeeStack([]);
+        assert_eq!(empty.apply(10).unwrap().rem(), 10);
+        assert_eq!(empty.reverse_from_rem(10), Some(10..=10));
+        assert!(empty.combined_fee_ratio().unwrap().is_zero());
+
+        let all = FeeStack([
+            Fee::<Ceil<Ratio<u8, u8>>>::new(Ratio::new(1, 2)).unwrap(),
+            Fee::<Ceil<Ratio<u8, u8>>>::ONE,
+        ]);
+        assert_eq!(all.apply(11).unwrap().fees(), &[6, 5]);
+        assert_eq!(all.reverse_from_rem(0), Some(0..=u64::MAX));
+        assert_eq!(all.reverse_from_rem(1), None);
+        assert!(all.combined_fee_ratio().unwrap().is_one());
+    }
+}
@@ -0,0 +1,230 @@
+use core::mem::size_of;
+
+use crate::{
+    ratio::{Ceil, Floor, Ratio},
+    AftFee, BefFee, Fee,
+};
+
+/// Concatenates `a` and `b`. `C` must be `A + B`
+#[inline]
+const fn concat_bytes<const A: usize, const B: usize, const C: usize>(
+    a: [u8; A],
+    b: [u8; B],
+) -> [u8; C] {
+    let mut res = [0u8; C];
+    let mut i = 0;
+    while i < A {
+        res[i] = a[i];
+        i += 1;
+    }
+    while i < C {
+        res[i] = b[i - A];
+        i += 1;
+    }
+    res
+}
+
+/// Splits `c` into its first `A` bytes and the remaining `B` bytes. `C` must be `A + B`
+#[inline]
+const fn split_bytes<const A: usize, const B: usize, const C: usize>(
+    c: [u8; C],
+) -> ([u8; A], [u8; B]) {
+    let mut a = [0u8; A];
+    let mut b = [0u8; B];
+    let mut i = 0;
+    while i < A {
+        a[i] = c[i];
+        i += 1;
+    }
+    while i < C {
+        b[i - A] = c[i];
+        i += 1;
+    }
+    (a, b)
+}
+
+macro_rules! impl_fee_le_bytes {
+    ($N:ty, $D:ty) => {
+        impl_fee_le_bytes!($N, $D, Ceil);
+        impl_fee_le_bytes!($N, $D, Floor);
+    };
+    ($N:ty, $D:ty, $R:ident) => {
+        impl Fee<$R<Ratio<$N, $D>>> {
+            /// Length of the output of [`Self::to_le_bytes`]
+            pub const LE_BYTES_LEN: usize = Ratio::<$N, $D>::LE_BYTES_LEN;
+
+            /// The fee ratio's [`Ratio::to_le_bytes`]
+            #[inline]
+            pub const fn to_le_bytes(&self) -> [u8; Self::LE_BYTES_LEN] {
+                self.0 .0.to_le_bytes()
+            }
+
+            /// Inverse of [`Self::to_le_bytes`]
+            ///
+            /// # Returns
+            ///
+            /// `None` if the decoded fee ratio is not valid, see [`Self::new`]
+            #[inline]
+            pub const fn from_le_bytes(bytes: [u8; Self::LE_BYTES_LEN]) -> Option<Self> {
+                Self::new(Ratio::<$N, $D>::from_le_bytes(bytes))
+            }
+        }
+    };
+}
+
+impl_fee_le_bytes!(u8, u8);
+impl_fee_le_bytes!(u8, u16);
+impl_fee_le_bytes!(u8, u32);
+impl_fee_le_bytes!(u8, u64);
+impl_fee_le_bytes!(u8, u128);
+
+impl_fee_le_bytes!(u16, u8);
+impl_fee_le_bytes!(u16, u16);
+impl_fee_le_bytes!(u16, u32);
+impl_fee_le_bytes!(u16, u64);
+impl_fee_le_bytes!(u16, u128);
+
+impl_fee_le_bytes!(u32, u8);
+impl_fee_le_bytes!(u32, u16);
+impl_fee_le_bytes!(u32, u32);
+impl_fee_le_bytes!(u32, u64);
+impl_fee_le_bytes!(u32, u128);
+
+impl_fee_le_bytes!(u64, u8);
+impl_fee_le_bytes!(u64, u16);
+impl_fee_le_bytes!(u64, u32);
+impl_fee_le_bytes!(u64, u64);
+impl_fee_le_bytes!(u64, u128);
+
+impl_fee_le_bytes!(u128, u8);
+impl_fee_le_bytes!(u128, u16);
+impl_fee_le_bytes!(u128, u32);
+impl_fee_le_bytes!(u128, u64);
+impl_fee_le_bytes!(u128, u128);
+
+macro_rules! impl_aft_fee_le_bytes {
+    ($T:ty) => {
+        impl AftFee<$T> {
+            /// Length of the output of [`Self::to_le_bytes`]
+            pub const LE_BYTES_LEN: usize = 2 * size_of::<$T>();
+
+            /// `rem` followed by `fee`, both in little-endian byte order
+            #[inline]
+            pub const fn to_le_bytes(&self) -> [u8; Self::LE_BYTES_LEN] {
+                concat_bytes(self.rem().to_le_bytes(), self.fee().to_le_bytes())
+            }
+
+            /// Inverse of [`Self::to_le_bytes`]
+            ///
+            /// # Returns
+            ///
+            /// `None` if `rem + fee` overflows, see [`BefFee::with_fee`]
+            #[inline]
+            pub const fn from_le_bytes(bytes: [u8; Self::LE_BYTES_LEN]) -> Option<Self> {
+                let (rem, fee) = split_bytes(bytes);
+                let rem = <$T>::from_le_bytes(rem);
+                let fee = <$T>::from_le_bytes(fee);
+                let bef_fee = match rem.checked_add(fee) {
+                    None => return None,
+                    Some(b) => b,
+                };
+                BefFee(bef_fee).with_fee(fee)
+            }
+        }
+    };
+}
+
+impl_aft_fee_le_bytes!(u64);
+impl_aft_fee_le_bytes!(u128);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    macro_rules! test_suite {
+        ($N:ty, $D:ty, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(n: $N, d: $D) {
+                    type R = Ratio<$N, $D>;
+
+                    let r = R::new(n, d);
+                    let bytes = r.to_le_bytes();
+                    let ceil = Fee::<Ceil<R>>::from_le_bytes(bytes);
+                    let floor = Fee::<Floor<R>>::from_le_bytes(bytes);
+                    prop_assert_eq!(ceil, Fee::<Ceil<R>>::new(r));
+                    prop_assert_eq!(floor, Fee::<Floor<R>>::new(r));
+                    if let (Some(ceil), Some(floor)) = (ceil, floor) {
+                        prop_assert_eq!(ceil.to_le_bytes(), bytes);
+                        prop_assert_eq!(floor.to_le_bytes(), bytes);
+                    }
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, u8, fee_le_bytes_u8_u8);
+    test_suite!(u8, u16, fee_le_bytes_u8_u16);
+    test_suite!(u8, u32, fee_le_bytes_u8_u32);
+    test_suite!(u8, u64, fee_le_bytes_u8_u64);
+    test_suite!(u8, u128, fee_le_bytes_u8_u128);
+    test_suite!(u16, u8, fee_le_bytes_u16_u8);
+    test_suite!(u16, u16, fee_le_bytes_u16_u16);
+    test_suite!(u16, u32, fee_le_bytes_u16_u32);
+    test_suite!(u16, u64, fee_le_bytes_u16_u64);
+    test_suite!(u16, u128, fee_le_bytes_u16_u128);
+    test_suite!(u32, u8, fee_le_bytes_u32_u8);
+    test_suite!(u32, u16, fee_le_bytes_u32_u16);
+    test_suite!(u32, u32, fee_le_bytes_u32_u32);
+    test_suite!(u32, u64, fee_le_bytes_u32_u64);
+    test_suite!(u32, u128, fee_le_bytes_u32_u128);
+    test_suite!(u64, u8, fee_le_bytes_u64_u8);
+    test_suite!(u64, u16, fee_le_bytes_u64_u16);
+    test_suite!(u64, u32, fee_le_bytes_u64_u32);
+    test_suite!(u64, u64, fee_le_bytes_u64_u64);
+    test_suite!(u64, u128, fee_le_bytes_u64_u128);
+    test_suite!(u128, u8, fee_le_bytes_u128_u8);
+    test_suite!(u128, u16, fee_le_bytes_u128_u16);
+    test_suite!(u128, u32, fee_le_bytes_u128_u32);
+    test_suite!(u128, u64, fee_le_bytes_u128_u64);
+    test_suite!(u128, u128, fee_le_bytes_u128_u128);
+
+    macro_rules! aft_fee_test {
+        ($T:ty, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(rem: $T, fee: $T) {
+                    let bytes: [u8; AftFee::<$T>::LE_BYTES_LEN] =
+                        [rem.to_le_bytes(), fee.to_le_bytes()].concat().try_into().unwrap();
+                    let aft = AftFee::<$T>::from_le_bytes(bytes);
+                    match rem.checked_add(fee) {
+                        None => prop_assert_eq!(aft, None),
+                        Some(bef) => {
+                            let aft = aft.unwrap();
+                            prop_assert_eq!(aft, BefFee(bef).with_fee(fee).unwrap());
+                            prop_assert_eq!(aft.to_le_bytes(), bytes);
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    aft_fee_test!(u64, aft_fee_le_bytes_u64);
+    aft_fee_test!(u128, aft_fee_le_bytes_u128);
+
+    #[test]
+    fn le_bytes_const() {
+        const FEE: Option<Fee<Ceil<Ratio<u8, u16>>>> =
+            Fee::<Ceil<Ratio<u8, u16>>>::from_le_bytes([1, 144, 1]);
+        const INVALID: Option<Fee<Ceil<Ratio<u8, u16>>>> =
+            Fee::<Ceil<Ratio<u8, u16>>>::from_le_bytes([2, 1, 0]);
+        const AFT: Option<AftFee> = AftFee::<u64>::from_le_bytes([1; 16]);
+
+        assert_eq!(FEE.unwrap().to_le_bytes(), [1, 144, 1]);
+        assert!(INVALID.is_none());
+        assert_eq!(AFT.unwrap().to_le_bytes(), [1; 16]);
+    }
+}
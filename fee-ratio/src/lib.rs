@@ -1,6 +1,9 @@
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 use core::{
     borrow::Borrow,
     fmt::{Display, Formatter},
@@ -13,8 +16,36 @@ pub mod ratio {
 }
 
 mod aft_bef_fee;
+mod any_fee;
+mod apply;
+mod const_fee;
+mod convert;
+mod fee_stack;
+mod le_bytes;
+mod macros;
+mod overflow;
+mod rounding;
+mod split;
+mod try_fee;
+
+#[cfg(feature = "borsh")]
+mod borsh_impls;
+
+#[cfg(feature = "bytemuck")]
+mod pod;
+
+#[cfg(feature = "serde")]
+mod serde_impls;
+
+#[cfg(feature = "serde")]
+pub mod serde_str;
 
 pub use aft_bef_fee::*;
+pub use any_fee::*;
+pub use apply::*;
+pub use fee_stack::*;
+pub use rounding::*;
+pub use split::*;
 
 use ratio::*;
 
@@ -25,6 +56,9 @@ use ratio::*;
 /// Fields are private to enforce this invariant
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Fee<D>(D);
 
 /// Displayed as `Fee({self.0})`
@@ -140,6 +174,57 @@ macro_rules! impl_fee_ratio {
                 }
             }
 
+            /// [`Self::apply`] for `u128` amounts
+            ///
+            /// # Params
+            /// - `amount`: the token amount before fees
+            ///
+            /// # Returns
+            /// `None` on overflow
+            #[inline]
+            pub const fn apply_u128(&self, amount: u128) -> Option<AftFee<u128>> {
+                let fee = match self.0.apply_u128(amount) {
+                    None => return None,
+                    Some(f) => f,
+                };
+                BefFee(amount).with_fee(fee)
+            }
+
+            /// [`Self::reverse_from_rem`] for `u128` amounts
+            ///
+            /// # Params
+            /// - `rem`: the remaining token amount after fees were levied
+            ///
+            /// # Returns
+            /// The range of possible `amount` values that was fed into [`Self::apply_u128`]
+            /// to output a [`AftFee`] with the same `rem`
+            #[inline]
+            pub const fn reverse_from_rem_u128(&self, rem: u128) -> Option<RangeInclusive<u128>> {
+                if self.0 .0.is_zero() {
+                    Some(rem..=rem)
+                } else {
+                    Floor(self.one_minus_fee_ratio()).reverse_u128(rem)
+                }
+            }
+
+            /// [`Self::reverse_from_fee`] for `u128` amounts
+            ///
+            /// # Params
+            /// - `fee`: the fee amount that was levied
+            ///
+            /// # Returns
+            /// The range of possible `amount` values that was fed into [`Self::apply_u128`]
+            /// to output a [`AftFee`] with the same `fee`
+            #[inline]
+            pub const fn reverse_from_fee_u128(&self, fee: u128) -> Option<RangeInclusive<u128>> {
+                let Self(r) = self;
+                if r.0.is_one() {
+                    Some(fee..=fee)
+                } else {
+                    r.reverse_u128(fee)
+                }
+            }
+
             /// # Returns
             /// `1.0` - self's ratio
             #[inline]
@@ -230,6 +315,57 @@ macro_rules! impl_fee_ratio {
                 }
             }
 
+            /// [`Self::apply`] for `u128` amounts
+            ///
+            /// # Params
+            /// - `amount`: the token amount before fees
+            ///
+            /// # Returns
+            /// `None` on overflow
+            #[inline]
+            pub const fn apply_u128(&self, amount: u128) -> Option<AftFee<u128>> {
+                let fee = match self.0.apply_u128(amount) {
+                    None => return None,
+                    Some(f) => f,
+                };
+                BefFee(amount).with_fee(fee)
+            }
+
+            /// [`Self::reverse_from_rem`] for `u128` amounts
+            ///
+            /// # Params
+            /// - `rem`: the remaining token amount after fees were levied
+            ///
+            /// # Returns
+            /// The range of possible `amount` values that was fed into [`Self::apply_u128`]
+            /// to output a [`AftFee`] with the same `rem`
+            #[inline]
+            pub const fn reverse_from_rem_u128(&self, rem: u128) -> Option<RangeInclusive<u128>> {
+                if self.0 .0.is_zero() {
+                    Some(rem..=rem)
+                } else {
+                    Ceil(self.one_minus_fee_ratio()).reverse_u128(rem)
+                }
+            }
+
+            /// [`Self::reverse_from_fee`] for `u128` amounts
+            ///
+            /// # Params
+            /// - `fee`: the fee amount that was levied
+            ///
+            /// # Returns
+            /// The range of possible `amount` values that was fed into [`Self::apply_u128`]
+            /// to output a [`AftFee`] with the same `fee`
+            #[inline]
+            pub const fn reverse_from_fee_u128(&self, fee: u128) -> Option<RangeInclusive<u128>> {
+                let Self(r) = self;
+                if r.0.is_one() {
+                    Some(fee..=fee)
+                } else {
+                    r.reverse_u128(fee)
+                }
+            }
+
             /// # Returns
             /// `1.0` - self's ratio
             #[inline]
@@ -272,6 +408,17 @@ impl_fee_ratio!(u64, u16);
 impl_fee_ratio!(u64, u32);
 impl_fee_ratio!(u64, u64);
 
+impl_fee_ratio!(u8, u128);
+impl_fee_ratio!(u16, u128);
+impl_fee_ratio!(u32, u128);
+impl_fee_ratio!(u64, u128);
+
+impl_fee_ratio!(u128, u8);
+impl_fee_ratio!(u128, u16);
+impl_fee_ratio!(u128, u32);
+impl_fee_ratio!(u128, u64);
+impl_fee_ratio!(u128, u128);
+
 #[cfg(test)]
 mod tests {
     use proptest::prelude::*;
@@ -558,4 +705,98 @@ mod tests {
     test_suite!(u64, u16, fee_tests_u64_u16);
     test_suite!(u64, u32, fee_tests_u64_u32);
     test_suite!(u64, u64, fee_tests_u64_u64);
+
+    macro_rules! test_suite_u128 {
+        (
+            $N:ty, $D:ty,
+            $test:ident
+        ) => {
+            proptest! {
+                #[test]
+                fn $test(
+                    d in 1..=<$D>::MAX,
+                    n: $N,
+                    bef in prop_oneof![any::<u128>(), any::<u64>().prop_map(u128::from)],
+                ) {
+                    type Max = <Ratio<$N, $D> as ArithTypes>::Max;
+
+                    let n = if n as Max > d as Max { d as $N } else { n };
+                    let ratio = Ratio::<$N, $D>::new(n, d);
+                    let floor = Fee::<Floor<Ratio<$N, $D>>>::new(ratio).unwrap();
+                    let ceil = Fee::<Ceil<Ratio<$N, $D>>>::new(ratio).unwrap();
+
+                    let floor_aaf = floor.apply_u128(bef).unwrap();
+                    let ceil_aaf = ceil.apply_u128(bef).unwrap();
+                    for aaf in [floor_aaf, ceil_aaf] {
+                        prop_assert_eq!(aaf.bef_fee(), bef);
+                    }
+                    // floor's fee should be smaller than ceil's by at most 1
+                    prop_assert!(ceil_aaf.fee() - floor_aaf.fee() <= 1);
+
+                    // u64 fns should agree with the u128 fns
+                    if let Ok(bef) = u64::try_from(bef) {
+                        let floor_aaf_u64 = floor.apply(bef).unwrap();
+                        prop_assert_eq!(u128::from(floor_aaf_u64.fee()), floor_aaf.fee());
+                        let ceil_aaf_u64 = ceil.apply(bef).unwrap();
+                        prop_assert_eq!(u128::from(ceil_aaf_u64.fee()), ceil_aaf.fee());
+                    }
+
+                    // round-trips
+                    for (rev_rem, rev_fee, aaf, apply) in [
+                        (
+                            floor.reverse_from_rem_u128(floor_aaf.rem()).unwrap(),
+                            floor.reverse_from_fee_u128(floor_aaf.fee()).unwrap(),
+                            floor_aaf,
+                            &(|x| floor.apply_u128(x).unwrap()) as &dyn Fn(u128) -> AftFee<u128>,
+                        ),
+                        (
+                            ceil.reverse_from_rem_u128(ceil_aaf.rem()).unwrap(),
+                            ceil.reverse_from_fee_u128(ceil_aaf.fee()).unwrap(),
+                            ceil_aaf,
+                            &(|x| ceil.apply_u128(x).unwrap()) as &dyn Fn(u128) -> AftFee<u128>,
+                        ),
+                    ] {
+                        prop_assert!(rev_rem.contains(&bef));
+                        prop_assert!(rev_fee.contains(&bef));
+                        for bound in [*rev_rem.start(), *rev_rem.end()] {
+                            prop_assert_eq!(apply(bound).rem(), aaf.rem());
+                        }
+                        for bound in [*rev_fee.start(), *rev_fee.end()] {
+                            prop_assert_eq!(apply(bound).fee(), aaf.fee());
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    test_suite_u128!(u8, u8, fee_tests_u128_u8_u8);
+    test_suite_u128!(u8, u16, fee_tests_u128_u8_u16);
+    test_suite_u128!(u8, u32, fee_tests_u128_u8_u32);
+    test_suite_u128!(u8, u64, fee_tests_u128_u8_u64);
+    test_suite_u128!(u8, u128, fee_tests_u128_u8_u128);
+
+    test_suite_u128!(u16, u8, fee_tests_u128_u16_u8);
+    test_suite_u128!(u16, u16, fee_tests_u128_u16_u16);
+    test_suite_u128!(u16, u32, fee_tests_u128_u16_u32);
+    test_suite_u128!(u16, u64, fee_tests_u128_u16_u64);
+    test_suite_u128!(u16, u128, fee_tests_u128_u16_u128);
+
+    test_suite_u128!(u32, u8, fee_tests_u128_u32_u8);
+    test_suite_u128!(u32, u16, fee_tests_u128_u32_u16);
+    test_suite_u128!(u32, u32, fee_tests_u128_u32_u32);
+    test_suite_u128!(u32, u64, fee_tests_u128_u32_u64);
+    test_suite_u128!(u32, u128, fee_tests_u128_u32_u128);
+
+    test_suite_u128!(u64, u8, fee_tests_u128_u64_u8);
+    test_suite_u128!(u64, u16, fee_tests_u128_u64_u16);
+    test_suite_u128!(u64, u32, fee_tests_u128_u64_u32);
+    test_suite_u128!(u64, u64, fee_tests_u128_u64_u64);
+    test_suite_u128!(u64, u128, fee_tests_u128_u64_u128);
+
+    test_suite_u128!(u128, u8, fee_tests_u128_u128_u8);
+    test_suite_u128!(u128, u16, fee_tests_u128_u128_u16);
+    test_suite_u128!(u128, u32, fee_tests_u128_u128_u32);
+    test_suite_u128!(u128, u64, fee_tests_u128_u128_u64);
+    test_suite_u128!(u128, u128, fee_tests_u128_u128_u128);
 }
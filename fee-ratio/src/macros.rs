@@ -0,0 +1,87 @@
+/// Expands to a const `Fee<$R<Ratio<..>>>` of the narrowest ratio types
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fee {
+    ($R:ident, $($t:tt)+) => {{
+        const PARTS: (u128, u128) = $crate::ratio::__ratio_parts!($($t)+);
+        type N = <$crate::ratio::macros::Width<{ $crate::ratio::macros::width(PARTS.0) }>
+            as $crate::ratio::macros::Uint>::T;
+        type D = <$crate::ratio::macros::Width<{ $crate::ratio::macros::width(PARTS.1) }>
+            as $crate::ratio::macros::Uint>::T;
+        const FEE: $crate::Fee<$crate::ratio::$R<$crate::ratio::Ratio<N, D>>> =
+            match $crate::Fee::<$crate::ratio::$R<$crate::ratio::Ratio<N, D>>>::new(
+                $crate::ratio::Ratio {
+                    n: PARTS.0 as N,
+                    d: PARTS.1 as D,
+                },
+            ) {
+                Some(f) => f,
+                None => panic!("fee ratio must be <= 1.0"),
+            };
+        FEE
+    }};
+}
+
+/// Creates a [`Fee`](crate::Fee) in const context, with the narrowest
+/// numerator and denominator types that can hold its ratio.
+///
+/// The first argument is the rounding mode of the fee, `ceil` or `floor`.
+/// The rest is the fee ratio, in any of the forms accepted by
+/// [`ratio!`](crate::ratio::ratio).
+///
+/// Fails to compile if the fee ratio is > 1.0 or its denominator is zero.
+///
+/// ```rust
+/// use sanctum_fee_ratio::{fee, Fee, ratio::{Ceil, Floor, Ratio}};
+///
+/// const FEE: Fee<Ceil<Ratio<u8, u16>>> = fee!(ceil, 25 bps);
+///
+/// assert_eq!(FEE, Fee::<Ceil<Ratio<u8, u16>>>::new(Ratio { n: 1, d: 400 }).unwrap());
+/// assert_eq!(FEE.to_inner().0, fee!(floor, 1 / 400).to_inner().0);
+/// ```
+///
+/// ```compile_fail
+/// use sanctum_fee_ratio::fee;
+///
+/// let over_one = fee!(ceil, 101 %);
+/// ```
+///
+/// ```compile_fail
+/// use sanctum_fee_ratio::fee;
+///
+/// let zero_denom = fee!(floor, 0 / 0);
+/// ```
+#[macro_export]
+macro_rules! fee {
+    (ceil, $($t:tt)+) => {
+        $crate::__fee!(Ceil, $($t)+)
+    };
+    (floor, $($t:tt)+) => {
+        $crate::__fee!(Floor, $($t)+)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ratio::{Ceil, Floor, Ratio},
+        Fee,
+    };
+
+    #[test]
+    fn fee_macro_narrowest() {
+        const A: Fee<Ceil<Ratio<u8, u16>>> = fee!(ceil, 25 bps);
+        const B: Fee<Floor<Ratio<u8, u16>>> = fee!(floor, 0.25 %);
+        assert_eq!(A.to_inner().0, Ratio { n: 1, d: 400 });
+        assert_eq!(B.to_inner().0, Ratio { n: 1, d: 400 });
+
+        let f: Fee<Ceil<Ratio<u8, u8>>> = fee!(ceil, 100 %);
+        assert_eq!(f, Fee::<Ceil<Ratio<u8, u8>>>::ONE);
+        let f: Fee<Floor<Ratio<u8, u8>>> = fee!(floor, 0 / 1);
+        assert_eq!(f, Fee::<Floor<Ratio<u8, u8>>>::ZERO);
+        let f: Fee<Ceil<Ratio<u16, u16>>> = fee!(ceil, 300 / 1_000);
+        assert_eq!(f.to_inner().0, Ratio { n: 300, d: 1_000 });
+        let f: Fee<Floor<Ratio<u8, u8>>> = fee!(floor, 0.5);
+        assert_eq!(f.to_inner().0, Ratio { n: 1, d: 2 });
+    }
+}
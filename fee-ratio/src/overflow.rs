@@ -0,0 +1,124 @@
+use crate::{
+    ratio::{Ceil, Floor, Ratio},
+    AftFee, Fee,
+};
+
+/// Since fee ratios are `<= 1.0`, `fee <= amount` and application never overflows,
+/// so the wide application of a `u64` amount can be infallible.
+macro_rules! impl_overflow_policies {
+    ($R:ident, $N:ty, $D:ty) => {
+        impl Fee<$R<Ratio<$N, $D>>> {
+            /// [`Self::apply_u128`] for `u64` amounts, but infallible
+            #[inline]
+            pub const fn apply_wide(&self, amount: u64) -> AftFee<u128> {
+                match self.apply_u128(amount as u128) {
+                    Some(a) => a,
+                    // fee ratio <= 1.0 so fee <= amount, so this is always Some
+                    None => unreachable!(),
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_all {
+    ($N:ty, $D:ty) => {
+        impl_overflow_policies!(Ceil, $N, $D);
+        impl_overflow_policies!(Floor, $N, $D);
+    };
+}
+
+impl_all!(u8, u8);
+impl_all!(u8, u16);
+impl_all!(u8, u32);
+impl_all!(u8, u64);
+
+impl_all!(u16, u8);
+impl_all!(u16, u16);
+impl_all!(u16, u32);
+impl_all!(u16, u64);
+
+impl_all!(u32, u8);
+impl_all!(u32, u16);
+impl_all!(u32, u32);
+impl_all!(u32, u64);
+
+impl_all!(u64, u8);
+impl_all!(u64, u16);
+impl_all!(u64, u32);
+impl_all!(u64, u64);
+
+impl_all!(u8, u128);
+impl_all!(u16, u128);
+impl_all!(u32, u128);
+impl_all!(u64, u128);
+
+impl_all!(u128, u8);
+impl_all!(u128, u16);
+impl_all!(u128, u32);
+impl_all!(u128, u64);
+impl_all!(u128, u128);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    macro_rules! check_overflow_policies {
+        ($fee:expr, $amt:expr) => {{
+            let f = $fee;
+            let aft = f.apply($amt).unwrap();
+            let wide = f.apply_wide($amt);
+            prop_assert_eq!(wide.rem(), aft.rem() as u128);
+            prop_assert_eq!(wide.fee(), aft.fee() as u128);
+        }};
+    }
+
+    macro_rules! test_suite {
+        ($N:ty, $D:ty, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(n: $N, d: $D, amt: u64) {
+                    let ratio = Ratio::<$N, $D> { n, d };
+                    if let Some(f) = Fee::<Ceil<Ratio<$N, $D>>>::new(ratio) {
+                        check_overflow_policies!(f, amt);
+                    }
+                    if let Some(f) = Fee::<Floor<Ratio<$N, $D>>>::new(ratio) {
+                        check_overflow_policies!(f, amt);
+                    }
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, u8, fee_overflow_policies_u8_u8);
+    test_suite!(u8, u16, fee_overflow_policies_u8_u16);
+    test_suite!(u8, u32, fee_overflow_policies_u8_u32);
+    test_suite!(u8, u64, fee_overflow_policies_u8_u64);
+    test_suite!(u8, u128, fee_overflow_policies_u8_u128);
+
+    test_suite!(u16, u8, fee_overflow_policies_u16_u8);
+    test_suite!(u16, u16, fee_overflow_policies_u16_u16);
+    test_suite!(u16, u32, fee_overflow_policies_u16_u32);
+    test_suite!(u16, u64, fee_overflow_policies_u16_u64);
+    test_suite!(u16, u128, fee_overflow_policies_u16_u128);
+
+    test_suite!(u32, u8, fee_overflow_policies_u32_u8);
+    test_suite!(u32, u16, fee_overflow_policies_u32_u16);
+    test_suite!(u32, u32, fee_overflow_policies_u32_u32);
+    test_suite!(u32, u64, fee_overflow_policies_u32_u64);
+    test_suite!(u32, u128, fee_overflow_policies_u32_u128);
+
+    test_suite!(u64, u8, fee_overflow_policies_u64_u8);
+    test_suite!(u64, u16, fee_overflow_policies_u64_u16);
+    test_suite!(u64, u32, fee_overflow_policies_u64_u32);
+    test_suite!(u64, u64, fee_overflow_policies_u64_u64);
+    test_suite!(u64, u128, fee_overflow_policies_u64_u128);
+
+    test_suite!(u128, u8, fee_overflow_policies_u128_u8);
+    test_suite!(u128, u16, fee_overflow_policies_u128_u16);
+    test_suite!(u128, u32, fee_overflow_policies_u128_u32);
+    test_suite!(u128, u64, fee_overflow_policies_u128_u64);
+    test_suite!(u128, u128, fee_overflow_policies_u128_u128);
+}
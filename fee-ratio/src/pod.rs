@@ -0,0 +1,94 @@
+//! [`bytemuck`] impls for zero-copy (de)serialization.
+//!
+//! [`Fee`]s over ratios whose `N` and `D` are the same type are [`NoUninit`] and
+//! [`CheckedBitPattern`], which checks the fee ratio's validity when casting from raw bytes.
+
+use bytemuck::{checked::try_cast_ref, CheckedBitPattern, NoUninit};
+
+use crate::{
+    ratio::{Ceil, Floor, Ratio},
+    Fee,
+};
+
+macro_rules! impl_pod {
+    ($T:ty) => {
+        impl_pod!($T, Ceil);
+        impl_pod!($T, Floor);
+    };
+    ($T:ty, $R:ident) => {
+        // Safety: repr(transparent) over a Pod Ratio
+        unsafe impl NoUninit for Fee<$R<Ratio<$T, $T>>> {}
+
+        // Safety: repr(transparent) over Bits
+        unsafe impl CheckedBitPattern for Fee<$R<Ratio<$T, $T>>> {
+            type Bits = $R<Ratio<$T, $T>>;
+
+            #[inline]
+            fn is_valid_bit_pattern($R(ratio): &Self::Bits) -> bool {
+                Self::new(*ratio).is_some()
+            }
+        }
+
+        impl Fee<$R<Ratio<$T, $T>>> {
+            /// Casts a reference to a raw fee ratio, e.g. one in zero-copy account data,
+            /// to a reference to a fee.
+            ///
+            /// # Returns
+            ///
+            /// `None` if the fee ratio is not valid, see [`Self::new`]
+            #[inline]
+            pub fn try_from_pod(ratio: &Ratio<$T, $T>) -> Option<&Self> {
+                try_cast_ref(ratio).ok()
+            }
+        }
+    };
+}
+
+impl_pod!(u8);
+impl_pod!(u16);
+impl_pod!(u32);
+impl_pod!(u64);
+impl_pod!(u128);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    macro_rules! test_suite {
+        ($T:ty, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(n: $T, d: $T) {
+                    type R = Ratio<$T, $T>;
+
+                    let r = R::new(n, d);
+                    let valid = d != 0 && n <= d;
+
+                    let ceil = Fee::<Ceil<R>>::try_from_pod(&r);
+                    let floor = Fee::<Floor<R>>::try_from_pod(&r);
+                    prop_assert_eq!(ceil.is_some(), valid);
+                    prop_assert_eq!(floor.is_some(), valid);
+
+                    let bytes = bytemuck::bytes_of(&r);
+                    let ceil_from_bytes = bytemuck::checked::try_from_bytes::<Fee<Ceil<R>>>(bytes);
+                    prop_assert_eq!(ceil_from_bytes.ok(), ceil);
+
+                    if let (Some(ceil), Some(floor)) = (ceil, floor) {
+                        prop_assert_eq!(*ceil, Fee::<Ceil<R>>::new(r).unwrap());
+                        prop_assert_eq!(*floor, Fee::<Floor<R>>::new(r).unwrap());
+                        prop_assert_eq!(bytemuck::bytes_of(ceil), bytes);
+                        prop_assert_eq!(bytemuck::bytes_of(floor), bytes);
+                    }
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, try_from_pod_u8);
+    test_suite!(u16, try_from_pod_u16);
+    test_suite!(u32, try_from_pod_u32);
+    test_suite!(u64, try_from_pod_u64);
+    test_suite!(u128, try_from_pod_u128);
+}
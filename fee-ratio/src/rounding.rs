@@ -0,0 +1,294 @@
+use core::{
+    fmt::{Display, Formatter},
+    ops::RangeInclusive,
+};
+
+use crate::{
+    ratio::{ArithTypes, Ceil, Floor, Ratio},
+    AftFee, Fee,
+};
+
+/// Which party a [`Fee`]'s rounding favours
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rounding {
+    /// Fee amounts are rounded up, same as `Fee<Ceil<_>>`
+    FavorProtocol,
+
+    /// Fee amounts are rounded down, same as `Fee<Floor<_>>`
+    FavorUser,
+}
+
+/// Displayed as `FavorProtocol` or `FavorUser`
+impl Display for Rounding {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::FavorProtocol => "FavorProtocol",
+            Self::FavorUser => "FavorUser",
+        })
+    }
+}
+
+/// A ratio `R` whose rounding direction is selected at runtime by a [`Rounding`] policy.
+///
+/// Must use with [`Fee`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Rounded<R> {
+    pub ratio: R,
+    pub rounding: Rounding,
+}
+
+/// Displayed as `{self.rounding}({self.ratio})`
+impl<R: Display> Display for Rounded<R> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!("{}({})", self.rounding, self.ratio))
+    }
+}
+
+impl<R: Copy> Fee<Rounded<R>> {
+    #[inline]
+    pub const fn rounding(&self) -> Rounding {
+        self.0.rounding
+    }
+}
+
+/// Evaluates `$e` with `$f` bound to the typed fee that `$self`'s rounding policy corresponds to
+macro_rules! dispatch {
+    ($self:expr, $f:ident => $e:expr) => {{
+        let Rounded { ratio, rounding } = $self.0;
+        match rounding {
+            Rounding::FavorProtocol => {
+                let $f = Fee(Ceil(ratio));
+                $e
+            }
+            Rounding::FavorUser => {
+                let $f = Fee(Floor(ratio));
+                $e
+            }
+        }
+    }};
+}
+
+macro_rules! impl_rounded_fee {
+    ($N:ty, $D:ty) => {
+        impl Fee<Rounded<Ratio<$N, $D>>> {
+            /// # Returns
+            /// - `None` if `fee_ratio` is not valid (`>1.0`)
+            /// - `None` if `fee_ratio`'s `denominator = 0`.
+            ///
+            /// See [`Fee::new`] for typed rounding modes
+            #[inline]
+            pub const fn new(fee_ratio: Ratio<$N, $D>, rounding: Rounding) -> Option<Self> {
+                match Fee::<Ceil<Ratio<$N, $D>>>::new(fee_ratio) {
+                    None => None,
+                    Some(_) => Some(Self(Rounded {
+                        ratio: fee_ratio,
+                        rounding,
+                    })),
+                }
+            }
+
+            /// # Safety
+            /// - `fee_ratio` must be valid (`<= 1.0`)
+            #[inline]
+            pub const unsafe fn new_unchecked(fee_ratio: Ratio<$N, $D>, rounding: Rounding) -> Self {
+                Self(Rounded {
+                    ratio: fee_ratio,
+                    rounding,
+                })
+            }
+
+            /// This fee with a different rounding policy
+            #[inline]
+            pub const fn with_rounding(self, rounding: Rounding) -> Self {
+                Self(Rounded {
+                    ratio: self.0.ratio,
+                    rounding,
+                })
+            }
+
+            /// See [`Fee::apply`] for typed rounding modes
+            #[inline]
+            pub const fn apply(&self, amount: u64) -> Option<AftFee> {
+                dispatch!(self, f => f.apply(amount))
+            }
+
+            /// See [`Fee::reverse_from_rem`] for typed rounding modes
+            #[inline]
+            pub const fn reverse_from_rem(&self, rem: u64) -> Option<RangeInclusive<u64>> {
+                dispatch!(self, f => f.reverse_from_rem(rem))
+            }
+
+            /// See [`Fee::reverse_from_fee`] for typed rounding modes
+            #[inline]
+            pub const fn reverse_from_fee(&self, fee: u64) -> Option<RangeInclusive<u64>> {
+                dispatch!(self, f => f.reverse_from_fee(fee))
+            }
+
+            /// See [`Fee::apply_u128`] for typed rounding modes
+            #[inline]
+            pub const fn apply_u128(&self, amount: u128) -> Option<AftFee<u128>> {
+                dispatch!(self, f => f.apply_u128(amount))
+            }
+
+            /// See [`Fee::reverse_from_rem_u128`] for typed rounding modes
+            #[inline]
+            pub const fn reverse_from_rem_u128(&self, rem: u128) -> Option<RangeInclusive<u128>> {
+                dispatch!(self, f => f.reverse_from_rem_u128(rem))
+            }
+
+            /// See [`Fee::reverse_from_fee_u128`] for typed rounding modes
+            #[inline]
+            pub const fn reverse_from_fee_u128(&self, fee: u128) -> Option<RangeInclusive<u128>> {
+                dispatch!(self, f => f.reverse_from_fee_u128(fee))
+            }
+
+            /// # Returns
+            /// `1.0` - self's ratio
+            #[inline]
+            pub const fn one_minus_fee_ratio(
+                &self,
+            ) -> Ratio<<Ratio<$N, $D> as ArithTypes>::Max, <Ratio<$N, $D> as ArithTypes>::Max> {
+                Fee(Ceil(self.0.ratio)).one_minus_fee_ratio()
+            }
+        }
+
+        impl From<Fee<Ceil<Ratio<$N, $D>>>> for Fee<Rounded<Ratio<$N, $D>>> {
+            #[inline]
+            fn from(Fee(Ceil(ratio)): Fee<Ceil<Ratio<$N, $D>>>) -> Self {
+                Self(Rounded {
+                    ratio,
+                    rounding: Rounding::FavorProtocol,
+                })
+            }
+        }
+
+        impl From<Fee<Floor<Ratio<$N, $D>>>> for Fee<Rounded<Ratio<$N, $D>>> {
+            #[inline]
+            fn from(Fee(Floor(ratio)): Fee<Floor<Ratio<$N, $D>>>) -> Self {
+                Self(Rounded {
+                    ratio,
+                    rounding: Rounding::FavorUser,
+                })
+            }
+        }
+    };
+}
+
+impl_rounded_fee!(u8, u8);
+impl_rounded_fee!(u8, u16);
+impl_rounded_fee!(u8, u32);
+impl_rounded_fee!(u8, u64);
+
+impl_rounded_fee!(u16, u8);
+impl_rounded_fee!(u16, u16);
+impl_rounded_fee!(u16, u32);
+impl_rounded_fee!(u16, u64);
+
+impl_rounded_fee!(u32, u8);
+impl_rounded_fee!(u32, u16);
+impl_rounded_fee!(u32, u32);
+impl_rounded_fee!(u32, u64);
+
+impl_rounded_fee!(u64, u8);
+impl_rounded_fee!(u64, u16);
+impl_rounded_fee!(u64, u32);
+impl_rounded_fee!(u64, u64);
+
+impl_rounded_fee!(u8, u128);
+impl_rounded_fee!(u16, u128);
+impl_rounded_fee!(u32, u128);
+impl_rounded_fee!(u64, u128);
+
+impl_rounded_fee!(u128, u8);
+impl_rounded_fee!(u128, u16);
+impl_rounded_fee!(u128, u32);
+impl_rounded_fee!(u128, u64);
+impl_rounded_fee!(u128, u128);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    macro_rules! check {
+        ($typed:expr, $rounded:expr, $amt:expr, $rem:expr, $fee:expr) => {{
+            let (t, r) = ($typed, $rounded);
+            prop_assert_eq!(r.apply($amt), t.apply($amt));
+            prop_assert_eq!(r.reverse_from_rem($rem), t.reverse_from_rem($rem));
+            prop_assert_eq!(r.reverse_from_fee($fee), t.reverse_from_fee($fee));
+            prop_assert_eq!(r.apply_u128($amt as u128), t.apply_u128($amt as u128));
+            prop_assert_eq!(
+                r.reverse_from_rem_u128($rem as u128),
+                t.reverse_from_rem_u128($rem as u128)
+            );
+            prop_assert_eq!(
+                r.reverse_from_fee_u128($fee as u128),
+                t.reverse_from_fee_u128($fee as u128)
+            );
+            prop_assert_eq!(r.one_minus_fee_ratio(), t.one_minus_fee_ratio());
+        }};
+    }
+
+    macro_rules! test_suite {
+        ($N:ty, $D:ty, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(n: $N, d: $D, amt: u64, rem: u64, fee: u64) {
+                    type R = Ratio<$N, $D>;
+                    type F = Fee<Rounded<R>>;
+
+                    let r = R::new(n, d);
+                    let typed = Fee::<Ceil<R>>::new(r);
+                    for rounding in [Rounding::FavorProtocol, Rounding::FavorUser] {
+                        prop_assert_eq!(F::new(r, rounding).is_some(), typed.is_some());
+                    }
+                    let (ceil, floor) = match (typed, Fee::<Floor<R>>::new(r)) {
+                        (Some(c), Some(f)) => (c, f),
+                        _ => return Ok(()),
+                    };
+                    let up = F::new(r, Rounding::FavorProtocol).unwrap();
+                    let down = up.with_rounding(Rounding::FavorUser);
+
+                    prop_assert_eq!(F::from(ceil), up);
+                    prop_assert_eq!(F::from(floor), down);
+                    check!(ceil, up, amt, rem, fee);
+                    check!(floor, down, amt, rem, fee);
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, u8, rounded_u8_u8);
+    test_suite!(u8, u16, rounded_u8_u16);
+    test_suite!(u8, u32, rounded_u8_u32);
+    test_suite!(u8, u64, rounded_u8_u64);
+
+    test_suite!(u16, u8, rounded_u16_u8);
+    test_suite!(u16, u16, rounded_u16_u16);
+    test_suite!(u16, u32, rounded_u16_u32);
+    test_suite!(u16, u64, rounded_u16_u64);
+
+    test_suite!(u32, u8, rounded_u32_u8);
+    test_suite!(u32, u16, rounded_u32_u16);
+    test_suite!(u32, u32, rounded_u32_u32);
+    test_suite!(u32, u64, rounded_u32_u64);
+
+    test_suite!(u64, u8, rounded_u64_u8);
+    test_suite!(u64, u16, rounded_u64_u16);
+    test_suite!(u64, u32, rounded_u64_u32);
+    test_suite!(u64, u64, rounded_u64_u64);
+
+    test_suite!(u8, u128, rounded_u8_u128);
+    test_suite!(u16, u128, rounded_u16_u128);
+    test_suite!(u32, u128, rounded_u32_u128);
+    test_suite!(u64, u128, rounded_u64_u128);
+
+    test_suite!(u128, u8, rounded_u128_u8);
+    test_suite!(u128, u16, rounded_u128_u16);
+    test_suite!(u128, u32, rounded_u128_u32);
+    test_suite!(u128, u64, rounded_u128_u64);
+    test_suite!(u128, u128, rounded_u128_u128);
+}
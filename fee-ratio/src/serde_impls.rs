@@ -0,0 +1,177 @@
+//! Deserialization of types with invariants, which are checked
+//! so that invalid data is rejected at load time
+
+use serde::{de::Error, Deserialize, Deserializer};
+
+use crate::{
+    ratio::{Ceil, Floor, Ratio},
+    AftFee, BefFee, Fee,
+};
+
+macro_rules! impl_fee_deserialize {
+    ($N:ty, $D:ty) => {
+        impl_fee_deserialize!($N, $D, Ceil);
+        impl_fee_deserialize!($N, $D, Floor);
+    };
+    ($N:ty, $D:ty, $R:ident) => {
+        /// Fails if the fee ratio is invalid, see [`Fee::new`]
+        impl<'de> Deserialize<'de> for Fee<$R<Ratio<$N, $D>>> {
+            #[inline]
+            fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+                let $R(ratio) = $R::<Ratio<$N, $D>>::deserialize(deserializer)?;
+                Self::new(ratio).ok_or_else(|| {
+                    De::Error::custom(format_args!(
+                        "invalid fee ratio {ratio}: must have nonzero denominator and be <= 1"
+                    ))
+                })
+            }
+        }
+    };
+}
+
+impl_fee_deserialize!(u8, u8);
+impl_fee_deserialize!(u8, u16);
+impl_fee_deserialize!(u8, u32);
+impl_fee_deserialize!(u8, u64);
+impl_fee_deserialize!(u8, u128);
+
+impl_fee_deserialize!(u16, u8);
+impl_fee_deserialize!(u16, u16);
+impl_fee_deserialize!(u16, u32);
+impl_fee_deserialize!(u16, u64);
+impl_fee_deserialize!(u16, u128);
+
+impl_fee_deserialize!(u32, u8);
+impl_fee_deserialize!(u32, u16);
+impl_fee_deserialize!(u32, u32);
+impl_fee_deserialize!(u32, u64);
+impl_fee_deserialize!(u32, u128);
+
+impl_fee_deserialize!(u64, u8);
+impl_fee_deserialize!(u64, u16);
+impl_fee_deserialize!(u64, u32);
+impl_fee_deserialize!(u64, u64);
+impl_fee_deserialize!(u64, u128);
+
+impl_fee_deserialize!(u128, u8);
+impl_fee_deserialize!(u128, u16);
+impl_fee_deserialize!(u128, u32);
+impl_fee_deserialize!(u128, u64);
+impl_fee_deserialize!(u128, u128);
+
+#[derive(Deserialize)]
+struct AftFeeFields<T> {
+    rem: T,
+    fee: T,
+}
+
+macro_rules! impl_aft_fee_deserialize {
+    ($T:ty) => {
+        /// Fails if `rem + fee` overflows
+        impl<'de> Deserialize<'de> for AftFee<$T> {
+            #[inline]
+            fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+                let AftFeeFields { rem, fee } = AftFeeFields::<$T>::deserialize(deserializer)?;
+                rem.checked_add(fee)
+                    .and_then(|bef_fee| BefFee(bef_fee).with_fee(fee))
+                    .ok_or_else(|| De::Error::custom("AftFee rem + fee overflows"))
+            }
+        }
+    };
+}
+
+impl_aft_fee_deserialize!(u64);
+impl_aft_fee_deserialize!(u128);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    macro_rules! test_suite {
+        ($N:ty, $D:ty, $test_mod:ident) => {
+            mod $test_mod {
+                use super::*;
+
+                proptest! {
+                    #[test]
+                    fn fee_deserialize_validates(n: $N, d: $D) {
+                        let json = serde_json::to_string(&Ratio::<$N, $D>::new(n, d)).unwrap();
+                        let valid = d != 0 && n as u128 <= d as u128;
+
+                        let ceil: Result<Fee<Ceil<Ratio<$N, $D>>>, _> = serde_json::from_str(&json);
+                        let floor: Result<Fee<Floor<Ratio<$N, $D>>>, _> = serde_json::from_str(&json);
+                        prop_assert_eq!(ceil.is_ok(), valid);
+                        prop_assert_eq!(floor.is_ok(), valid);
+                        if let (Ok(ceil), Ok(floor)) = (ceil, floor) {
+                            prop_assert_eq!(serde_json::to_string(&ceil).unwrap(), json.clone());
+                            prop_assert_eq!(serde_json::to_string(&floor).unwrap(), json);
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, u8, fee_serde_u8_u8_tests);
+    test_suite!(u8, u16, fee_serde_u8_u16_tests);
+    test_suite!(u8, u32, fee_serde_u8_u32_tests);
+    test_suite!(u8, u64, fee_serde_u8_u64_tests);
+    test_suite!(u8, u128, fee_serde_u8_u128_tests);
+    test_suite!(u16, u8, fee_serde_u16_u8_tests);
+    test_suite!(u16, u16, fee_serde_u16_u16_tests);
+    test_suite!(u16, u32, fee_serde_u16_u32_tests);
+    test_suite!(u16, u64, fee_serde_u16_u64_tests);
+    test_suite!(u16, u128, fee_serde_u16_u128_tests);
+    test_suite!(u32, u8, fee_serde_u32_u8_tests);
+    test_suite!(u32, u16, fee_serde_u32_u16_tests);
+    test_suite!(u32, u32, fee_serde_u32_u32_tests);
+    test_suite!(u32, u64, fee_serde_u32_u64_tests);
+    test_suite!(u32, u128, fee_serde_u32_u128_tests);
+    test_suite!(u64, u8, fee_serde_u64_u8_tests);
+    test_suite!(u64, u16, fee_serde_u64_u16_tests);
+    test_suite!(u64, u32, fee_serde_u64_u32_tests);
+    test_suite!(u64, u64, fee_serde_u64_u64_tests);
+    test_suite!(u64, u128, fee_serde_u64_u128_tests);
+    test_suite!(u128, u8, fee_serde_u128_u8_tests);
+    test_suite!(u128, u16, fee_serde_u128_u16_tests);
+    test_suite!(u128, u32, fee_serde_u128_u32_tests);
+    test_suite!(u128, u64, fee_serde_u128_u64_tests);
+    test_suite!(u128, u128, fee_serde_u128_u128_tests);
+
+    proptest! {
+        #[test]
+        fn aft_fee_deserialize_validates(rem: u64, fee: u64) {
+            let json = format!(r#"{{"rem":{rem},"fee":{fee}}}"#);
+            let res: Result<AftFee, _> = serde_json::from_str(&json);
+            match rem.checked_add(fee) {
+                None => prop_assert!(res.is_err()),
+                Some(bef) => {
+                    let aft = res.unwrap();
+                    prop_assert_eq!(aft, BefFee(bef).with_fee(fee).unwrap());
+                    prop_assert_eq!(serde_json::to_string(&aft).unwrap(), json);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn serde_cases() {
+        let fee: Fee<Ceil<Ratio<u8, u16>>> = serde_json::from_str(r#"{"n":1,"d":400}"#).unwrap();
+        assert_eq!(fee.to_inner().0, Ratio::new(1, 400));
+
+        let err =
+            serde_json::from_str::<Fee<Ceil<Ratio<u8, u16>>>>(r#"{"n":2,"d":1}"#).unwrap_err();
+        assert!(err.to_string().contains("invalid fee ratio 2/1"));
+        assert!(serde_json::from_str::<Fee<Floor<Ratio<u8, u16>>>>(r#"{"n":0,"d":0}"#).is_err());
+
+        let bef: BefFee<u128> = serde_json::from_str(&u128::MAX.to_string()).unwrap();
+        assert_eq!(bef, BefFee(u128::MAX));
+        assert!(serde_json::from_str::<AftFee<u128>>(&format!(
+            r#"{{"rem":{},"fee":1}}"#,
+            u128::MAX
+        ))
+        .is_err());
+    }
+}
@@ -0,0 +1,203 @@
+//! String form (de)serialization of [`Fee`]s for use with
+//! `#[serde(with = "sanctum_fee_ratio::serde_str")]`.
+//!
+//! Fees are serialized as their fee ratio's `"n/d"` string and deserialized from any of the
+//! string formats accepted by [`Ratio`]'s [`FromStr`] implementation, such as `"0.25%"`
+//! and `"25bps"`, failing if the fee ratio is invalid, see [`Fee::new`].
+//!
+//! ```rust
+//! use sanctum_fee_ratio::{Fee, ratio::{Ceil, Ratio}};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Config {
+//!     #[serde(with = "sanctum_fee_ratio::serde_str")]
+//!     fee: Fee<Ceil<Ratio<u8, u16>>>,
+//! }
+//!
+//! let config: Config = serde_json::from_str(r#"{ "fee": "25bps" }"#).unwrap();
+//! assert_eq!(
+//!     config,
+//!     Config { fee: Fee::<Ceil<Ratio<u8, u16>>>::new(Ratio { n: 1, d: 400 }).unwrap() },
+//! );
+//! assert_eq!(serde_json::to_string(&config).unwrap(), r#"{"fee":"1/400"}"#);
+//!
+//! assert!(serde_json::from_str::<Config>(r#"{ "fee": "101%" }"#).is_err());
+//! ```
+
+use core::{fmt::Formatter, marker::PhantomData, str::FromStr};
+
+use serde::{de::Visitor, Deserializer, Serializer};
+
+use crate::{
+    ratio::{Ceil, Floor, Ratio},
+    Fee,
+};
+
+mod private {
+    use crate::ratio::serde_str::RatioStr;
+
+    pub trait Sealed: Sized {
+        type Ratio: RatioStr + Copy;
+
+        fn fee_ratio(&self) -> Self::Ratio;
+
+        fn from_fee_ratio(ratio: Self::Ratio) -> Option<Self>;
+    }
+}
+
+/// A [`Fee`] type that can be (de)serialized in the string form.
+///
+/// Sealed so that only `Fee<Ceil<Ratio<..>>>` and `Fee<Floor<Ratio<..>>>` can be used with this module.
+pub trait FeeStr: private::Sealed {}
+
+macro_rules! impl_fee_str {
+    ($N:ty, $D:ty) => {
+        impl_fee_str!($N, $D, Ceil);
+        impl_fee_str!($N, $D, Floor);
+    };
+    ($N:ty, $D:ty, $R:ident) => {
+        impl private::Sealed for Fee<$R<Ratio<$N, $D>>> {
+            type Ratio = Ratio<$N, $D>;
+
+            #[inline]
+            fn fee_ratio(&self) -> Self::Ratio {
+                self.0 .0
+            }
+
+            #[inline]
+            fn from_fee_ratio(ratio: Self::Ratio) -> Option<Self> {
+                Self::new(ratio)
+            }
+        }
+
+        impl FeeStr for Fee<$R<Ratio<$N, $D>>> {}
+    };
+}
+
+impl_fee_str!(u8, u8);
+impl_fee_str!(u8, u16);
+impl_fee_str!(u8, u32);
+impl_fee_str!(u8, u64);
+impl_fee_str!(u8, u128);
+
+impl_fee_str!(u16, u8);
+impl_fee_str!(u16, u16);
+impl_fee_str!(u16, u32);
+impl_fee_str!(u16, u64);
+impl_fee_str!(u16, u128);
+
+impl_fee_str!(u32, u8);
+impl_fee_str!(u32, u16);
+impl_fee_str!(u32, u32);
+impl_fee_str!(u32, u64);
+impl_fee_str!(u32, u128);
+
+impl_fee_str!(u64, u8);
+impl_fee_str!(u64, u16);
+impl_fee_str!(u64, u32);
+impl_fee_str!(u64, u64);
+impl_fee_str!(u64, u128);
+
+impl_fee_str!(u128, u8);
+impl_fee_str!(u128, u16);
+impl_fee_str!(u128, u32);
+impl_fee_str!(u128, u64);
+impl_fee_str!(u128, u128);
+
+/// Serializes `fee` as its fee ratio's `"n/d"` string
+#[inline]
+pub fn serialize<F: FeeStr, S: Serializer>(fee: &F, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&fee.fee_ratio())
+}
+
+/// Deserializes a fee from any of the string formats accepted by its fee ratio's
+/// [`FromStr`] implementation, failing if the fee ratio is invalid, see [`Fee::new`]
+#[inline]
+pub fn deserialize<'de, F, D>(deserializer: D) -> Result<F, D::Error>
+where
+    F: FeeStr,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(StrVisitor(PhantomData))
+}
+
+struct StrVisitor<F>(PhantomData<F>);
+
+impl<F: FeeStr> Visitor<'_> for StrVisitor<F> {
+    type Value = F;
+
+    #[inline]
+    fn expecting(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("a fee ratio string e.g. \"1/400\", \"0.0025\", \"0.25%\" or \"25bps\"")
+    }
+
+    #[inline]
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<F, E> {
+        let ratio = F::Ratio::from_str(v).map_err(E::custom)?;
+        F::from_fee_ratio(ratio).ok_or_else(|| {
+            E::custom(format_args!(
+                "invalid fee ratio {ratio}: must have nonzero denominator and be <= 1"
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Config {
+        #[serde(with = "crate::serde_str")]
+        ceil: Fee<Ceil<Ratio<u16, u16>>>,
+
+        #[serde(with = "crate::serde_str")]
+        floor: Fee<Floor<Ratio<u64, u128>>>,
+    }
+
+    #[test]
+    fn fee_str_round_trip() {
+        let config = Config {
+            ceil: Fee::<Ceil<Ratio<u16, u16>>>::new(Ratio { n: 25, d: 10_000 }).unwrap(),
+            floor: Fee::<Floor<Ratio<u64, u128>>>::new(Ratio {
+                n: u64::MAX,
+                d: u128::MAX,
+            })
+            .unwrap(),
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"ceil":"25/10000","floor":"{}/{}"}}"#,
+                u64::MAX,
+                u128::MAX
+            )
+        );
+        let de: Config = serde_json::from_str(&json).unwrap();
+        assert_eq!(de, config);
+
+        let de: Config = serde_json::from_str(r#"{ "ceil": "25bps", "floor": "100%" }"#).unwrap();
+        assert_eq!(de.ceil.to_inner().0, Ratio::<u16, u16> { n: 1, d: 400 });
+        assert!(de.floor.to_inner().0.is_one());
+    }
+
+    #[test]
+    fn fee_str_errors() {
+        for (json, err) in [
+            (r#""101%""#, "invalid fee ratio 101/100"),
+            (r#""1/0""#, "invalid fee ratio 1/0"),
+            (r#""0/0""#, "invalid fee ratio 0/0"),
+            (r#""65536/1""#, "ratio numerator too large"),
+            (r#""abc""#, "invalid ratio string"),
+            (r#"{"n":1,"d":2}"#, "expected a fee ratio string"),
+        ] {
+            let mut de = serde_json::Deserializer::from_str(json);
+            let res: Result<Fee<Ceil<Ratio<u16, u16>>>, _> = deserialize(&mut de);
+            assert!(res.unwrap_err().to_string().contains(err), "{json}");
+        }
+    }
+}
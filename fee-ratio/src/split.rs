@@ -0,0 +1,327 @@
+use core::fmt::{Display, Formatter};
+
+use crate::{
+    ratio::{allocate_u128_into, Floor, Ratio, TieBreak, U128Parts},
+    AftFee,
+};
+
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+/// Policy for allocating the dust left over after flooring
+/// each recipient's share in [`AftFee::split`]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum DustPolicy {
+    /// Dust is allocated 1 unit each to the recipients with the largest
+    /// fractional parts of their exact shares, ties broken in favor of
+    /// the recipient that comes first.
+    ///
+    /// Minimizes each share's deviation from its exact value, which is always `< 1`.
+    /// Same as [`allocate_u128_into`] with [`TieBreak::LowestIndex`] over the ratios'
+    /// numerators when expressed over their lowest common denominator.
+    #[default]
+    LargestRemainder,
+
+    /// All dust is allocated to the first recipient
+    First,
+
+    /// All dust is allocated to the last recipient
+    Last,
+}
+
+/// Displayed as the variant name
+impl Display for DustPolicy {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::LargestRemainder => "LargestRemainder",
+            Self::First => "First",
+            Self::Last => "Last",
+        })
+    }
+}
+
+impl AftFee {
+    /// Splits [`Self::fee`] among `K` recipients by their ratios of it.
+    ///
+    /// Each recipient is first allocated the floor of its exact share,
+    /// then the `< K` units of dust left over are allocated according to `policy`,
+    /// so that the returned shares always sum to exactly [`Self::fee`].
+    ///
+    /// # Params
+    /// - `ratios`: each recipient's ratio of the fee, which must sum to exactly `1.0`
+    /// - `policy`: how the dust left over after flooring each share is allocated
+    ///
+    /// # Returns
+    /// Each recipient's share of the fee, in the same order as `ratios`.
+    ///
+    /// `None` if `ratios` do not sum to exactly `1.0`, including if `K == 0`,
+    /// or if their lowest common denominator does not fit into a [`u128`]
+    ///
+    /// ```rust
+    /// use sanctum_fee_ratio::{DustPolicy, Fee, ratio::{Ceil, Ratio}};
+    ///
+    /// let aft = Fee::<Ceil<Ratio<u8, u8>>>::new(Ratio::new(1, 10)).unwrap().apply(100).unwrap();
+    /// let thirds = [Ratio::<u8, u8>::new(1, 3); 3];
+    ///
+    /// assert_eq!(aft.split(&thirds, DustPolicy::LargestRemainder), Some([4, 3, 3]));
+    /// assert_eq!(aft.split(&thirds, DustPolicy::Last), Some([3, 3, 4]));
+    /// ```
+    #[inline]
+    pub fn split<R: U128Parts, const K: usize>(
+        &self,
+        ratios: &[R; K],
+        policy: DustPolicy,
+    ) -> Option<[u64; K]> {
+        let mut weights = [0; K];
+        let mut shares = [0; K];
+        self.split_into(ratios, policy, &mut weights, &mut shares)?;
+        Some(shares)
+    }
+
+    /// [`Self::split`] for a number of recipients only known at runtime
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn split_vec<R: U128Parts>(&self, ratios: &[R], policy: DustPolicy) -> Option<Vec<u64>> {
+        let mut weights = vec![0; ratios.len()];
+        let mut shares = vec![0; ratios.len()];
+        self.split_into(ratios, policy, &mut weights, &mut shares)?;
+        Some(shares)
+    }
+
+    /// `weights.len()` and `shares.len()` must be equal to `ratios.len()`.
+    /// `weights` is scratch space for each ratio's numerator over their lowest common denominator.
+    fn split_into<R: U128Parts>(
+        &self,
+        ratios: &[R],
+        policy: DustPolicy,
+        weights: &mut [u128],
+        shares: &mut [u64],
+    ) -> Option<()> {
+        let lowest_form = |r: &R| {
+            let (n, d) = r.to_u128_parts();
+            Ratio::<u128, u128> { n, d }.lowest_form()
+        };
+        let lcd = ratios.iter().try_fold(1, |lcd, r| {
+            let Ratio { d, .. } = lowest_form(r);
+            if d == 0 {
+                return Some(lcd);
+            }
+            // lcm(lcd, d) = (lcd / gcd(lcd, d)) * d
+            Ratio::<u128, u128> { n: lcd, d }
+                .lowest_form()
+                .n
+                .checked_mul(d)
+        })?;
+
+        let mut sum = 0u128;
+        for (r, w) in ratios.iter().zip(weights.iter_mut()) {
+            let Ratio { n, d } = lowest_form(r);
+            // division-safety: d != 0, and d divides lcd
+            *w = if d == 0 { 0 } else { n.checked_mul(lcd / d)? };
+            sum = sum.checked_add(*w)?;
+        }
+        if sum != lcd {
+            return None;
+        }
+
+        let fee = self.fee();
+        let dust_idx = match policy {
+            DustPolicy::LargestRemainder => {
+                return allocate_u128_into(fee, weights, TieBreak::LowestIndex, shares)
+            }
+            DustPolicy::First => 0,
+            // unchecked-arith: sum == lcd >= 1, so ratios is not empty
+            DustPolicy::Last => shares.len() - 1,
+        };
+        let mut allocated = 0;
+        for (w, share) in weights.iter().zip(shares.iter_mut()) {
+            *share = match Floor(Ratio { n: *w, d: lcd }).apply(fee) {
+                Some(s) => s,
+                // w <= lcd, so the share is <= fee and this is always Some
+                None => unreachable!(),
+            };
+            // unchecked-arith: sum of floors of shares of fee <= fee
+            allocated += *share;
+        }
+        // unchecked-arith: allocated <= fee
+        shares[dust_idx] += fee - allocated;
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cmp::Ordering;
+
+    use proptest::prelude::*;
+
+    use crate::{ratio::U256, BefFee};
+
+    use super::*;
+
+    const POLICIES: [DustPolicy; 3] = [
+        DustPolicy::LargestRemainder,
+        DustPolicy::First,
+        DustPolicy::Last,
+    ];
+
+    /// The exact share of `fee` of a recipient with ratio `(n, d)`,
+    /// as its floor and its fractional part `(rem, d)`
+    fn exact_share(fee: u64, (n, d): (u128, u128)) -> (u64, (u128, u128)) {
+        if n == 0 || d == 0 {
+            return (0, (0, 1));
+        }
+        let (q, rem) = U256::widening_mul(fee as u128, n).div_rem_u128(d);
+        (q.to_u128_checked().unwrap() as u64, (rem, d))
+    }
+
+    /// Compares fractional parts `r1/d1` and `r2/d2`, both with nonzero denominators
+    fn cmp_frac((r1, d1): (u128, u128), (r2, d2): (u128, u128)) -> Ordering {
+        U256::widening_mul(r1, d2).const_cmp(&U256::widening_mul(r2, d1))
+    }
+
+    /// `K` ratios over a common denominator that sum to exactly 1.0
+    fn unit_partition<const K: usize>() -> impl Strategy<Value = [Ratio<u32, u32>; K]> {
+        ([any::<u32>(); K], 1..=u32::MAX).prop_map(|(cuts, d)| {
+            let mut cuts = cuts.map(|c| c % d);
+            cuts[K - 1] = d;
+            cuts.sort_unstable();
+            let mut prev = 0;
+            cuts.map(|c| {
+                let n = c - prev;
+                prev = c;
+                Ratio { n, d }
+            })
+        })
+    }
+
+    fn check<R: U128Parts + Copy, const K: usize>(
+        aft: AftFee,
+        ratios: [R; K],
+    ) -> Result<(), TestCaseError> {
+        let fee = aft.fee();
+        for policy in POLICIES {
+            let shares = aft.split(&ratios, policy).unwrap();
+            prop_assert_eq!(shares.iter().sum::<u64>(), fee);
+
+            #[cfg(feature = "alloc")]
+            prop_assert_eq!(aft.split_vec(&ratios, policy).unwrap(), shares.to_vec());
+
+            for (i, (r, share)) in ratios.iter().zip(shares).enumerate() {
+                let (floor, (rem, _)) = exact_share(fee, r.to_u128_parts());
+                let bonus = share - floor;
+                match policy {
+                    DustPolicy::LargestRemainder => {
+                        prop_assert!(bonus <= 1);
+                        if bonus == 1 {
+                            prop_assert!(rem != 0);
+                        }
+                    }
+                    DustPolicy::First if i != 0 => prop_assert_eq!(bonus, 0),
+                    DustPolicy::Last if i != K - 1 => prop_assert_eq!(bonus, 0),
+                    _ => (),
+                }
+            }
+        }
+
+        // every recipient that got dust has a fractional part at least that of every one that did not
+        let shares = aft.split(&ratios, DustPolicy::LargestRemainder).unwrap();
+        let fracs = ratios.map(|r| exact_share(fee, r.to_u128_parts()));
+        for (i, (fi, si)) in fracs.iter().zip(shares).enumerate() {
+            for (j, (fj, sj)) in fracs.iter().zip(shares).enumerate() {
+                if si > fi.0 && sj == fj.0 {
+                    let ord = cmp_frac(fi.1, fj.1);
+                    prop_assert!(ord.is_gt() || (ord.is_eq() && i < j));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn split_1(fee: u64, rem: u64) {
+            let aft = BefFee::<u64>(fee.saturating_add(rem)).with_fee(fee).unwrap();
+            check(aft, [Ratio::<u8, u8>::ONE])?;
+            check(aft, [Ratio::<u128, u128> { n: u128::MAX, d: u128::MAX }])?;
+        }
+
+        #[test]
+        fn split_3(fee: u64, ratios in unit_partition::<3>()) {
+            check(BefFee::<u64>(fee).with_fee(fee).unwrap(), ratios)?;
+        }
+
+        #[test]
+        fn split_8(fee: u64, ratios in unit_partition::<8>()) {
+            check(BefFee::<u64>(fee).with_fee(fee).unwrap(), ratios)?;
+        }
+
+        #[test]
+        fn split_mixed_denoms(fee: u64, a in 1..=u16::MAX, b in 1..=u16::MAX) {
+            // a/(a+b) and b/(a+b) as a ratio over u32 and one over u64
+            let d = a as u32 + b as u32;
+            let aft = BefFee::<u64>(fee).with_fee(fee).unwrap();
+            check(aft, [Ratio::<u64, u64> { n: a as u64 * 3, d: d as u64 * 3 }, Ratio { n: b as u64, d: d as u64 }])?;
+        }
+
+        #[test]
+        fn split_not_one(fee: u64, ratios in unit_partition::<3>(), extra in 1..=u32::MAX) {
+            let aft = BefFee::<u64>(fee).with_fee(fee).unwrap();
+            let mut over = ratios;
+            over[0].n = over[0].n.saturating_add(extra);
+            if over[0].n != ratios[0].n {
+                for policy in POLICIES {
+                    prop_assert_eq!(aft.split(&over, policy), None);
+                }
+            }
+            let mut under = ratios;
+            under[2].n = 0;
+            if ratios[2].n != 0 {
+                for policy in POLICIES {
+                    prop_assert_eq!(aft.split(&under, policy), None);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn split_edge_cases() {
+        let aft = BefFee::<u64>(100).with_fee(10).unwrap();
+        let empty: [Ratio<u8, u8>; 0] = [];
+        assert_eq!(aft.split(&empty, DustPolicy::LargestRemainder), None);
+        #[cfg(feature = "alloc")]
+        assert_eq!(aft.split_vec(&empty, DustPolicy::First), None);
+
+        // First and Last allocate dust regardless of the recipient's ratio
+        let r = [
+            Ratio::<u8, u8>::ZERO,
+            Ratio { n: 1, d: 3 },
+            Ratio { n: 2, d: 3 },
+        ];
+        assert_eq!(aft.split(&r, DustPolicy::First), Some([1, 3, 6]));
+        assert_eq!(aft.split(&r, DustPolicy::LargestRemainder), Some([0, 3, 7]));
+
+        // lowest common denominator 30 is larger than every denominator
+        let r = [
+            Ratio::<u8, u8> { n: 1, d: 6 },
+            Ratio { n: 1, d: 10 },
+            Ratio { n: 1, d: 15 },
+            Ratio { n: 2, d: 3 },
+        ];
+        assert_eq!(
+            aft.split(&r, DustPolicy::LargestRemainder),
+            Some([2, 1, 1, 6])
+        );
+
+        // ties go to the first recipient
+        let halves = [Ratio::<u8, u8> { n: 1, d: 2 }; 2];
+        let aft = BefFee::<u64>(1).with_fee(1).unwrap();
+        assert_eq!(
+            aft.split(&halves, DustPolicy::LargestRemainder),
+            Some([1, 0])
+        );
+        assert_eq!(aft.split(&halves, DustPolicy::Last), Some([0, 1]));
+        assert_eq!(DustPolicy::default().to_string(), "LargestRemainder");
+    }
+}
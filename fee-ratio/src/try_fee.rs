@@ -0,0 +1,221 @@
+use core::ops::RangeInclusive;
+
+use crate::{
+    ratio::{ArithTypes, Ceil, Floor, Ratio, RatioError},
+    AftFee, Fee,
+};
+
+macro_rules! impl_try_fee {
+    ($R:ident, $OneMinusR:ident, $N:ty, $D:ty) => {
+        impl Fee<$R<Ratio<$N, $D>>> {
+            /// [`Self::new`], but returns a [`RatioError`] instead of `None`
+            ///
+            /// # Errors
+            ///
+            /// - [`RatioError::ZeroDenominator`] if `fee_ratio`'s `denominator = 0`
+            /// - [`RatioError::InvalidFee`] if `fee_ratio` is not valid (`>1.0`)
+            #[inline]
+            pub const fn try_new(fee_ratio: Ratio<$N, $D>) -> Result<Self, RatioError> {
+                if fee_ratio.d == 0 {
+                    Err(RatioError::ZeroDenominator)
+                } else if fee_ratio.n as <Ratio<$N, $D> as ArithTypes>::Max
+                    > fee_ratio.d as <Ratio<$N, $D> as ArithTypes>::Max
+                {
+                    Err(RatioError::InvalidFee)
+                } else {
+                    Ok(Self($R(fee_ratio)))
+                }
+            }
+
+            /// [`Self::apply`], but returns a [`RatioError`] instead of `None`
+            ///
+            /// # Errors
+            ///
+            /// - [`RatioError::Overflow`] on overflow
+            #[inline]
+            pub const fn try_apply(&self, amount: u64) -> Result<AftFee, RatioError> {
+                match self.apply(amount) {
+                    None => Err(RatioError::Overflow),
+                    Some(a) => Ok(a),
+                }
+            }
+
+            /// [`Self::reverse_from_rem`], but returns a [`RatioError`] instead of `None`
+            ///
+            /// # Errors
+            ///
+            /// - [`RatioError::ZeroRatio`] if `self` is a 100% fee but `rem != 0`
+            /// - [`RatioError::Overflow`] if the smallest possible `amount > u64::MAX`
+            #[inline]
+            pub const fn try_reverse_from_rem(
+                &self,
+                rem: u64,
+            ) -> Result<RangeInclusive<u64>, RatioError> {
+                if self.0 .0.is_zero() {
+                    Ok(rem..=rem)
+                } else {
+                    $OneMinusR(self.one_minus_fee_ratio()).try_reverse(rem)
+                }
+            }
+
+            /// [`Self::reverse_from_fee`], but returns a [`RatioError`] instead of `None`
+            ///
+            /// # Errors
+            ///
+            /// - [`RatioError::ZeroRatio`] if `self` is a 0% fee but `fee != 0`
+            /// - [`RatioError::Overflow`] if the smallest possible `amount > u64::MAX`
+            #[inline]
+            pub const fn try_reverse_from_fee(
+                &self,
+                fee: u64,
+            ) -> Result<RangeInclusive<u64>, RatioError> {
+                let Self(r) = self;
+                if r.0.is_one() {
+                    Ok(fee..=fee)
+                } else {
+                    r.try_reverse(fee)
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_try_fee_all {
+    ($N:ty, $D:ty) => {
+        impl_try_fee!(Ceil, Floor, $N, $D);
+        impl_try_fee!(Floor, Ceil, $N, $D);
+    };
+}
+
+impl_try_fee_all!(u8, u8);
+impl_try_fee_all!(u8, u16);
+impl_try_fee_all!(u8, u32);
+impl_try_fee_all!(u8, u64);
+
+impl_try_fee_all!(u16, u8);
+impl_try_fee_all!(u16, u16);
+impl_try_fee_all!(u16, u32);
+impl_try_fee_all!(u16, u64);
+
+impl_try_fee_all!(u32, u8);
+impl_try_fee_all!(u32, u16);
+impl_try_fee_all!(u32, u32);
+impl_try_fee_all!(u32, u64);
+
+impl_try_fee_all!(u64, u8);
+impl_try_fee_all!(u64, u16);
+impl_try_fee_all!(u64, u32);
+impl_try_fee_all!(u64, u64);
+
+impl_try_fee_all!(u8, u128);
+impl_try_fee_all!(u16, u128);
+impl_try_fee_all!(u32, u128);
+impl_try_fee_all!(u64, u128);
+
+impl_try_fee_all!(u128, u8);
+impl_try_fee_all!(u128, u16);
+impl_try_fee_all!(u128, u32);
+impl_try_fee_all!(u128, u64);
+impl_try_fee_all!(u128, u128);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn try_new_errors() {
+        assert_eq!(
+            Fee::<Ceil<Ratio<u8, u8>>>::try_new(Ratio { n: 0, d: 0 }),
+            Err(RatioError::ZeroDenominator)
+        );
+        assert_eq!(
+            Fee::<Floor<Ratio<u8, u8>>>::try_new(Ratio { n: 2, d: 1 }),
+            Err(RatioError::InvalidFee)
+        );
+        assert_eq!(
+            Fee::<Ceil<Ratio<u8, u8>>>::try_new(Ratio { n: 1, d: 1 }),
+            Ok(Fee::<Ceil<Ratio<u8, u8>>>::ONE)
+        );
+        assert_eq!(
+            Fee::<Floor<Ratio<u8, u8>>>::ONE.try_reverse_from_rem(1),
+            Err(RatioError::ZeroRatio)
+        );
+    }
+
+    macro_rules! check_try_fee {
+        ($fee:expr, $bef:expr, $rem:expr, $fee_amt:expr) => {{
+            let f = $fee;
+            prop_assert_eq!(f.try_apply($bef).ok(), f.apply($bef));
+            prop_assert_eq!(f.try_reverse_from_rem($rem).ok(), f.reverse_from_rem($rem));
+            prop_assert_eq!(
+                f.try_reverse_from_fee($fee_amt).ok(),
+                f.reverse_from_fee($fee_amt)
+            );
+        }};
+    }
+
+    macro_rules! test_suite {
+        ($N:ty, $D:ty, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(n: $N, d: $D, bef: u64, rem: u64, fee: u64) {
+                    let ratio = Ratio::<$N, $D> { n, d };
+                    let ceil = Fee::<Ceil<Ratio<$N, $D>>>::try_new(ratio);
+                    let floor = Fee::<Floor<Ratio<$N, $D>>>::try_new(ratio);
+                    prop_assert_eq!(ceil.ok(), Fee::<Ceil<Ratio<$N, $D>>>::new(ratio));
+                    prop_assert_eq!(floor.ok(), Fee::<Floor<Ratio<$N, $D>>>::new(ratio));
+                    match (ceil, floor) {
+                        (Ok(ceil), Ok(floor)) => {
+                            check_try_fee!(ceil, bef, rem, fee);
+                            check_try_fee!(floor, bef, rem, fee);
+                        }
+                        (Err(c), Err(f)) => {
+                            prop_assert_eq!(c, f);
+                            prop_assert_eq!(
+                                c,
+                                if d == 0 {
+                                    RatioError::ZeroDenominator
+                                } else {
+                                    RatioError::InvalidFee
+                                }
+                            );
+                        }
+                        _ => return Err(TestCaseError::fail(format!("{ceil:?} {floor:?}"))),
+                    }
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, u8, try_fee_u8_u8);
+    test_suite!(u8, u16, try_fee_u8_u16);
+    test_suite!(u8, u32, try_fee_u8_u32);
+    test_suite!(u8, u64, try_fee_u8_u64);
+    test_suite!(u8, u128, try_fee_u8_u128);
+
+    test_suite!(u16, u8, try_fee_u16_u8);
+    test_suite!(u16, u16, try_fee_u16_u16);
+    test_suite!(u16, u32, try_fee_u16_u32);
+    test_suite!(u16, u64, try_fee_u16_u64);
+    test_suite!(u16, u128, try_fee_u16_u128);
+
+    test_suite!(u32, u8, try_fee_u32_u8);
+    test_suite!(u32, u16, try_fee_u32_u16);
+    test_suite!(u32, u32, try_fee_u32_u32);
+    test_suite!(u32, u64, try_fee_u32_u64);
+    test_suite!(u32, u128, try_fee_u32_u128);
+
+    test_suite!(u64, u8, try_fee_u64_u8);
+    test_suite!(u64, u16, try_fee_u64_u16);
+    test_suite!(u64, u32, try_fee_u64_u32);
+    test_suite!(u64, u64, try_fee_u64_u64);
+    test_suite!(u64, u128, try_fee_u64_u128);
+
+    test_suite!(u128, u8, try_fee_u128_u8);
+    test_suite!(u128, u16, try_fee_u128_u16);
+    test_suite!(u128, u32, try_fee_u128_u32);
+    test_suite!(u128, u64, try_fee_u128_u64);
+    test_suite!(u128, u128, try_fee_u128_u128);
+}
@@ -0,0 +1,378 @@
+use core::fmt::{Display, Formatter};
+
+use crate::U256;
+
+/// Which holders receive the last units of an [`allocate`]d amount
+/// when they have equal remainders
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum TieBreak {
+    /// Holders that come first in the weights slice are preferred
+    #[default]
+    LowestIndex,
+
+    /// Holders that come last in the weights slice are preferred
+    HighestIndex,
+}
+
+/// Displayed as the variant name
+impl Display for TieBreak {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::LowestIndex => "LowestIndex",
+            Self::HighestIndex => "HighestIndex",
+        })
+    }
+}
+
+/// `(amount * weight) / total` and `(amount * weight) % total`, where `weight <= total`
+#[inline]
+const fn floor_rem(amount: u64, weight: u128, total: u128) -> (u64, u128) {
+    match (amount as u128).checked_mul(weight) {
+        // division-safety: total is nonzero.
+        // weight <= total so quotient <= amount fits into u64
+        Some(product) => ((product / total) as u64, product % total),
+        None => {
+            // division-safety: total is nonzero
+            let (q, rem) = U256::widening_mul(amount as u128, weight).div_rem_u128(total);
+            match q.to_u128_checked() {
+                Some(q) => (q as u64, rem),
+                // weight <= total so quotient <= amount, so this is always Some
+                None => unreachable!(),
+            }
+        }
+    }
+}
+
+/// Distributes `amount` across holders in proportion to their `weights`,
+/// conserving the total exactly.
+///
+/// Each holder is first allocated the floor of its exact share
+/// `amount * weight / sum(weights)`, then the `< weights.len()` units left over
+/// are allocated 1 each to the holders with the largest remainders,
+/// with equal remainders ordered according to `tie_break`.
+///
+/// Every share is therefore either the floor or the ceiling of its exact value,
+/// and holders with weight `0` always get `0`.
+///
+/// Runs in `O(weights.len() * log2(sum(weights)))` time with no allocations.
+///
+/// # Params
+/// - `amount`: the total amount to distribute
+/// - `weights`: each holder's weight. Their sum is computed as a [`u128`].
+/// - `tie_break`: how holders with equal remainders are ordered
+///
+/// # Returns
+/// Each holder's share of `amount`, in the same order as `weights`.
+///
+/// `None` if `weights` sum to `0`, including if `N == 0`
+///
+/// ```rust
+/// use sanctum_u64_ratio::{allocate, TieBreak};
+///
+/// // exact shares are 33.33, 33.33, 33.33
+/// assert_eq!(allocate(100, &[1, 1, 1], TieBreak::LowestIndex), Some([34, 33, 33]));
+/// assert_eq!(allocate(100, &[1, 1, 1], TieBreak::HighestIndex), Some([33, 33, 34]));
+///
+/// // exact shares are 14.29, 28.57, 57.14
+/// assert_eq!(allocate(100, &[1, 2, 4], TieBreak::LowestIndex), Some([14, 29, 57]));
+/// ```
+#[inline]
+pub fn allocate<const N: usize>(
+    amount: u64,
+    weights: &[u64; N],
+    tie_break: TieBreak,
+) -> Option<[u64; N]> {
+    let mut shares = [0; N];
+    allocate_into(amount, weights, tie_break, &mut shares)?;
+    Some(shares)
+}
+
+/// [`allocate`] for a number of holders only known at runtime,
+/// writing each holder's share into `shares`.
+///
+/// # Returns
+/// `None`, leaving `shares` in an unspecified state, if:
+/// - `shares.len() != weights.len()`
+/// - `weights` sum to `0`, including if they are empty
+/// - the sum of `weights` overflows [`u128`]
+///
+/// ```rust
+/// use sanctum_u64_ratio::{allocate_into, TieBreak};
+///
+/// let weights = [5, 0, 3];
+/// let mut shares = [0; 3];
+///
+/// assert_eq!(allocate_into(7, &weights, TieBreak::LowestIndex, &mut shares), Some(()));
+/// // exact shares are 4.375, 0, 2.625
+/// assert_eq!(shares, [4, 0, 3]);
+///
+/// assert_eq!(allocate_into(7, &weights, TieBreak::LowestIndex, &mut shares[..2]), None);
+/// ```
+#[inline]
+pub fn allocate_into(
+    amount: u64,
+    weights: &[u64],
+    tie_break: TieBreak,
+    shares: &mut [u64],
+) -> Option<()> {
+    allocate_weights_into(amount, weights, tie_break, shares)
+}
+
+/// [`allocate_into`] with [`u128`] weights, e.g. the numerators of
+/// fractions over a common denominator that does not fit into a [`u64`].
+///
+/// # Returns
+/// `None`, leaving `shares` in an unspecified state, if:
+/// - `shares.len() != weights.len()`
+/// - `weights` sum to `0`, including if they are empty
+/// - the sum of `weights` overflows [`u128`]
+///
+/// ```rust
+/// use sanctum_u64_ratio::{allocate_u128_into, TieBreak};
+///
+/// let weights = [u128::MAX / 2, u128::MAX / 2];
+/// let mut shares = [0; 2];
+///
+/// assert_eq!(allocate_u128_into(7, &weights, TieBreak::HighestIndex, &mut shares), Some(()));
+/// assert_eq!(shares, [3, 4]);
+///
+/// assert_eq!(allocate_u128_into(7, &[u128::MAX, 1], TieBreak::LowestIndex, &mut shares), None);
+/// ```
+#[inline]
+pub fn allocate_u128_into(
+    amount: u64,
+    weights: &[u128],
+    tie_break: TieBreak,
+    shares: &mut [u64],
+) -> Option<()> {
+    allocate_weights_into(amount, weights, tie_break, shares)
+}
+
+fn allocate_weights_into<W: Copy + Into<u128>>(
+    amount: u64,
+    weights: &[W],
+    tie_break: TieBreak,
+    shares: &mut [u64],
+) -> Option<()> {
+    if shares.len() != weights.len() {
+        return None;
+    }
+    let total = weights
+        .iter()
+        .try_fold(0u128, |sum, w| sum.checked_add((*w).into()))?;
+    if total == 0 {
+        return None;
+    }
+
+    let mut allocated = 0;
+    for (w, share) in weights.iter().zip(shares.iter_mut()) {
+        *share = floor_rem(amount, (*w).into(), total).0;
+        // unchecked-arith: sum of floors of shares of amount <= amount
+        allocated += *share;
+    }
+    // unchecked-arith: allocated <= amount
+    let dust = amount - allocated;
+    if dust == 0 {
+        return Some(());
+    }
+
+    let count_rems = |pred: &dyn Fn(u128) -> bool| {
+        weights
+            .iter()
+            .filter(|w| pred(floor_rem(amount, (**w).into(), total).1))
+            .count() as u64
+    };
+
+    // The remainders sum to `dust * total` and are each < total,
+    // so more than `dust` of them are nonzero.
+    // Binary search for the `dust`-th largest remainder as the threshold:
+    // the largest t such that at least `dust` remainders are >= t.
+    let mut lo = 1;
+    // unchecked-arith: total > 0
+    let mut hi = total - 1;
+    while lo < hi {
+        let mid = lo + (hi - lo + 1) / 2;
+        if count_rems(&|r| r >= mid) >= dust {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+    let threshold = lo;
+
+    // Every remainder above the threshold gets 1, which is < dust of them by construction,
+    // then the rest go to the ones equal to the threshold in tie-break order
+    let mut ties = dust - count_rems(&|r| r > threshold);
+    let mut bump = |(w, share): (&W, &mut u64)| {
+        let rem = floor_rem(amount, (*w).into(), total).1;
+        if rem > threshold {
+            *share += 1;
+        } else if rem == threshold && ties > 0 {
+            *share += 1;
+            ties -= 1;
+        }
+    };
+    match tie_break {
+        TieBreak::LowestIndex => weights.iter().zip(shares.iter_mut()).for_each(&mut bump),
+        TieBreak::HighestIndex => weights
+            .iter()
+            .zip(shares.iter_mut())
+            .rev()
+            .for_each(&mut bump),
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{collection::vec, prelude::*};
+
+    use super::*;
+
+    const TIE_BREAKS: [TieBreak; 2] = [TieBreak::LowestIndex, TieBreak::HighestIndex];
+
+    fn weights() -> impl Strategy<Value = Vec<u64>> {
+        prop_oneof![
+            // small weights for many equal remainders
+            vec(0..=4u64, 1..=64),
+            vec(any::<u64>(), 1..=64),
+            vec(prop_oneof![Just(0), Just(1), Just(u64::MAX)], 1..=16),
+        ]
+    }
+
+    fn check(amount: u64, weights: &[u64], tie_break: TieBreak) -> Result<(), TestCaseError> {
+        let mut shares = vec![0; weights.len()];
+        let res = allocate_into(amount, weights, tie_break, &mut shares);
+
+        let wide: Vec<u128> = weights.iter().map(|w| *w as u128).collect();
+        let mut wide_shares = vec![0; weights.len()];
+        let wide_res = allocate_u128_into(amount, &wide, tie_break, &mut wide_shares);
+        prop_assert_eq!(wide_res.map(|_| &wide_shares), res.map(|_| &shares));
+
+        check_u128(amount, &wide, tie_break)
+    }
+
+    fn check_u128(amount: u64, weights: &[u128], tie_break: TieBreak) -> Result<(), TestCaseError> {
+        let total = weights.iter().try_fold(0u128, |sum, w| sum.checked_add(*w));
+        let mut shares = vec![0; weights.len()];
+        let res = allocate_u128_into(amount, weights, tie_break, &mut shares);
+        let total = match total {
+            Some(t) if t != 0 => t,
+            _ => {
+                prop_assert_eq!(res, None);
+                return Ok(());
+            }
+        };
+        prop_assert_eq!(res, Some(()));
+
+        // conservation
+        prop_assert_eq!(
+            shares.iter().map(|s| *s as u128).sum::<u128>(),
+            amount as u128
+        );
+
+        // no share deviates from its exact value by 1 or more
+        let mut bumped_min = None;
+        let mut unbumped_max = None;
+        for (i, (w, s)) in weights.iter().zip(shares.iter()).enumerate() {
+            let exact = U256::widening_mul(amount as u128, *w);
+            let scaled = U256::widening_mul(*s as u128, total);
+            let deviation = match scaled.checked_sub(exact) {
+                Some(dev) => dev,
+                None => exact.checked_sub(scaled).unwrap(),
+            };
+            prop_assert!(deviation.const_cmp(&U256::from_u128(total)).is_lt());
+
+            // largest remainders get bumped, with ties ordered by tie_break
+            let (floor, rem) = floor_rem(amount, *w, total);
+            let key = (
+                rem,
+                match tie_break {
+                    TieBreak::LowestIndex => usize::MAX - i,
+                    TieBreak::HighestIndex => i,
+                },
+            );
+            if *s == floor {
+                unbumped_max = unbumped_max.max(Some(key));
+            } else {
+                prop_assert_eq!(*s, floor + 1);
+                prop_assert!(rem != 0);
+                bumped_min = Some(bumped_min.map_or(key, |m: (u128, usize)| m.min(key)));
+            }
+        }
+        if let (Some(b), Some(u)) = (bumped_min, unbumped_max) {
+            prop_assert!(b > u);
+        }
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn allocate_conservation_and_deviation(amount: u64, weights in weights()) {
+            for tie_break in TIE_BREAKS {
+                check(amount, &weights, tie_break)?;
+            }
+        }
+
+        #[test]
+        fn allocate_small_amounts(amount in 0..=128u64, weights in weights()) {
+            for tie_break in TIE_BREAKS {
+                check(amount, &weights, tie_break)?;
+            }
+        }
+
+        #[test]
+        fn allocate_u128_weights(
+            amount: u64,
+            weights in vec(prop_oneof![any::<u128>(), 0..=u128::MAX / 64, 0..=4u128], 1..=64),
+        ) {
+            for tie_break in TIE_BREAKS {
+                check_u128(amount, &weights, tie_break)?;
+            }
+        }
+
+        #[test]
+        fn allocate_array_eq_slice(amount: u64, weights: [u64; 5]) {
+            for tie_break in TIE_BREAKS {
+                let mut shares = [0; 5];
+                let res = allocate_into(amount, &weights, tie_break, &mut shares);
+                prop_assert_eq!(allocate(amount, &weights, tie_break), res.map(|_| shares));
+            }
+        }
+
+        #[test]
+        fn allocate_len_mismatch(amount: u64, weights in weights(), extra in 1..=4usize) {
+            let mut shares = vec![0; weights.len() + extra];
+            prop_assert_eq!(allocate_into(amount, &weights, TieBreak::LowestIndex, &mut shares), None);
+            prop_assert_eq!(
+                allocate_into(amount, &weights, TieBreak::LowestIndex, &mut shares[..weights.len() - 1]),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn allocate_edge_cases() {
+        assert_eq!(allocate(100, &[], TieBreak::LowestIndex), None);
+        assert_eq!(allocate(100, &[0, 0], TieBreak::LowestIndex), None);
+        assert_eq!(allocate(0, &[1, 2], TieBreak::LowestIndex), Some([0, 0]));
+        assert_eq!(
+            allocate(u64::MAX, &[u64::MAX; 3], TieBreak::LowestIndex),
+            Some([u64::MAX / 3; 3])
+        );
+        assert_eq!(
+            allocate(u64::MAX, &[u64::MAX, 0, 1], TieBreak::HighestIndex),
+            Some([u64::MAX - 1, 0, 1])
+        );
+        assert_eq!(
+            allocate(5, &[1, 1, 1, 1], TieBreak::LowestIndex),
+            Some([2, 1, 1, 1])
+        );
+        assert_eq!(
+            allocate(5, &[1, 1, 1, 1], TieBreak::HighestIndex),
+            Some([1, 1, 1, 2])
+        );
+        assert_eq!(TieBreak::default().to_string(), "LowestIndex");
+    }
+}
@@ -0,0 +1,489 @@
+use core::{
+    cmp::Ordering,
+    fmt::{Display, Formatter},
+    hash::{Hash, Hasher},
+    ops::RangeInclusive,
+};
+
+use crate::{gcd_u128, Ceil, Floor, Ratio, RatioApply, TryFromRatioError, U128Parts, U256};
+
+/// A [`Floor`] of a [`Ratio`] whose type is only known at runtime
+pub type AnyFloor = Floor<AnyRatio>;
+
+/// A [`Ceil`] of a [`Ratio`] whose type is only known at runtime
+pub type AnyCeil = Ceil<AnyRatio>;
+
+macro_rules! impl_any_ratio {
+    ($($V:ident($N:ty, $D:ty)),* $(,)?) => {
+        /// A [`Ratio`] of any numerator and denominator type combination,
+        /// for when the type is only known at runtime, e.g. when loaded from config,
+        /// or for keeping ratios of different types in the same collection.
+        ///
+        /// Comparison, equality and hashing are by value across variants,
+        /// e.g. `AnyRatio::U8U8(1/2) == AnyRatio::U64U64(2/4)`.
+        #[derive(Debug, Copy, Clone)]
+        pub enum AnyRatio {
+            $(
+                #[doc = concat!("`Ratio<", stringify!($N), ", ", stringify!($D), ">`")]
+                $V(Ratio<$N, $D>),
+            )*
+        }
+
+        impl AnyRatio {
+            /// See [`Ratio::is_zero`]
+            #[inline]
+            pub const fn is_zero(&self) -> bool {
+                match self {
+                    $(Self::$V(r) => r.is_zero(),)*
+                }
+            }
+
+            /// See [`Ratio::is_one`]
+            #[inline]
+            pub const fn is_one(&self) -> bool {
+                match self {
+                    $(Self::$V(r) => r.is_one(),)*
+                }
+            }
+
+            /// # Returns
+            ///
+            /// `(n, d)`
+            #[inline]
+            pub const fn to_u128_parts(&self) -> (u128, u128) {
+                match self {
+                    $(Self::$V(r) => (r.n as u128, r.d as u128),)*
+                }
+            }
+
+            /// Fraction comparison across variants.
+            ///
+            /// Zero denominators are treated as zero, same as [`Ratio::const_cmp`].
+            #[inline]
+            pub const fn const_cmp(&self, other: &Self) -> Ordering {
+                match (self.is_zero(), other.is_zero()) {
+                    (true, true) => return Ordering::Equal,
+                    (true, false) => return Ordering::Less,
+                    (false, true) => return Ordering::Greater,
+                    (false, false) => (),
+                };
+                let (a, b) = self.to_u128_parts();
+                let (c, d) = other.to_u128_parts();
+                U256::widening_mul(a, d).const_cmp(&U256::widening_mul(c, b))
+            }
+
+            /// Returns the fraction's lowest form as a `Ratio<u128, u128>`.
+            ///
+            /// This is `0/0` if [`Self::is_zero()`]
+            #[inline]
+            pub const fn lowest_form(&self) -> Ratio<u128, u128> {
+                if self.is_zero() {
+                    return Ratio::<u128, u128>::ZERO;
+                }
+                let (n, d) = self.to_u128_parts();
+                let gcd = gcd_u128(n, d);
+                // division-safety: gcd is never 0 due to early return above
+                Ratio {
+                    n: n / gcd,
+                    d: d / gcd,
+                }
+            }
+        }
+
+        impl Floor<AnyRatio> {
+            /// See [`Floor::apply`] for [`Ratio`]s
+            #[inline]
+            pub const fn apply(&self, amount: u64) -> Option<u64> {
+                match self.0 {
+                    $(AnyRatio::$V(r) => Floor(r).apply(amount),)*
+                }
+            }
+
+            /// See [`Floor::reverse`] for [`Ratio`]s
+            #[inline]
+            pub const fn reverse(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                match self.0 {
+                    $(AnyRatio::$V(r) => Floor(r).reverse(amt_after_apply),)*
+                }
+            }
+
+            /// See [`Floor::reverse_est`] for [`Ratio`]s
+            #[inline]
+            pub const fn reverse_est(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                match self.0 {
+                    $(AnyRatio::$V(r) => Floor(r).reverse_est(amt_after_apply),)*
+                }
+            }
+
+            /// See [`Floor::apply_u128`] for [`Ratio`]s
+            #[inline]
+            pub const fn apply_u128(&self, amount: u128) -> Option<u128> {
+                match self.0 {
+                    $(AnyRatio::$V(r) => Floor(r).apply_u128(amount),)*
+                }
+            }
+
+            /// See [`Floor::reverse_u128`] for [`Ratio`]s
+            #[inline]
+            pub const fn reverse_u128(&self, amt_after_apply: u128) -> Option<RangeInclusive<u128>> {
+                match self.0 {
+                    $(AnyRatio::$V(r) => Floor(r).reverse_u128(amt_after_apply),)*
+                }
+            }
+
+            /// See [`Floor::reverse_est_u128`] for [`Ratio`]s
+            #[inline]
+            pub const fn reverse_est_u128(
+                &self,
+                amt_after_apply: u128,
+            ) -> Option<RangeInclusive<u128>> {
+                match self.0 {
+                    $(AnyRatio::$V(r) => Floor(r).reverse_est_u128(amt_after_apply),)*
+                }
+            }
+        }
+
+        impl Ceil<AnyRatio> {
+            /// See [`Ceil::apply`] for [`Ratio`]s
+            #[inline]
+            pub const fn apply(&self, amount: u64) -> Option<u64> {
+                match self.0 {
+                    $(AnyRatio::$V(r) => Ceil(r).apply(amount),)*
+                }
+            }
+
+            /// See [`Ceil::reverse`] for [`Ratio`]s
+            #[inline]
+            pub const fn reverse(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                match self.0 {
+                    $(AnyRatio::$V(r) => Ceil(r).reverse(amt_after_apply),)*
+                }
+            }
+
+            /// See [`Ceil::reverse_est`] for [`Ratio`]s
+            #[inline]
+            pub const fn reverse_est(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                match self.0 {
+                    $(AnyRatio::$V(r) => Ceil(r).reverse_est(amt_after_apply),)*
+                }
+            }
+
+            /// See [`Ceil::apply_u128`] for [`Ratio`]s
+            #[inline]
+            pub const fn apply_u128(&self, amount: u128) -> Option<u128> {
+                match self.0 {
+                    $(AnyRatio::$V(r) => Ceil(r).apply_u128(amount),)*
+                }
+            }
+
+            /// See [`Ceil::reverse_u128`] for [`Ratio`]s
+            #[inline]
+            pub const fn reverse_u128(&self, amt_after_apply: u128) -> Option<RangeInclusive<u128>> {
+                match self.0 {
+                    $(AnyRatio::$V(r) => Ceil(r).reverse_u128(amt_after_apply),)*
+                }
+            }
+
+            /// See [`Ceil::reverse_est_u128`] for [`Ratio`]s
+            #[inline]
+            pub const fn reverse_est_u128(
+                &self,
+                amt_after_apply: u128,
+            ) -> Option<RangeInclusive<u128>> {
+                match self.0 {
+                    $(AnyRatio::$V(r) => Ceil(r).reverse_est_u128(amt_after_apply),)*
+                }
+            }
+        }
+
+        /// Displayed as `{numerator}/{denominator}`
+        impl Display for AnyRatio {
+            #[inline]
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                match self {
+                    $(Self::$V(r) => r.fmt(f),)*
+                }
+            }
+        }
+
+        $(
+            impl From<Ratio<$N, $D>> for AnyRatio {
+                #[inline]
+                fn from(r: Ratio<$N, $D>) -> Self {
+                    Self::$V(r)
+                }
+            }
+
+            impl From<Floor<Ratio<$N, $D>>> for Floor<AnyRatio> {
+                #[inline]
+                fn from(Floor(r): Floor<Ratio<$N, $D>>) -> Self {
+                    Self(r.into())
+                }
+            }
+
+            impl From<Ceil<Ratio<$N, $D>>> for Ceil<AnyRatio> {
+                #[inline]
+                fn from(Ceil(r): Ceil<Ratio<$N, $D>>) -> Self {
+                    Self(r.into())
+                }
+            }
+
+            /// The ratio is converted to its [`AnyRatio::lowest_form`], so that ratios of the same value
+            /// always convert to the same result regardless of their variant.
+            ///
+            /// # Errors
+            ///
+            /// [`TryFromRatioError`] if the ratio's lowest form does not fit
+            impl TryFrom<AnyRatio> for Ratio<$N, $D> {
+                type Error = TryFromRatioError;
+
+                #[inline]
+                fn try_from(r: AnyRatio) -> Result<Self, Self::Error> {
+                    let Ratio { n, d } = r.lowest_form();
+                    Self::from_u128_parts(n, d).ok_or(TryFromRatioError)
+                }
+            }
+
+            /// See [`Ratio`]'s [`TryFrom<AnyRatio>`] implementation
+            impl TryFrom<Floor<AnyRatio>> for Floor<Ratio<$N, $D>> {
+                type Error = TryFromRatioError;
+
+                #[inline]
+                fn try_from(Floor(r): Floor<AnyRatio>) -> Result<Self, Self::Error> {
+                    r.try_into().map(Self)
+                }
+            }
+
+            /// See [`Ratio`]'s [`TryFrom<AnyRatio>`] implementation
+            impl TryFrom<Ceil<AnyRatio>> for Ceil<Ratio<$N, $D>> {
+                type Error = TryFromRatioError;
+
+                #[inline]
+                fn try_from(Ceil(r): Ceil<AnyRatio>) -> Result<Self, Self::Error> {
+                    r.try_into().map(Self)
+                }
+            }
+        )*
+    };
+}
+
+impl_any_ratio!(
+    U8U8(u8, u8),
+    U8U16(u8, u16),
+    U8U32(u8, u32),
+    U8U64(u8, u64),
+    U8U128(u8, u128),
+    U16U8(u16, u8),
+    U16U16(u16, u16),
+    U16U32(u16, u32),
+    U16U64(u16, u64),
+    U16U128(u16, u128),
+    U32U8(u32, u8),
+    U32U16(u32, u16),
+    U32U32(u32, u32),
+    U32U64(u32, u64),
+    U32U128(u32, u128),
+    U64U8(u64, u8),
+    U64U16(u64, u16),
+    U64U32(u64, u32),
+    U64U64(u64, u64),
+    U64U128(u64, u128),
+    U128U8(u128, u8),
+    U128U16(u128, u16),
+    U128U32(u128, u32),
+    U128U64(u128, u64),
+    U128U128(u128, u128),
+);
+
+/// Uses [`AnyRatio::const_cmp`], see its docs for more info
+impl PartialEq for AnyRatio {
+    #[inline]
+    fn eq(&self, rhs: &Self) -> bool {
+        self.const_cmp(rhs).is_eq()
+    }
+}
+
+/// Uses [`AnyRatio::const_cmp`], see its docs for more info
+impl Eq for AnyRatio {}
+
+/// Uses [`AnyRatio::const_cmp`], see its docs for more info
+impl PartialOrd for AnyRatio {
+    #[inline]
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+/// Uses [`AnyRatio::const_cmp`], see its docs for more info
+impl Ord for AnyRatio {
+    #[inline]
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.const_cmp(rhs)
+    }
+}
+
+/// Hashes the fraction's lowest form as `u128`s
+/// so that equal ratios of different variants hash the same
+impl Hash for AnyRatio {
+    #[inline]
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        let Ratio { n, d } = self.lowest_form();
+        n.hash(state);
+        d.hash(state);
+    }
+}
+
+impl RatioApply for Floor<AnyRatio> {
+    #[inline]
+    fn apply(&self, amount: u64) -> Option<u64> {
+        Self::apply(self, amount)
+    }
+
+    #[inline]
+    fn reverse_est(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+        Self::reverse_est(self, amt_after_apply)
+    }
+
+    #[inline]
+    fn reverse(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+        Self::reverse(self, amt_after_apply)
+    }
+}
+
+impl RatioApply for Ceil<AnyRatio> {
+    #[inline]
+    fn apply(&self, amount: u64) -> Option<u64> {
+        Self::apply(self, amount)
+    }
+
+    #[inline]
+    fn reverse_est(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+        Self::reverse_est(self, amt_after_apply)
+    }
+
+    #[inline]
+    fn reverse(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+        Self::reverse(self, amt_after_apply)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::DefaultHasher;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn hash_of<T: Hash>(t: &T) -> u64 {
+        let mut h = DefaultHasher::new();
+        t.hash(&mut h);
+        h.finish()
+    }
+
+    macro_rules! test_suite {
+        ($N:ty, $D:ty, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(n: $N, d: $D, amt: u64, aaf: u64, wide_amt: u128, wide_aaf: u128, k in 1..=4u8) {
+                    type R = Ratio<$N, $D>;
+
+                    let r = R::new(n, d);
+                    let any = AnyRatio::from(r);
+
+                    let lowest = r.lowest_form_self();
+                    prop_assert_eq!(Ratio::<$N, $D>::try_from(any).map(|x| (x.n, x.d)), Ok((lowest.n, lowest.d)));
+                    prop_assert_eq!(any.to_u128_parts(), (n as u128, d as u128));
+                    prop_assert_eq!(any.is_zero(), r.is_zero());
+                    prop_assert_eq!(any.is_one(), r.is_one());
+                    prop_assert_eq!(any.to_string(), r.to_string());
+
+                    let (f, c) = (AnyFloor::from(Floor(r)), AnyCeil::from(Ceil(r)));
+                    prop_assert_eq!(f.apply(amt), Floor(r).apply(amt));
+                    prop_assert_eq!(f.reverse(aaf), Floor(r).reverse(aaf));
+                    prop_assert_eq!(RatioApply::reverse_est(&f, aaf), Floor(r).reverse_est(aaf));
+                    prop_assert_eq!(f.apply_u128(wide_amt), Floor(r).apply_u128(wide_amt));
+                    prop_assert_eq!(f.reverse_u128(wide_aaf), Floor(r).reverse_u128(wide_aaf));
+                    prop_assert_eq!(c.apply(amt), Ceil(r).apply(amt));
+                    prop_assert_eq!(c.reverse(aaf), Ceil(r).reverse(aaf));
+                    prop_assert_eq!(RatioApply::reverse_est(&c, aaf), Ceil(r).reverse_est(aaf));
+                    prop_assert_eq!(c.apply_u128(wide_amt), Ceil(r).apply_u128(wide_amt));
+                    prop_assert_eq!(c.reverse_u128(wide_aaf), Ceil(r).reverse_u128(wide_aaf));
+
+                    // same value in the widest variant
+                    let k = k as u128;
+                    let scaled = AnyRatio::U128U128(Ratio::new(
+                        (n as u128).saturating_mul(k),
+                        (d as u128).saturating_mul(k),
+                    ));
+                    if (n as u128).checked_mul(k).is_some() && (d as u128).checked_mul(k).is_some() {
+                        prop_assert_eq!(scaled, any);
+                        prop_assert_eq!(hash_of(&scaled), hash_of(&any));
+                        prop_assert_eq!(
+                            Ratio::<$N, $D>::try_from(scaled).map(|x| x == r),
+                            Ok(true)
+                        );
+                    }
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, u8, any_u8_u8);
+    test_suite!(u8, u16, any_u8_u16);
+    test_suite!(u8, u32, any_u8_u32);
+    test_suite!(u8, u64, any_u8_u64);
+
+    test_suite!(u16, u8, any_u16_u8);
+    test_suite!(u16, u16, any_u16_u16);
+    test_suite!(u16, u32, any_u16_u32);
+    test_suite!(u16, u64, any_u16_u64);
+
+    test_suite!(u32, u8, any_u32_u8);
+    test_suite!(u32, u16, any_u32_u16);
+    test_suite!(u32, u32, any_u32_u32);
+    test_suite!(u32, u64, any_u32_u64);
+
+    test_suite!(u64, u8, any_u64_u8);
+    test_suite!(u64, u16, any_u64_u16);
+    test_suite!(u64, u32, any_u64_u32);
+    test_suite!(u64, u64, any_u64_u64);
+
+    test_suite!(u8, u128, any_u8_u128);
+    test_suite!(u16, u128, any_u16_u128);
+    test_suite!(u32, u128, any_u32_u128);
+    test_suite!(u64, u128, any_u64_u128);
+
+    test_suite!(u128, u8, any_u128_u8);
+    test_suite!(u128, u16, any_u128_u16);
+    test_suite!(u128, u32, any_u128_u32);
+    test_suite!(u128, u64, any_u128_u64);
+    test_suite!(u128, u128, any_u128_u128);
+
+    proptest! {
+        #[test]
+        fn any_cmp_across_variants(a: u16, b: u16, c: u64, d: u64) {
+            let x = AnyRatio::U16U16(Ratio::new(a, b));
+            let y = AnyRatio::U64U64(Ratio::new(c, d));
+            let expected = Ratio::<u64, u64>::new(a.into(), b.into()).const_cmp(&Ratio::new(c, d));
+            prop_assert_eq!(x.cmp(&y), expected);
+            prop_assert_eq!(y.cmp(&x), expected.reverse());
+            if x == y {
+                prop_assert_eq!(hash_of(&x), hash_of(&y));
+            }
+        }
+    }
+
+    #[test]
+    fn any_ratio_narrowing() {
+        let r = AnyRatio::U64U64(Ratio::new(1_000, 400_000));
+        assert_eq!(
+            Ratio::<u8, u16>::try_from(r).map(|r| (r.n, r.d)),
+            Ok((1, 400))
+        );
+        assert_eq!(Ratio::<u8, u8>::try_from(r), Err(TryFromRatioError));
+    }
+}
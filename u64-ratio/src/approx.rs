@@ -0,0 +1,469 @@
+use core::cmp::Ordering;
+
+use crate::{Ratio, U128Parts, U256};
+
+/// Best rational approximations of a nonzero ratio within given numerator and denominator bounds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Approximations {
+    /// Largest ratio within bounds `<=` the original
+    lower: (u128, u128),
+
+    /// Smallest ratio within bounds `>=` the original.
+    /// `None` if the original is larger than `n_max / 1`
+    upper: Option<(u128, u128)>,
+
+    /// Closest of `lower` and `upper`, `lower` if they are equally close
+    nearest: (u128, u128),
+}
+
+/// Computes the best lower and upper approximations of nonzero `n / d` with
+/// numerator `<= n_max` and denominator `<= d_max` by walking down the Stern–Brocot tree,
+/// taking as many steps in the same direction at once as the continued fraction expansion
+/// of `n / d` and the bounds allow.
+///
+/// Each step at depth `k` moves the bound on the same side as the `k-2`th convergent
+/// through the semiconvergents `(t * p_{k-1} + p_{k-2}) / (t * q_{k-1} + q_{k-2})`
+/// for `t = 1..=a_k`, landing on the `k`th convergent. Even convergents are `<=` `n / d`,
+/// odd ones are `>=` `n / d`.
+///
+/// Once the bounds stop a step at `t < a_k`, the semiconvergent with `t` and the
+/// `k-1`th convergent are adjacent in the Stern–Brocot tree: any ratio strictly between them
+/// has a numerator and denominator larger than their mediant's, which would be out of bounds.
+/// They are therefore the best approximations on either side.
+///
+/// `d_max` must be nonzero.
+fn approximations(n: u128, d: u128, n_max: u128, d_max: u128) -> Approximations {
+    // k-2th and k-1th convergents, starting with 0/1 and 1/0 at k = 0
+    let (mut p2, mut q2) = (0u128, 1u128);
+    let (mut p1, mut q1) = (1u128, 0u128);
+    // complete quotient x_k = u / v, n / d = (x_k * p1 + p2) / (x_k * q1 + q2)
+    let (mut u, mut v) = (n, d);
+    let mut is_k_even = true;
+
+    loop {
+        // division-safety: v is nonzero since the loop exits when remainder is 0
+        let a = u / v;
+        let r = u % v;
+        // p2 <= n_max and q2 <= d_max since all previous steps were within bounds
+        let t_max = match (p1, q1) {
+            (0, _) => (d_max - q2) / q1,
+            (_, 0) => (n_max - p2) / p1,
+            _ => ((n_max - p2) / p1).min((d_max - q2) / q1),
+        };
+
+        if a <= t_max {
+            // unchecked-arith: within bounds since a <= t_max
+            let p = a * p1 + p2;
+            let q = a * q1 + q2;
+            if r == 0 {
+                return Approximations {
+                    lower: (p, q),
+                    upper: Some((p, q)),
+                    nearest: (p, q),
+                };
+            }
+            (p2, q2, p1, q1) = (p1, q1, p, q);
+            (u, v) = (v, r);
+            is_k_even = !is_k_even;
+            continue;
+        }
+
+        // unchecked-arith: within bounds since t_max was computed from the bounds
+        let tq1 = t_max * q1;
+        let semi = (t_max * p1 + p2, tq1 + q2);
+
+        let prev = (p1, q1);
+
+        // 1/0 is only ever the k-1th convergent at k = 0, or the
+        // semiconvergent with t = 0 at k = 1. It means n / d > n_max,
+        // so there is no upper approximation.
+        match (q1, semi.1) {
+            (0, _) => {
+                return Approximations {
+                    lower: semi,
+                    upper: None,
+                    nearest: semi,
+                }
+            }
+            (_, 0) => {
+                return Approximations {
+                    lower: prev,
+                    upper: None,
+                    nearest: prev,
+                }
+            }
+            _ => (),
+        }
+
+        // |x - semi| = (x_k - t) / ((x_k * q1 + q2) * (t * q1 + q2))
+        // |x - prev| = 1 / (q1 * (x_k * q1 + q2))
+        // semi is closer iff u * q1 < v * (t * q1) + v * (t * q1 + q2)
+        let lhs = U256::widening_mul(u, q1);
+        let semi_dist_cmp_prev =
+            match U256::widening_mul(v, tq1).checked_add(U256::widening_mul(v, semi.1)) {
+                None => Ordering::Less,
+                Some(rhs) => lhs.const_cmp(&rhs),
+            };
+
+        let (lower, upper) = if is_k_even {
+            (semi, prev)
+        } else {
+            (prev, semi)
+        };
+        return Approximations {
+            lower,
+            upper: Some(upper),
+            nearest: match semi_dist_cmp_prev {
+                Ordering::Less => semi,
+                Ordering::Greater => prev,
+                Ordering::Equal => lower,
+            },
+        };
+    }
+}
+
+impl<N, D> Ratio<N, D>
+where
+    Self: U128Parts,
+{
+    #[inline]
+    fn approximations<N2, D2>(&self) -> Option<Approximations>
+    where
+        Ratio<N2, D2>: U128Parts,
+    {
+        let (n, d) = self.to_u128_parts();
+        if n == 0 || d == 0 {
+            None
+        } else {
+            Some(approximations(
+                n,
+                d,
+                Ratio::<N2, D2>::N_MAX,
+                Ratio::<N2, D2>::D_MAX,
+            ))
+        }
+    }
+
+    /// Returns the `Ratio<N2, D2>` closest to this ratio.
+    ///
+    /// If 2 ratios are equally close, the smaller one is returned.
+    ///
+    /// The result is in its lowest form and is `0/0` if it is zero.
+    ///
+    /// ```rust
+    /// use sanctum_u64_ratio::Ratio;
+    ///
+    /// let pi: Ratio<u64, u64> = Ratio { n: 3_141_592_653_589_793, d: 1_000_000_000_000_000 };
+    /// assert_eq!(pi.approximate::<u16, u8>(), Ratio { n: 355, d: 113 });
+    /// ```
+    #[inline]
+    pub fn approximate<N2, D2>(&self) -> Ratio<N2, D2>
+    where
+        Ratio<N2, D2>: U128Parts,
+    {
+        let (n, d) = self
+            .approximations::<N2, D2>()
+            .map_or((0, 0), |a| canonical_zero(a.nearest));
+        // approximations are always within bounds, so this never panics
+        Ratio::<N2, D2>::from_u128_parts(n, d).unwrap()
+    }
+
+    /// Returns the largest `Ratio<N2, D2>` that is `<=` this ratio.
+    ///
+    /// The result is in its lowest form and is `0/0` if it is zero.
+    #[inline]
+    pub fn approximate_floor<N2, D2>(&self) -> Ratio<N2, D2>
+    where
+        Ratio<N2, D2>: U128Parts,
+    {
+        let (n, d) = self
+            .approximations::<N2, D2>()
+            .map_or((0, 0), |a| canonical_zero(a.lower));
+        // approximations are always within bounds, so this never panics
+        Ratio::<N2, D2>::from_u128_parts(n, d).unwrap()
+    }
+
+    /// Returns the smallest `Ratio<N2, D2>` that is `>=` this ratio.
+    ///
+    /// The result is in its lowest form and is `0/0` if it is zero.
+    ///
+    /// # Returns
+    ///
+    /// `None` if this ratio is larger than `N2::MAX / 1`
+    #[inline]
+    pub fn approximate_ceil<N2, D2>(&self) -> Option<Ratio<N2, D2>>
+    where
+        Ratio<N2, D2>: U128Parts,
+    {
+        let (n, d) = match self.approximations::<N2, D2>() {
+            None => (0, 0),
+            Some(a) => canonical_zero(a.upper?),
+        };
+        Ratio::<N2, D2>::from_u128_parts(n, d)
+    }
+}
+
+/// `0/1` -> `0/0` to be consistent with [`Ratio::lowest_form`]
+#[inline]
+const fn canonical_zero((n, d): (u128, u128)) -> (u128, u128) {
+    if n == 0 {
+        (0, 0)
+    } else {
+        (n, d)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// `a/b` cmp `c/d`, treating all zero ratios as equal and smallest
+    fn cmp_parts((a, b): (u128, u128), (c, d): (u128, u128)) -> Ordering {
+        match (a == 0 || b == 0, c == 0 || d == 0) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => U256::widening_mul(a, d).cmp(&U256::widening_mul(c, b)),
+        }
+    }
+
+    fn is_lowest_form((n, d): (u128, u128)) -> bool {
+        let mut a = n;
+        let mut b = d;
+        while b > 0 {
+            (a, b) = (b, a % b);
+        }
+        (n, d) == (0, 0) || a == 1
+    }
+
+    /// Checks that the approximations are on the correct sides, in lowest form and
+    /// adjacent in the Stern-Brocot tree with their mediant out of bounds,
+    /// which means there are no closer ratios within bounds.
+    fn check_approximations<N, D, N2, D2>(r: Ratio<N, D>) -> Result<(), TestCaseError>
+    where
+        Ratio<N, D>: U128Parts + core::fmt::Debug,
+        Ratio<N2, D2>: U128Parts + core::fmt::Debug,
+    {
+        let x = r.to_u128_parts();
+        let floor = r.approximate_floor::<N2, D2>().to_u128_parts();
+        let ceil = r.approximate_ceil::<N2, D2>().map(|c| c.to_u128_parts());
+        let nearest = r.approximate::<N2, D2>().to_u128_parts();
+
+        prop_assert!(is_lowest_form(floor), "{floor:?}");
+        prop_assert!(is_lowest_form(nearest), "{nearest:?}");
+        prop_assert_ne!(cmp_parts(floor, x), Ordering::Greater);
+
+        let ceil = match ceil {
+            None => {
+                // x > N2::MAX / 1
+                let max = (Ratio::<N2, D2>::N_MAX, 1);
+                prop_assert_eq!(cmp_parts(x, max), Ordering::Greater);
+                prop_assert_eq!(floor, max);
+                prop_assert_eq!(nearest, max);
+                return Ok(());
+            }
+            Some(c) => c,
+        };
+        prop_assert!(is_lowest_form(ceil), "{ceil:?}");
+        prop_assert_ne!(cmp_parts(ceil, x), Ordering::Less);
+        prop_assert!(nearest == floor || nearest == ceil);
+
+        if cmp_parts(floor, ceil) == Ordering::Equal {
+            prop_assert_eq!(floor, ceil);
+            prop_assert_eq!(cmp_parts(floor, x), Ordering::Equal);
+            return Ok(());
+        }
+
+        // adjacent: ceil.n * floor.d - floor.n * ceil.d == 1
+        let (fn_, fd) = if floor.0 == 0 { (0, 1) } else { floor };
+        let (cn, cd) = ceil;
+        prop_assert_eq!(
+            U256::widening_mul(cn, fd).checked_sub(U256::widening_mul(fn_, cd)),
+            Some(U256::from_u128(1))
+        );
+        // mediant out of bounds
+        let mediant_in_bounds = matches!(
+            (fn_.checked_add(cn), fd.checked_add(cd)),
+            (Some(n), Some(d)) if n <= Ratio::<N2, D2>::N_MAX && d <= Ratio::<N2, D2>::D_MAX
+        );
+        prop_assert!(!mediant_in_bounds, "{floor:?} {ceil:?}");
+        Ok(())
+    }
+
+    macro_rules! test_suite {
+        ($N:ty, $D:ty, $test_mod:ident) => {
+            mod $test_mod {
+                use super::*;
+
+                type R = Ratio<$N, $D>;
+
+                proptest! {
+                    #[test]
+                    fn approximations_optimal(n: $N, d: $D, small_n in 0..=<$N>::from(u8::MAX), small_d in 0..=<$D>::from(u8::MAX)) {
+                        for r in [R::new(n, d), R::new(small_n, d), R::new(n, small_d), R::new(small_n, small_d)] {
+                            check_approximations::<$N, $D, u8, u8>(r)?;
+                            check_approximations::<$N, $D, u8, u64>(r)?;
+                            check_approximations::<$N, $D, u16, u16>(r)?;
+                            check_approximations::<$N, $D, u32, u16>(r)?;
+                            check_approximations::<$N, $D, u64, u64>(r)?;
+                            check_approximations::<$N, $D, u128, u8>(r)?;
+                            check_approximations::<$N, $D, u128, u128>(r)?;
+                        }
+                    }
+                }
+
+                proptest! {
+                    #[test]
+                    fn approximate_identity_if_fits(n: $N, d: $D) {
+                        let r = R::new(n, d);
+                        prop_assert_eq!(r.approximate::<$N, $D>(), r);
+                        prop_assert_eq!(r.approximate_floor::<$N, $D>(), r);
+                        prop_assert_eq!(r.approximate_ceil::<$N, $D>(), Some(r));
+                        let l = r.lowest_form();
+                        prop_assert_eq!(
+                            r.approximate::<$N, $D>().to_u128_parts(),
+                            (l.n.into(), l.d.into())
+                        );
+                    }
+                }
+            }
+        };
+    }
+
+    /// Compares against a brute force search over all `Ratio<u8, u8>`s
+    macro_rules! brute_force_suite {
+        ($N:ty, $D:ty, $test_mod:ident) => {
+            mod $test_mod {
+                use super::*;
+
+                type R = Ratio<$N, $D>;
+
+                proptest! {
+                    #[test]
+                    fn matches_brute_force(n in 1..=<$N>::MAX, d in 1..=<$D>::MAX) {
+                        let (n, d) = (n as u128, d as u128);
+                        let mut lower = (0u128, 1u128);
+                        let mut upper = None;
+                        for q in 1..=255u128 {
+                            for p in 0..=255u128 {
+                                match (p * d).cmp(&(n * q)) {
+                                    Ordering::Greater => {
+                                        if upper.map_or(true, |(un, ud): (u128, u128)| p * ud < un * q) {
+                                            upper = Some((p, q));
+                                        }
+                                    }
+                                    _ => {
+                                        if p * lower.1 > lower.0 * q {
+                                            lower = (p, q);
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        // exact match is both lower and upper
+                        if p_eq(lower, (n, d)) {
+                            upper = Some(lower);
+                        }
+                        // |x - lower| <= |upper - x|, tie goes to lower
+                        let nearest = match upper {
+                            None => lower,
+                            Some(u) => {
+                                let dist_lower = (n * lower.1 - lower.0 * d) * u.1;
+                                let dist_upper = (u.0 * d - n * u.1) * lower.1;
+                                if dist_lower <= dist_upper { lower } else { u }
+                            }
+                        };
+
+                        let r = R::new(n as $N, d as $D);
+                        let [lower, nearest] = [lower, nearest].map(canonical_zero);
+                        prop_assert_eq!(r.approximate_floor::<u8, u8>().to_u128_parts(), lower);
+                        prop_assert_eq!(
+                            r.approximate_ceil::<u8, u8>().map(|c| c.to_u128_parts()),
+                            upper.map(canonical_zero)
+                        );
+                        prop_assert_eq!(r.approximate::<u8, u8>().to_u128_parts(), nearest);
+                    }
+                }
+            }
+        };
+    }
+
+    fn p_eq((a, b): (u128, u128), (c, d): (u128, u128)) -> bool {
+        a * d == c * b
+    }
+
+    test_suite!(u8, u8, approx_u8_u8_tests);
+    test_suite!(u8, u16, approx_u8_u16_tests);
+    test_suite!(u8, u32, approx_u8_u32_tests);
+    test_suite!(u8, u64, approx_u8_u64_tests);
+    test_suite!(u8, u128, approx_u8_u128_tests);
+    test_suite!(u16, u8, approx_u16_u8_tests);
+    test_suite!(u16, u16, approx_u16_u16_tests);
+    test_suite!(u16, u32, approx_u16_u32_tests);
+    test_suite!(u16, u64, approx_u16_u64_tests);
+    test_suite!(u16, u128, approx_u16_u128_tests);
+    test_suite!(u32, u8, approx_u32_u8_tests);
+    test_suite!(u32, u16, approx_u32_u16_tests);
+    test_suite!(u32, u32, approx_u32_u32_tests);
+    test_suite!(u32, u64, approx_u32_u64_tests);
+    test_suite!(u32, u128, approx_u32_u128_tests);
+    test_suite!(u64, u8, approx_u64_u8_tests);
+    test_suite!(u64, u16, approx_u64_u16_tests);
+    test_suite!(u64, u32, approx_u64_u32_tests);
+    test_suite!(u64, u64, approx_u64_u64_tests);
+    test_suite!(u64, u128, approx_u64_u128_tests);
+    test_suite!(u128, u8, approx_u128_u8_tests);
+    test_suite!(u128, u16, approx_u128_u16_tests);
+    test_suite!(u128, u32, approx_u128_u32_tests);
+    test_suite!(u128, u64, approx_u128_u64_tests);
+    test_suite!(u128, u128, approx_u128_u128_tests);
+
+    brute_force_suite!(u8, u8, approx_brute_force_u8_u8_tests);
+    brute_force_suite!(u8, u16, approx_brute_force_u8_u16_tests);
+    brute_force_suite!(u8, u32, approx_brute_force_u8_u32_tests);
+    brute_force_suite!(u8, u64, approx_brute_force_u8_u64_tests);
+    brute_force_suite!(u16, u8, approx_brute_force_u16_u8_tests);
+    brute_force_suite!(u16, u16, approx_brute_force_u16_u16_tests);
+    brute_force_suite!(u16, u32, approx_brute_force_u16_u32_tests);
+    brute_force_suite!(u16, u64, approx_brute_force_u16_u64_tests);
+    brute_force_suite!(u32, u8, approx_brute_force_u32_u8_tests);
+    brute_force_suite!(u32, u16, approx_brute_force_u32_u16_tests);
+    brute_force_suite!(u32, u32, approx_brute_force_u32_u32_tests);
+    brute_force_suite!(u32, u64, approx_brute_force_u32_u64_tests);
+    brute_force_suite!(u64, u8, approx_brute_force_u64_u8_tests);
+    brute_force_suite!(u64, u16, approx_brute_force_u64_u16_tests);
+    brute_force_suite!(u64, u32, approx_brute_force_u64_u32_tests);
+    brute_force_suite!(u64, u64, approx_brute_force_u64_u64_tests);
+
+    #[test]
+    fn approximate_cases() {
+        let pi: Ratio<u64, u64> = Ratio {
+            n: 3_141_592_653_589_793,
+            d: 1_000_000_000_000_000,
+        };
+        assert_eq!(pi.approximate::<u8, u8>(), Ratio { n: 245, d: 78 });
+        assert_eq!(pi.approximate_floor::<u8, u8>(), Ratio { n: 245, d: 78 });
+        assert_eq!(pi.approximate_ceil::<u8, u8>(), Some(Ratio { n: 22, d: 7 }));
+        assert_eq!(pi.approximate::<u16, u8>(), Ratio { n: 355, d: 113 });
+
+        let big: Ratio<u64, u8> = Ratio { n: 1_000, d: 1 };
+        assert_eq!(big.approximate::<u8, u8>(), Ratio { n: 255, d: 1 });
+        assert_eq!(big.approximate_ceil::<u8, u8>(), None);
+
+        let tiny: Ratio<u8, u64> = Ratio { n: 1, d: 1_000 };
+        assert_eq!(tiny.approximate::<u8, u8>(), Ratio { n: 0, d: 0 });
+        assert_eq!(tiny.approximate_floor::<u8, u8>(), Ratio { n: 0, d: 0 });
+        assert_eq!(
+            tiny.approximate_ceil::<u8, u8>(),
+            Some(Ratio { n: 1, d: 255 })
+        );
+
+        let zero: Ratio<u64, u64> = Ratio { n: 0, d: 5 };
+        assert_eq!(
+            zero.approximate_ceil::<u8, u8>(),
+            Some(Ratio::<u8, u8>::ZERO)
+        );
+    }
+}
@@ -0,0 +1,460 @@
+use crate::{gcd_u128, gcd_u16, gcd_u32, gcd_u64, gcd_u8, ArithTypes, Ratio, U256};
+
+macro_rules! impl_ratio_arith {
+    ($N:ty, $D:ty, [$gcd:expr, $EXT:ident]) => {
+        impl Ratio<$N, $D> {
+            /// [`Self::lowest_form`], but cast back into `Self`.
+            ///
+            /// Lossless because reducing a fraction never increases `n` or `d`
+            #[inline]
+            pub(crate) const fn lowest_form_self(&self) -> Self {
+                let Ratio { n, d } = self.lowest_form();
+                Self {
+                    n: n as $N,
+                    d: d as $D,
+                }
+            }
+
+            /// Constructs `num / (b1 * d)` in lowest form given that it is the
+            /// result of adding or subtracting 2 fractions in lowest form `a/b` and `c/d`
+            /// where `g = gcd(b, d)`, `b1 = b/g`, `num = a(d/g) ± c(b/g)`.
+            ///
+            /// `gcd(num, b1 * d) = gcd(num, g)` since `gcd(a, b) = gcd(c, d) = gcd(b/g, d/g) = 1`.
+            /// Ref: Knuth, TAOCP Vol. 2, 4.5.1.
+            ///
+            /// Returns `None` if the reduced fraction does not fit into `Self`.
+            #[inline]
+            const fn from_sum_parts(
+                num: <Self as ArithTypes>::Ext,
+                b1: <Self as ArithTypes>::Max,
+                d: <Self as ArithTypes>::Max,
+                g: <Self as ArithTypes>::Max,
+            ) -> Option<Self> {
+                type Max = <Ratio<$N, $D> as ArithTypes>::Max;
+
+                if ext_is_zero!($EXT, num) {
+                    return Some(Self::ZERO);
+                }
+                // division-safety: g != 0 since b and d are nonzero
+                let (_, r) = ext_div_rem!($EXT, num, g);
+                // gcd(num, g) = gcd(g, num % g)
+                let g2 = $gcd(g, r as Max);
+                // division-safety: g2 != 0 since g != 0
+                let (n, _) = ext_div_rem!($EXT, num, g2);
+                let n = match ext_to_checked!($EXT, n, $N) {
+                    None => return None,
+                    Some(n) => n,
+                };
+                // g2 divides g which divides d
+                let d = ext_mul!($EXT, b1, d / g2);
+                let d = match ext_to_checked!($EXT, d, $D) {
+                    None => return None,
+                    Some(d) => d,
+                };
+                Some(Self { n, d })
+            }
+
+            /// Fraction multiplication.
+            ///
+            /// Both operands are reduced to their lowest forms and cross-reduced
+            /// before multiplying to avoid overflows, so the result is in its lowest form.
+            ///
+            /// # Returns
+            ///
+            /// - [`Self::ZERO`] if either operand [`Self::is_zero()`]
+            /// - `None` if the result in its lowest form does not fit into `Self`
+            ///
+            /// ```rust
+            /// use sanctum_u64_ratio::Ratio;
+            ///
+            #[doc = concat!("type R = Ratio<", stringify!($N), ", ", stringify!($D), ">;")]
+            ///
+            /// // even though 100 * 100 overflows u8,
+            /// // 100/3 * 3/100 = 1/1
+            /// assert_eq!(R::new(100, 3).checked_mul(&R::new(3, 100)), Some(R::ONE));
+            /// ```
+            #[inline]
+            pub const fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+                if self.is_zero() || rhs.is_zero() {
+                    return Some(Self::ZERO);
+                }
+                let Ratio { n: a, d: b } = self.lowest_form();
+                let Ratio { n: c, d } = rhs.lowest_form();
+                // division-safety: all of a, b, c, d are nonzero
+                let g1 = $gcd(d, a);
+                let g2 = $gcd(b, c);
+                Self::from_reduced_products(a / g1, c / g2, b / g2, d / g1)
+            }
+
+            /// `(n1 * n2) / (d1 * d2)` where the result is already known to be in lowest form
+            #[inline]
+            const fn from_reduced_products(
+                n1: <Self as ArithTypes>::Max,
+                n2: <Self as ArithTypes>::Max,
+                d1: <Self as ArithTypes>::Max,
+                d2: <Self as ArithTypes>::Max,
+            ) -> Option<Self> {
+                type Max = <Ratio<$N, $D> as ArithTypes>::Max;
+
+                // overflowing Max means it cannot fit into N or D either
+                let n = match n1.checked_mul(n2) {
+                    Some(n) if n <= <$N>::MAX as Max => n as $N,
+                    _ => return None,
+                };
+                let d = match d1.checked_mul(d2) {
+                    Some(d) if d <= <$D>::MAX as Max => d as $D,
+                    _ => return None,
+                };
+                Some(Self { n, d })
+            }
+
+            /// Fraction division.
+            ///
+            /// See [`Self::checked_mul`] for more details.
+            ///
+            /// # Returns
+            ///
+            /// - `None` if `rhs` [`Self::is_zero()`]
+            /// - [`Self::ZERO`] if `self` [`Self::is_zero()`]
+            /// - `None` if the result in its lowest form does not fit into `Self`
+            #[inline]
+            pub const fn checked_div(&self, rhs: &Self) -> Option<Self> {
+                if rhs.is_zero() {
+                    return None;
+                }
+                if self.is_zero() {
+                    return Some(Self::ZERO);
+                }
+                let Ratio { n: a, d: b } = self.lowest_form();
+                // multiply by c/d = rhs.recip()
+                let Ratio { n: d, d: c } = rhs.lowest_form();
+                // division-safety: all of a, b, c, d are nonzero
+                let g1 = $gcd(d, a);
+                let g2 = $gcd(b, c);
+                Self::from_reduced_products(a / g1, c / g2, b / g2, d / g1)
+            }
+
+            /// Reciprocal. `d/n`
+            ///
+            /// # Returns
+            ///
+            /// `None` if `self` [`Self::is_zero()`]
+            #[inline]
+            pub const fn recip(&self) -> Option<Ratio<$D, $N>> {
+                if self.is_zero() {
+                    None
+                } else {
+                    Some(Ratio {
+                        n: self.d,
+                        d: self.n,
+                    })
+                }
+            }
+
+            /// Fraction addition.
+            ///
+            /// Both operands are reduced to their lowest forms and the
+            /// gcd of their denominators is factored out before multiplying to avoid overflows,
+            /// so the result is in its lowest form.
+            ///
+            /// # Returns
+            ///
+            /// - the lowest form of the other operand if either operand [`Self::is_zero()`]
+            /// - `None` if the result in its lowest form does not fit into `Self`
+            ///
+            /// ```rust
+            /// use sanctum_u64_ratio::Ratio;
+            ///
+            #[doc = concat!("type R = Ratio<", stringify!($N), ", ", stringify!($D), ">;")]
+            ///
+            /// // 1/6 + 1/3 = 1/2
+            /// assert_eq!(R::new(1, 6).checked_add(&R::new(1, 3)), Some(R::new(1, 2)));
+            /// ```
+            #[inline]
+            pub const fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                if rhs.is_zero() {
+                    return Some(self.lowest_form_self());
+                }
+                if self.is_zero() {
+                    return Some(rhs.lowest_form_self());
+                }
+                let Ratio { n: a, d: b } = self.lowest_form();
+                let Ratio { n: c, d } = rhs.lowest_form();
+                // division-safety: b and d are nonzero
+                let g = $gcd(d, b);
+                let b1 = b / g;
+                let d1 = d / g;
+                let ad1 = ext_mul!($EXT, a, d1);
+                let cb1 = ext_mul!($EXT, c, b1);
+                // overflowing Ext means the reduced numerator,
+                // which is >= num / g > Ext::MAX / Max::MAX, cannot fit into N either
+                let num = match ad1.checked_add(cb1) {
+                    None => return None,
+                    Some(x) => x,
+                };
+                Self::from_sum_parts(num, b1, d, g)
+            }
+
+            /// Fraction subtraction.
+            ///
+            /// See [`Self::checked_add`] for more details.
+            ///
+            /// # Returns
+            ///
+            /// - the lowest form of `self` if `rhs` [`Self::is_zero()`]
+            /// - [`Self::ZERO`] if `self == rhs`
+            /// - `None` if `rhs > self`
+            /// - `None` if the result in its lowest form does not fit into `Self`
+            #[inline]
+            pub const fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+                if rhs.is_zero() {
+                    return Some(self.lowest_form_self());
+                }
+                if self.is_zero() {
+                    return None;
+                }
+                let Ratio { n: a, d: b } = self.lowest_form();
+                let Ratio { n: c, d } = rhs.lowest_form();
+                // division-safety: b and d are nonzero
+                let g = $gcd(d, b);
+                let b1 = b / g;
+                let d1 = d / g;
+                let ad1 = ext_mul!($EXT, a, d1);
+                let cb1 = ext_mul!($EXT, c, b1);
+                let num = match ad1.checked_sub(cb1) {
+                    None => return None,
+                    Some(x) => x,
+                };
+                Self::from_sum_parts(num, b1, d, g)
+            }
+        }
+    };
+}
+
+impl_ratio_arith!(u8, u8, [gcd_u8, u16]);
+impl_ratio_arith!(u8, u16, [gcd_u16, u32]);
+impl_ratio_arith!(u8, u32, [gcd_u32, u64]);
+impl_ratio_arith!(u8, u64, [gcd_u64, u128]);
+impl_ratio_arith!(u8, u128, [gcd_u128, U256]);
+
+impl_ratio_arith!(u16, u8, [gcd_u16, u32]);
+impl_ratio_arith!(u16, u16, [gcd_u16, u32]);
+impl_ratio_arith!(u16, u32, [gcd_u32, u64]);
+impl_ratio_arith!(u16, u64, [gcd_u64, u128]);
+impl_ratio_arith!(u16, u128, [gcd_u128, U256]);
+
+impl_ratio_arith!(u32, u8, [gcd_u32, u64]);
+impl_ratio_arith!(u32, u16, [gcd_u32, u64]);
+impl_ratio_arith!(u32, u32, [gcd_u32, u64]);
+impl_ratio_arith!(u32, u64, [gcd_u64, u128]);
+impl_ratio_arith!(u32, u128, [gcd_u128, U256]);
+
+impl_ratio_arith!(u64, u8, [gcd_u64, u128]);
+impl_ratio_arith!(u64, u16, [gcd_u64, u128]);
+impl_ratio_arith!(u64, u32, [gcd_u64, u128]);
+impl_ratio_arith!(u64, u64, [gcd_u64, u128]);
+impl_ratio_arith!(u64, u128, [gcd_u128, U256]);
+
+impl_ratio_arith!(u128, u8, [gcd_u128, U256]);
+impl_ratio_arith!(u128, u16, [gcd_u128, U256]);
+impl_ratio_arith!(u128, u32, [gcd_u128, U256]);
+impl_ratio_arith!(u128, u64, [gcd_u128, U256]);
+impl_ratio_arith!(u128, u128, [gcd_u128, U256]);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// Reference: `n/d` in lowest form if it fits into `Ratio<N, D>`
+    fn reduced_ref<N: TryFrom<u128>, D: TryFrom<u128>>(n: u128, d: u128) -> Option<Ratio<N, D>> {
+        if n == 0 {
+            return Some(Ratio {
+                n: N::try_from(0).ok()?,
+                d: D::try_from(0).ok()?,
+            });
+        }
+        let g = gcd_u128(n, d);
+        Some(Ratio {
+            n: N::try_from(n / g).ok()?,
+            d: D::try_from(d / g).ok()?,
+        })
+    }
+
+    /// Results should be compared against [`reduced_ref`]
+    /// for ratios with n and d no larger than u64, whose products fit into u128
+    macro_rules! test_suite_ref {
+        ($N:ty, $D:ty, $test_mod:ident) => {
+            mod $test_mod {
+                use super::*;
+
+                type R = Ratio<$N, $D>;
+
+                proptest! {
+                    #[test]
+                    fn mul_div_matches_ref(a: $N, b in 1..=<$D>::MAX, c: $N, d in 1..=<$D>::MAX) {
+                        let [l, r] = [R::new(a, b), R::new(c, d)];
+                        let [a, b, c, d] = [a as u128, b as u128, c as u128, d as u128];
+
+                        let expected = reduced_ref(a * c, b * d);
+                        prop_assert_eq!(l.checked_mul(&r), expected);
+
+                        let expected = if c == 0 { None } else { reduced_ref(a * d, b * c) };
+                        prop_assert_eq!(l.checked_div(&r), expected);
+                    }
+                }
+
+                proptest! {
+                    #[test]
+                    fn add_sub_matches_ref(a: $N, b in 1..=<$D>::MAX, c: $N, d in 1..=<$D>::MAX) {
+                        let [l, r] = [R::new(a, b), R::new(c, d)];
+                        let [a, b, c, d] = [a as u128, b as u128, c as u128, d as u128];
+                        let den = b * d;
+
+                        if let Some(num) = (a * d).checked_add(c * b) {
+                            prop_assert_eq!(l.checked_add(&r), reduced_ref(num, den));
+                        }
+                        let expected = (a * d).checked_sub(c * b).and_then(|num| reduced_ref(num, den));
+                        prop_assert_eq!(l.checked_sub(&r), expected);
+                    }
+                }
+            }
+        };
+    }
+
+    macro_rules! test_suite {
+        ($N:ty, $D:ty, $test_mod:ident) => {
+            mod $test_mod {
+                use super::*;
+
+                type R = Ratio<$N, $D>;
+
+                fn assert_lowest_form(r: &R) -> Result<(), TestCaseError> {
+                    let l = r.lowest_form();
+                    prop_assert_eq!(
+                        (l.n, l.d),
+                        (r.n.into(), r.d.into()),
+                        "{} not in lowest form",
+                        r
+                    );
+                    Ok(())
+                }
+
+                proptest! {
+                    #[test]
+                    fn mul_div_props(a: $N, b: $D, c: $N, d: $D) {
+                        let [l, r] = [R::new(a, b), R::new(c, d)];
+
+                        let prod = l.checked_mul(&r);
+                        prop_assert_eq!(prod, r.checked_mul(&l));
+                        if let Some(prod) = prod {
+                            assert_lowest_form(&prod)?;
+                            if !r.is_zero() {
+                                prop_assert_eq!(prod.checked_div(&r), Some(l.lowest_form_self()));
+                            }
+                        }
+
+                        match l.checked_div(&r) {
+                            None => {}
+                            Some(quot) => {
+                                assert_lowest_form(&quot)?;
+                                prop_assert_eq!(quot.checked_mul(&r), Some(l.lowest_form_self()));
+                            }
+                        }
+                        if r.is_zero() {
+                            prop_assert_eq!(l.checked_div(&r), None);
+                        }
+                        if l.is_zero() || r.is_zero() {
+                            prop_assert_eq!(l.checked_mul(&r), Some(R::ZERO));
+                        }
+                    }
+                }
+
+                proptest! {
+                    #[test]
+                    fn add_sub_props(a: $N, b: $D, c: $N, d: $D) {
+                        let [l, r] = [R::new(a, b), R::new(c, d)];
+
+                        let sum = l.checked_add(&r);
+                        prop_assert_eq!(sum, r.checked_add(&l));
+                        if let Some(sum) = sum {
+                            assert_lowest_form(&sum)?;
+                            prop_assert_ne!(sum.const_cmp(&l), core::cmp::Ordering::Less);
+                            prop_assert_eq!(sum.checked_sub(&r), Some(l.lowest_form_self()));
+                        }
+
+                        match l.checked_sub(&r) {
+                            None => {}
+                            Some(diff) => {
+                                assert_lowest_form(&diff)?;
+                                prop_assert_ne!(l.const_cmp(&r), core::cmp::Ordering::Less);
+                                prop_assert_eq!(diff.checked_add(&r), Some(l.lowest_form_self()));
+                            }
+                        }
+                        if l.const_cmp(&r) == core::cmp::Ordering::Less {
+                            prop_assert_eq!(l.checked_sub(&r), None);
+                        }
+                        prop_assert_eq!(l.checked_sub(&l), Some(R::ZERO));
+                    }
+                }
+
+                proptest! {
+                    #[test]
+                    fn recip_props(a: $N, b: $D) {
+                        let r = R::new(a, b);
+                        match r.recip() {
+                            None => prop_assert!(r.is_zero()),
+                            Some(recip) => {
+                                prop_assert_eq!(recip.recip(), Some(r));
+                            }
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    test_suite_ref!(u8, u8, arith_ref_u8_u8_tests);
+    test_suite_ref!(u8, u16, arith_ref_u8_u16_tests);
+    test_suite_ref!(u8, u32, arith_ref_u8_u32_tests);
+    test_suite_ref!(u8, u64, arith_ref_u8_u64_tests);
+    test_suite_ref!(u16, u8, arith_ref_u16_u8_tests);
+    test_suite_ref!(u16, u16, arith_ref_u16_u16_tests);
+    test_suite_ref!(u16, u32, arith_ref_u16_u32_tests);
+    test_suite_ref!(u16, u64, arith_ref_u16_u64_tests);
+    test_suite_ref!(u32, u8, arith_ref_u32_u8_tests);
+    test_suite_ref!(u32, u16, arith_ref_u32_u16_tests);
+    test_suite_ref!(u32, u32, arith_ref_u32_u32_tests);
+    test_suite_ref!(u32, u64, arith_ref_u32_u64_tests);
+    test_suite_ref!(u64, u8, arith_ref_u64_u8_tests);
+    test_suite_ref!(u64, u16, arith_ref_u64_u16_tests);
+    test_suite_ref!(u64, u32, arith_ref_u64_u32_tests);
+    test_suite_ref!(u64, u64, arith_ref_u64_u64_tests);
+
+    test_suite!(u8, u8, arith_u8_u8_tests);
+    test_suite!(u8, u16, arith_u8_u16_tests);
+    test_suite!(u8, u32, arith_u8_u32_tests);
+    test_suite!(u8, u64, arith_u8_u64_tests);
+    test_suite!(u8, u128, arith_u8_u128_tests);
+    test_suite!(u16, u8, arith_u16_u8_tests);
+    test_suite!(u16, u16, arith_u16_u16_tests);
+    test_suite!(u16, u32, arith_u16_u32_tests);
+    test_suite!(u16, u64, arith_u16_u64_tests);
+    test_suite!(u16, u128, arith_u16_u128_tests);
+    test_suite!(u32, u8, arith_u32_u8_tests);
+    test_suite!(u32, u16, arith_u32_u16_tests);
+    test_suite!(u32, u32, arith_u32_u32_tests);
+    test_suite!(u32, u64, arith_u32_u64_tests);
+    test_suite!(u32, u128, arith_u32_u128_tests);
+    test_suite!(u64, u8, arith_u64_u8_tests);
+    test_suite!(u64, u16, arith_u64_u16_tests);
+    test_suite!(u64, u32, arith_u64_u32_tests);
+    test_suite!(u64, u64, arith_u64_u64_tests);
+    test_suite!(u64, u128, arith_u64_u128_tests);
+    test_suite!(u128, u8, arith_u128_u8_tests);
+    test_suite!(u128, u16, arith_u128_u16_tests);
+    test_suite!(u128, u32, arith_u128_u32_tests);
+    test_suite!(u128, u64, arith_u128_u64_tests);
+    test_suite!(u128, u128, arith_u128_u128_tests);
+}
@@ -0,0 +1,182 @@
+use core::{
+    fmt::{Display, Formatter},
+    ops::RangeInclusive,
+};
+
+use crate::{Ceil, Floor, Ratio, RatioApply};
+
+/// A zero-sized ratio `(N/D)` whose numerator and denominator are known at compile time.
+///
+/// Must use with [`crate::Ceil`] or [`crate::Floor`] for application on [`u64`]s,
+/// with the same API as their [`Ratio`] counterparts.
+///
+/// `D == 0` is rejected at compile time when the ratio is used.
+///
+/// ```compile_fail
+/// use sanctum_u64_ratio::{ConstRatio, Floor};
+///
+/// let _ = Floor(ConstRatio::<1, 0>::new()).apply(1);
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ConstRatio<const N: u64, const D: u64>;
+
+/// Displayed as `{N}/{D}`
+impl<const N: u64, const D: u64> Display for ConstRatio<N, D> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!("{N}/{D}"))
+    }
+}
+
+impl<const N: u64, const D: u64> ConstRatio<N, D> {
+    /// This ratio as a runtime [`Ratio`].
+    ///
+    /// Fails to compile if `D == 0`
+    pub const RATIO: Ratio<u64, u64> = {
+        assert!(D != 0, "ConstRatio denominator must be nonzero");
+        Ratio { n: N, d: D }
+    };
+
+    /// Fails to compile if `D == 0`
+    #[inline]
+    pub const fn new() -> Self {
+        let _ = Self::RATIO;
+        Self
+    }
+
+    #[inline]
+    pub const fn to_ratio(self) -> Ratio<u64, u64> {
+        Self::RATIO
+    }
+}
+
+impl<const N: u64, const D: u64> From<ConstRatio<N, D>> for Ratio<u64, u64> {
+    #[inline]
+    fn from(r: ConstRatio<N, D>) -> Self {
+        r.to_ratio()
+    }
+}
+
+macro_rules! impl_const_ratio_div {
+    ($R:ident) => {
+        impl<const N: u64, const D: u64> $R<ConstRatio<N, D>> {
+            #[doc = concat!("[`", stringify!($R), "::apply`] for [`ConstRatio`]s")]
+            #[inline]
+            pub const fn apply(&self, amount: u64) -> Option<u64> {
+                $R(ConstRatio::<N, D>::RATIO).apply(amount)
+            }
+
+            #[doc = concat!("[`", stringify!($R), "::reverse`] for [`ConstRatio`]s")]
+            #[inline]
+            pub const fn reverse(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                $R(ConstRatio::<N, D>::RATIO).reverse(amt_after_apply)
+            }
+
+            #[doc = concat!("[`", stringify!($R), "::reverse_est`] for [`ConstRatio`]s")]
+            #[inline]
+            pub const fn reverse_est(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                $R(ConstRatio::<N, D>::RATIO).reverse_est(amt_after_apply)
+            }
+
+            #[doc = concat!("[`", stringify!($R), "::apply_u128`] for [`ConstRatio`]s")]
+            #[inline]
+            pub const fn apply_u128(&self, amount: u128) -> Option<u128> {
+                $R(ConstRatio::<N, D>::RATIO).apply_u128(amount)
+            }
+
+            #[doc = concat!("[`", stringify!($R), "::reverse_u128`] for [`ConstRatio`]s")]
+            #[inline]
+            pub const fn reverse_u128(
+                &self,
+                amt_after_apply: u128,
+            ) -> Option<RangeInclusive<u128>> {
+                $R(ConstRatio::<N, D>::RATIO).reverse_u128(amt_after_apply)
+            }
+
+            #[doc = concat!("[`", stringify!($R), "::reverse_est_u128`] for [`ConstRatio`]s")]
+            #[inline]
+            pub const fn reverse_est_u128(
+                &self,
+                amt_after_apply: u128,
+            ) -> Option<RangeInclusive<u128>> {
+                $R(ConstRatio::<N, D>::RATIO).reverse_est_u128(amt_after_apply)
+            }
+        }
+
+        impl<const N: u64, const D: u64> RatioApply for $R<ConstRatio<N, D>> {
+            #[inline]
+            fn apply(&self, amount: u64) -> Option<u64> {
+                Self::apply(self, amount)
+            }
+
+            #[inline]
+            fn reverse_est(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                Self::reverse_est(self, amt_after_apply)
+            }
+
+            #[inline]
+            fn reverse(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                Self::reverse(self, amt_after_apply)
+            }
+        }
+    };
+}
+
+impl_const_ratio_div!(Floor);
+impl_const_ratio_div!(Ceil);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn const_ratio_zero_sized() {
+        assert_eq!(core::mem::size_of::<ConstRatio<1, 1_000>>(), 0);
+        assert_eq!(core::mem::size_of::<Floor<ConstRatio<1, 1_000>>>(), 0);
+        assert_eq!(ConstRatio::<1, 1_000>::new().to_string(), "1/1000");
+        assert_eq!(
+            Ratio::from(ConstRatio::<1, 1_000>::new()),
+            Ratio { n: 1, d: 1_000 }
+        );
+    }
+
+    macro_rules! check {
+        ($c:expr, $r:expr, $amt:expr, $aaf:expr, $wide_amt:expr, $wide_aaf:expr) => {{
+            let (c, r) = ($c, $r);
+            prop_assert_eq!(c.apply($amt), r.apply($amt));
+            prop_assert_eq!(c.reverse($aaf), r.reverse($aaf));
+            prop_assert_eq!(c.reverse_est($aaf), r.reverse_est($aaf));
+            prop_assert_eq!(c.apply_u128($wide_amt), r.apply_u128($wide_amt));
+            prop_assert_eq!(c.reverse_u128($wide_aaf), r.reverse_u128($wide_aaf));
+            prop_assert_eq!(c.reverse_est_u128($wide_aaf), r.reverse_est_u128($wide_aaf));
+            prop_assert_eq!(RatioApply::reverse(&c, $aaf), r.reverse($aaf));
+        }};
+    }
+
+    macro_rules! test_suite {
+        ($N:expr, $D:expr, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(amt: u64, aaf: u64, wide_amt: u128, wide_aaf: u128) {
+                    type C = ConstRatio<{ $N }, { $D }>;
+                    let r = C::RATIO;
+                    check!(Floor(C::new()), Floor(r), amt, aaf, wide_amt, wide_aaf);
+                    check!(Ceil(C::new()), Ceil(r), amt, aaf, wide_amt, wide_aaf);
+                }
+            }
+        };
+    }
+
+    test_suite!(0, 1, const_ratio_zero);
+    test_suite!(1, 1, const_ratio_one);
+    test_suite!(1, 1_000, const_ratio_10_bps);
+    test_suite!(3, 7, const_ratio_3_7);
+    test_suite!(7, 3, const_ratio_7_3);
+    test_suite!(1_000_000_000, 1, const_ratio_decimals_9);
+    test_suite!(1, 1_000_000_000, const_ratio_inv_decimals_9);
+    test_suite!(u64::MAX, 1, const_ratio_max_1);
+    test_suite!(1, u64::MAX, const_ratio_1_max);
+    test_suite!(u64::MAX, u64::MAX - 1, const_ratio_max_max_minus_1);
+}
@@ -0,0 +1,329 @@
+use core::fmt::{Display, Formatter};
+
+use crate::{Ceil, Floor, Ratio};
+
+/// Error returned when converting a [`Ratio`] into a narrower `Ratio` type
+/// that neither it nor its lowest form fits into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TryFromRatioError;
+
+impl Display for TryFromRatioError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("ratio does not fit into target type")
+    }
+}
+
+/// Conversion from `Ratio<$N1, $D1>` where `$N2` and `$D2` are at least as wide as `$N1` and `$D1`
+macro_rules! impl_from {
+    ($N1:ty, $D1:ty, $N2:ty, $D2:ty) => {
+        impl From<Ratio<$N1, $D1>> for Ratio<$N2, $D2> {
+            #[inline]
+            fn from(Ratio { n, d }: Ratio<$N1, $D1>) -> Self {
+                Self {
+                    n: n.into(),
+                    d: d.into(),
+                }
+            }
+        }
+
+        impl From<Floor<Ratio<$N1, $D1>>> for Floor<Ratio<$N2, $D2>> {
+            #[inline]
+            fn from(Floor(r): Floor<Ratio<$N1, $D1>>) -> Self {
+                Self(r.into())
+            }
+        }
+
+        impl From<Ceil<Ratio<$N1, $D1>>> for Ceil<Ratio<$N2, $D2>> {
+            #[inline]
+            fn from(Ceil(r): Ceil<Ratio<$N1, $D1>>) -> Self {
+                Self(r.into())
+            }
+        }
+    };
+}
+
+/// Conversion from `Ratio<$N1, $D1>` where at least one of `$N2` and `$D2` is narrower than
+/// `$N1` and `$D1` respectively.
+macro_rules! impl_try_from {
+    ($N1:ty, $D1:ty, $N2:ty, $D2:ty) => {
+        /// The ratio is converted to its [`Ratio::lowest_form`], so that ratios of the same value
+        /// always convert to the same result regardless of how they were scaled.
+        ///
+        /// # Errors
+        ///
+        /// [`TryFromRatioError`] if the ratio's lowest form does not fit
+        impl TryFrom<Ratio<$N1, $D1>> for Ratio<$N2, $D2> {
+            type Error = TryFromRatioError;
+
+            #[inline]
+            fn try_from(r: Ratio<$N1, $D1>) -> Result<Self, Self::Error> {
+                let Ratio { n, d } = r.lowest_form();
+                match (<$N2>::try_from(n), <$D2>::try_from(d)) {
+                    (Ok(n), Ok(d)) => Ok(Self { n, d }),
+                    _ => Err(TryFromRatioError),
+                }
+            }
+        }
+
+        /// See [`Ratio`]'s [`TryFrom`] implementation
+        impl TryFrom<Floor<Ratio<$N1, $D1>>> for Floor<Ratio<$N2, $D2>> {
+            type Error = TryFromRatioError;
+
+            #[inline]
+            fn try_from(Floor(r): Floor<Ratio<$N1, $D1>>) -> Result<Self, Self::Error> {
+                r.try_into().map(Self)
+            }
+        }
+
+        /// See [`Ratio`]'s [`TryFrom`] implementation
+        impl TryFrom<Ceil<Ratio<$N1, $D1>>> for Ceil<Ratio<$N2, $D2>> {
+            type Error = TryFromRatioError;
+
+            #[inline]
+            fn try_from(Ceil(r): Ceil<Ratio<$N1, $D1>>) -> Result<Self, Self::Error> {
+                r.try_into().map(Self)
+            }
+        }
+    };
+}
+
+/// Invokes `$m!($N1, $D1, $N2, $D2)` for every `$N2`, `$D2` combination
+macro_rules! for_each_target {
+    (@d $m:ident, $N1:ty, $D1:ty, $N2:ty, [$($D2:ty),*]) => {
+        $($m!($N1, $D1, $N2, $D2);)*
+    };
+    ($m:ident, $N1:ty, $D1:ty, [$($N2:ty),*], $D2s:tt) => {
+        $(for_each_target!(@d $m, $N1, $D1, $N2, $D2s);)*
+    };
+}
+
+/// Implements all conversions from `Ratio<$N1, $D1>` given
+/// the types narrower and wider than `$N1` and `$D1`
+macro_rules! impl_conversions {
+    (
+        ($N1:ty, [$($NN:ty),*], [$($WN:ty),*]),
+        ($D1:ty, [$($ND:ty),*], [$($WD:ty),*])
+    ) => {
+        for_each_target!(impl_from, $N1, $D1, [$N1], [$($WD),*]);
+        for_each_target!(impl_from, $N1, $D1, [$($WN),*], [$D1 $(, $WD)*]);
+
+        for_each_target!(impl_try_from, $N1, $D1, [$($NN),*], [$($ND,)* $D1 $(, $WD)*]);
+        for_each_target!(impl_try_from, $N1, $D1, [$N1 $(, $WN)*], [$($ND),*]);
+    };
+}
+
+impl_conversions!(
+    (u8, [], [u16, u32, u64, u128]),
+    (u8, [], [u16, u32, u64, u128])
+);
+impl_conversions!(
+    (u8, [], [u16, u32, u64, u128]),
+    (u16, [u8], [u32, u64, u128])
+);
+impl_conversions!(
+    (u8, [], [u16, u32, u64, u128]),
+    (u32, [u8, u16], [u64, u128])
+);
+impl_conversions!(
+    (u8, [], [u16, u32, u64, u128]),
+    (u64, [u8, u16, u32], [u128])
+);
+impl_conversions!(
+    (u8, [], [u16, u32, u64, u128]),
+    (u128, [u8, u16, u32, u64], [])
+);
+
+impl_conversions!(
+    (u16, [u8], [u32, u64, u128]),
+    (u8, [], [u16, u32, u64, u128])
+);
+impl_conversions!((u16, [u8], [u32, u64, u128]), (u16, [u8], [u32, u64, u128]));
+impl_conversions!((u16, [u8], [u32, u64, u128]), (u32, [u8, u16], [u64, u128]));
+impl_conversions!((u16, [u8], [u32, u64, u128]), (u64, [u8, u16, u32], [u128]));
+impl_conversions!(
+    (u16, [u8], [u32, u64, u128]),
+    (u128, [u8, u16, u32, u64], [])
+);
+
+impl_conversions!(
+    (u32, [u8, u16], [u64, u128]),
+    (u8, [], [u16, u32, u64, u128])
+);
+impl_conversions!((u32, [u8, u16], [u64, u128]), (u16, [u8], [u32, u64, u128]));
+impl_conversions!((u32, [u8, u16], [u64, u128]), (u32, [u8, u16], [u64, u128]));
+impl_conversions!((u32, [u8, u16], [u64, u128]), (u64, [u8, u16, u32], [u128]));
+impl_conversions!(
+    (u32, [u8, u16], [u64, u128]),
+    (u128, [u8, u16, u32, u64], [])
+);
+
+impl_conversions!(
+    (u64, [u8, u16, u32], [u128]),
+    (u8, [], [u16, u32, u64, u128])
+);
+impl_conversions!((u64, [u8, u16, u32], [u128]), (u16, [u8], [u32, u64, u128]));
+impl_conversions!((u64, [u8, u16, u32], [u128]), (u32, [u8, u16], [u64, u128]));
+impl_conversions!((u64, [u8, u16, u32], [u128]), (u64, [u8, u16, u32], [u128]));
+impl_conversions!(
+    (u64, [u8, u16, u32], [u128]),
+    (u128, [u8, u16, u32, u64], [])
+);
+
+impl_conversions!(
+    (u128, [u8, u16, u32, u64], []),
+    (u8, [], [u16, u32, u64, u128])
+);
+impl_conversions!(
+    (u128, [u8, u16, u32, u64], []),
+    (u16, [u8], [u32, u64, u128])
+);
+impl_conversions!(
+    (u128, [u8, u16, u32, u64], []),
+    (u32, [u8, u16], [u64, u128])
+);
+impl_conversions!(
+    (u128, [u8, u16, u32, u64], []),
+    (u64, [u8, u16, u32], [u128])
+);
+impl_conversions!(
+    (u128, [u8, u16, u32, u64], []),
+    (u128, [u8, u16, u32, u64], [])
+);
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Debug;
+
+    use proptest::prelude::*;
+
+    use crate::U128Parts;
+
+    use super::*;
+
+    /// Checks that `a` is converted as-is by a widening conversion,
+    /// else to its lowest form if that fits, else fails
+    fn check_conversion<A, B>(a: A) -> Result<(), TestCaseError>
+    where
+        A: U128Parts + Copy,
+        B: U128Parts + TryFrom<A> + Debug,
+    {
+        let (n, d) = a.to_u128_parts();
+        let fits = |(n, d)| n <= B::N_MAX && d <= B::D_MAX;
+        let (mut x, mut y) = (n, d);
+        while y > 0 {
+            (x, y) = (y, x % y);
+        }
+        let lowest = if n == 0 || d == 0 {
+            (0, 0)
+        } else {
+            (n / x, d / x)
+        };
+        let expected = if B::N_MAX >= A::N_MAX && B::D_MAX >= A::D_MAX {
+            Some((n, d))
+        } else {
+            Some(lowest).filter(|l| fits(*l))
+        };
+        let res = B::try_from(a).ok().map(|b| b.to_u128_parts());
+        prop_assert_eq!(res, expected);
+        Ok(())
+    }
+
+    macro_rules! check_all_targets {
+        ($r:expr, [$($T:ty),*]) => {
+            $(
+                check_conversion::<_, Ratio<$T, u8>>($r)?;
+                check_conversion::<_, Ratio<$T, u16>>($r)?;
+                check_conversion::<_, Ratio<$T, u32>>($r)?;
+                check_conversion::<_, Ratio<$T, u64>>($r)?;
+                check_conversion::<_, Ratio<$T, u128>>($r)?;
+            )*
+        };
+    }
+
+    macro_rules! test_suite {
+        ($N:ty, $D:ty, $test_mod:ident) => {
+            mod $test_mod {
+                use super::*;
+
+                type R = Ratio<$N, $D>;
+
+                proptest! {
+                    #[test]
+                    fn conversions(n: $N, d: $D, small_n: u8, small_d: u8, k: u8) {
+                        // multiples of a small ratio so that the lowest form is often narrower
+                        let scaled = R::new(
+                            <$N>::from(small_n).saturating_mul(k.into()),
+                            <$D>::from(small_d).saturating_mul(k.into()),
+                        );
+                        for r in [R::new(n, d), R::new(small_n.into(), small_d.into()), scaled] {
+                            check_all_targets!(r, [u8, u16, u32, u64, u128]);
+
+                            let wide: Ratio<u128, u128> = r.into();
+                            prop_assert_eq!((wide.n, wide.d), r.to_u128_parts());
+                            // narrowing back reduces to lowest form, unless it is the identity conversion
+                            let lowest = if (R::N_MAX, R::D_MAX) == (u128::MAX, u128::MAX) {
+                                r
+                            } else {
+                                r.lowest_form_self()
+                            };
+                            let narrowed = R::try_from(wide).unwrap();
+                            prop_assert_eq!((narrowed.n, narrowed.d), (lowest.n, lowest.d));
+
+                            let floor: Floor<Ratio<u128, u128>> = Floor(r).into();
+                            prop_assert_eq!(floor.0, wide);
+                            let ceil: Ceil<Ratio<u128, u128>> = Ceil(r).into();
+                            prop_assert_eq!(ceil.0, wide);
+                            prop_assert_eq!(Floor::<R>::try_from(floor).map(|f| (f.0.n, f.0.d)), Ok((lowest.n, lowest.d)));
+                            prop_assert_eq!(Ceil::<R>::try_from(ceil).map(|c| (c.0.n, c.0.d)), Ok((lowest.n, lowest.d)));
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, u8, convert_u8_u8_tests);
+    test_suite!(u8, u16, convert_u8_u16_tests);
+    test_suite!(u8, u32, convert_u8_u32_tests);
+    test_suite!(u8, u64, convert_u8_u64_tests);
+    test_suite!(u8, u128, convert_u8_u128_tests);
+    test_suite!(u16, u8, convert_u16_u8_tests);
+    test_suite!(u16, u16, convert_u16_u16_tests);
+    test_suite!(u16, u32, convert_u16_u32_tests);
+    test_suite!(u16, u64, convert_u16_u64_tests);
+    test_suite!(u16, u128, convert_u16_u128_tests);
+    test_suite!(u32, u8, convert_u32_u8_tests);
+    test_suite!(u32, u16, convert_u32_u16_tests);
+    test_suite!(u32, u32, convert_u32_u32_tests);
+    test_suite!(u32, u64, convert_u32_u64_tests);
+    test_suite!(u32, u128, convert_u32_u128_tests);
+    test_suite!(u64, u8, convert_u64_u8_tests);
+    test_suite!(u64, u16, convert_u64_u16_tests);
+    test_suite!(u64, u32, convert_u64_u32_tests);
+    test_suite!(u64, u64, convert_u64_u64_tests);
+    test_suite!(u64, u128, convert_u64_u128_tests);
+    test_suite!(u128, u8, convert_u128_u8_tests);
+    test_suite!(u128, u16, convert_u128_u16_tests);
+    test_suite!(u128, u32, convert_u128_u32_tests);
+    test_suite!(u128, u64, convert_u128_u64_tests);
+    test_suite!(u128, u128, convert_u128_u128_tests);
+
+    #[test]
+    fn conversion_cases() {
+        let r: Ratio<u64, u64> = Ratio::new(1_000, 400_000);
+        assert_eq!(Ratio::<u8, u16>::try_from(r), Ok(Ratio::new(1, 400)));
+        assert_eq!(Ratio::<u8, u8>::try_from(r), Err(TryFromRatioError));
+        assert_eq!(
+            Floor::<Ratio<u8, u16>>::try_from(Floor(r)).map(|f| f.0),
+            Ok(Ratio::new(1, 400))
+        );
+
+        let zero: Ratio<u64, u64> = Ratio::new(0, 1_000);
+        let zero_narrow = Ratio::<u8, u8>::try_from(zero).unwrap();
+        assert_eq!((zero_narrow.n, zero_narrow.d), (0, 0));
+
+        let wide: Ratio<u64, u128> = Ratio::<u8, u16>::new(1, 400).into();
+        assert_eq!((wide.n, wide.d), (1, 400));
+    }
+}
@@ -0,0 +1,126 @@
+use core::cmp::Ordering;
+
+use crate::Ratio;
+
+macro_rules! impl_widen {
+    ($N:ty, $D:ty) => {
+        impl Ratio<$N, $D> {
+            /// Losslessly casts this ratio's numerator and denominator into [`u64`]s
+            #[inline]
+            pub const fn widen(&self) -> Ratio<u64, u64> {
+                Ratio {
+                    n: self.n as u64,
+                    d: self.d as u64,
+                }
+            }
+
+            /// Fraction comparison with a ratio of a different type.
+            ///
+            /// Any `Ratio` with numerator and denominator types no wider than [`u64`]
+            /// can be compared with this in const contexts by first [`Ratio::widen`]ing it.
+            ///
+            /// Zero denominators are treated as zero, same as [`Self::const_cmp`].
+            ///
+            /// ```rust
+            /// use core::cmp::Ordering;
+            /// use sanctum_u64_ratio::Ratio;
+            ///
+            #[doc = concat!("const CAP: Ratio<", stringify!($N), ", ", stringify!($D), "> = Ratio { n: 1, d: 4 };")]
+            /// const RATE: Ratio<u64, u64> = Ratio { n: 250, d: 1_000 };
+            ///
+            /// const CMP: Ordering = CAP.cmp_with(&RATE.widen());
+            ///
+            /// assert_eq!(CMP, Ordering::Equal);
+            /// ```
+            #[inline]
+            pub const fn cmp_with(&self, other: &Ratio<u64, u64>) -> Ordering {
+                self.widen().const_cmp(other)
+            }
+        }
+    };
+}
+
+impl_widen!(u8, u8);
+impl_widen!(u8, u16);
+impl_widen!(u8, u32);
+impl_widen!(u8, u64);
+
+impl_widen!(u16, u8);
+impl_widen!(u16, u16);
+impl_widen!(u16, u32);
+impl_widen!(u16, u64);
+
+impl_widen!(u32, u8);
+impl_widen!(u32, u16);
+impl_widen!(u32, u32);
+impl_widen!(u32, u64);
+
+impl_widen!(u64, u8);
+impl_widen!(u64, u16);
+impl_widen!(u64, u32);
+impl_widen!(u64, u64);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn reference_cmp((n1, d1): (u128, u128), (n2, d2): (u128, u128)) -> Ordering {
+        match (n1 == 0 || d1 == 0, n2 == 0 || d2 == 0) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => (n1 * d2).cmp(&(n2 * d1)),
+        }
+    }
+
+    macro_rules! test_suite {
+        ($N1:ty, $D1:ty, $N2:ty, $D2:ty, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(n1: $N1, d1: $D1, n2: $N2, d2: $D2, k in 1..=4u8) {
+                    let a: Ratio<$N1, $D1> = Ratio { n: n1, d: d1 };
+                    let b: Ratio<$N2, $D2> = Ratio { n: n2, d: d2 };
+                    let expected = reference_cmp(
+                        (n1.into(), d1.into()),
+                        (n2.into(), d2.into()),
+                    );
+
+                    prop_assert_eq!(a.cmp_with(&b.widen()), expected);
+                    prop_assert_eq!(b.cmp_with(&a.widen()), expected.reverse());
+
+                    // same value in a different type
+                    let k = k as $N1;
+                    if let (Some(n), Some(d)) = (n1.checked_mul(k), d1.checked_mul(k as $D1)) {
+                        if let (Ok(n), Ok(d)) = (<$N2>::try_from(n), <$D2>::try_from(d)) {
+                            let scaled: Ratio<$N2, $D2> = Ratio { n, d };
+                            prop_assert!(a.cmp_with(&scaled.widen()).is_eq());
+                            prop_assert!(scaled.cmp_with(&a.widen()).is_eq());
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, u8, u16, u16, cross_cmp_u8_u8_u16_u16);
+    test_suite!(u8, u16, u64, u64, cross_cmp_u8_u16_u64_u64);
+    test_suite!(u8, u32, u32, u8, cross_cmp_u8_u32_u32_u8);
+    test_suite!(u8, u64, u16, u8, cross_cmp_u8_u64_u16_u8);
+
+    test_suite!(u16, u8, u8, u16, cross_cmp_u16_u8_u8_u16);
+    test_suite!(u16, u16, u64, u64, cross_cmp_u16_u16_u64_u64);
+    test_suite!(u16, u32, u32, u16, cross_cmp_u16_u32_u32_u16);
+    test_suite!(u16, u64, u8, u8, cross_cmp_u16_u64_u8_u8);
+
+    test_suite!(u32, u8, u64, u16, cross_cmp_u32_u8_u64_u16);
+    test_suite!(u32, u16, u8, u64, cross_cmp_u32_u16_u8_u64);
+    test_suite!(u32, u32, u16, u16, cross_cmp_u32_u32_u16_u16);
+    test_suite!(u32, u64, u64, u32, cross_cmp_u32_u64_u64_u32);
+
+    test_suite!(u64, u8, u8, u8, cross_cmp_u64_u8_u8_u8);
+    test_suite!(u64, u16, u16, u64, cross_cmp_u64_u16_u16_u64);
+    test_suite!(u64, u32, u32, u32, cross_cmp_u64_u32_u32_u32);
+    test_suite!(u64, u64, u8, u32, cross_cmp_u64_u64_u8_u32);
+}
@@ -0,0 +1,500 @@
+use core::fmt::{Display, Formatter, Result, Write};
+
+use crate::{Ceil, Floor, HalfEven, HalfUp, Ratio, U256};
+
+/// The maximum number of decimal places output by [`DecimalFmt`]
+/// if no precision is specified and the ratio cannot be
+/// represented exactly with fewer decimal places.
+pub const DEFAULT_MAX_PRECISION: usize = 18;
+
+/// The unit that [`DecimalFmt`] expresses a ratio's value in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DecimalUnit {
+    /// e.g. `0.0025`
+    One,
+
+    /// e.g. `0.25%`
+    Percent,
+
+    /// Basis points e.g. `25bps`
+    Bps,
+}
+
+impl DecimalUnit {
+    /// Number of decimal places the decimal point is shifted right by
+    #[inline]
+    const fn shift(&self) -> usize {
+        match self {
+            Self::One => 0,
+            Self::Percent => 2,
+            Self::Bps => 4,
+        }
+    }
+
+    #[inline]
+    const fn suffix(&self) -> &'static str {
+        match self {
+            Self::One => "",
+            Self::Percent => "%",
+            Self::Bps => "bps",
+        }
+    }
+}
+
+/// Formats a rounding-mode-wrapped [`Ratio`] e.g. `Floor<Ratio<N, D>>`
+/// as a decimal number in [`DecimalUnit`]s,
+/// rounding in the direction of the rounding mode.
+///
+/// The formatter's precision e.g. `{:.2}` sets the number of decimal places.
+/// If not specified, the minimum number of decimal places required to
+/// represent the ratio exactly is used, up to [`DEFAULT_MAX_PRECISION`].
+///
+/// The output can be parsed back into a [`Ratio`] using its [`core::str::FromStr`] implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DecimalFmt<R> {
+    pub ratio: R,
+    pub unit: DecimalUnit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Rounding {
+    Floor,
+    Ceil,
+    HalfUp,
+    HalfEven,
+}
+
+macro_rules! impl_decimal_fmt_ctors {
+    ($R:ident) => {
+        impl<R> $R<R> {
+            /// Formats this ratio as a decimal number e.g. `0.0025`.
+            ///
+            /// See [`DecimalFmt`]
+            #[inline]
+            pub const fn decimal(self) -> DecimalFmt<Self> {
+                DecimalFmt {
+                    ratio: self,
+                    unit: DecimalUnit::One,
+                }
+            }
+
+            /// Formats this ratio as a percentage e.g. `0.25%`.
+            ///
+            /// See [`DecimalFmt`]
+            #[inline]
+            pub const fn percent(self) -> DecimalFmt<Self> {
+                DecimalFmt {
+                    ratio: self,
+                    unit: DecimalUnit::Percent,
+                }
+            }
+
+            /// Formats this ratio in basis points e.g. `25bps`.
+            ///
+            /// See [`DecimalFmt`]
+            #[inline]
+            pub const fn bps(self) -> DecimalFmt<Self> {
+                DecimalFmt {
+                    ratio: self,
+                    unit: DecimalUnit::Bps,
+                }
+            }
+        }
+    };
+}
+
+impl_decimal_fmt_ctors!(Floor);
+impl_decimal_fmt_ctors!(Ceil);
+impl_decimal_fmt_ctors!(HalfUp);
+impl_decimal_fmt_ctors!(HalfEven);
+
+/// Long division step: `(10r / d, 10r % d)`. Requires `r < d`
+#[inline]
+fn next_digit(r: u128, d: u128) -> (u8, u128) {
+    match r.checked_mul(10) {
+        Some(r10) => ((r10 / d) as u8, r10 % d),
+        None => {
+            let (q, r) = U256::widening_mul(r, 10).div_rem_u128(d);
+            // q < 10 since r < d
+            (q.lo() as u8, r)
+        }
+    }
+}
+
+/// Writes a stream of decimal digits, holding back the last non-9 digit
+/// and the run of 9s after it so that the final digit can be rounded up
+/// with carry propagation without buffering all the digits.
+struct DigitWriter<'a, 'b> {
+    f: &'a mut Formatter<'b>,
+
+    /// Number of digits before the decimal point, including leading zeros
+    int_len: usize,
+
+    /// Number of digits emitted so far, including suppressed leading zeros
+    emitted: usize,
+
+    /// Whether a digit has been written to `f`
+    started: bool,
+
+    /// `None` if no digit has been pushed yet
+    pending: Option<u8>,
+
+    /// Number of 9s following `pending`
+    nines: usize,
+}
+
+impl DigitWriter<'_, '_> {
+    fn emit(&mut self, digit: u8, times: usize) -> Result {
+        for _ in 0..times {
+            // the last digit before the decimal point is always written
+            let is_leading_zero = digit == 0 && !self.started && self.emitted + 1 < self.int_len;
+            if self.emitted == self.int_len {
+                self.f.write_char('.')?;
+            }
+            if !is_leading_zero {
+                self.f.write_char((b'0' + digit) as char)?;
+                self.started = true;
+            }
+            self.emitted += 1;
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, digit: u8) -> Result {
+        match self.pending {
+            None => self.pending = Some(digit),
+            Some(_) if digit == 9 => self.nines += 1,
+            Some(p) => {
+                self.emit(p, 1)?;
+                self.emit(9, self.nines)?;
+                self.pending = Some(digit);
+                self.nines = 0;
+            }
+        }
+        Ok(())
+    }
+
+    fn last_digit(&self) -> u8 {
+        if self.nines > 0 {
+            9
+        } else {
+            self.pending.unwrap_or(0)
+        }
+    }
+
+    /// `pending` must not be 9 if `round_up`, which is guaranteed by
+    /// pushing a leading 0 first
+    fn finish(mut self, round_up: bool) -> Result {
+        let p = self.pending.unwrap_or(0);
+        if round_up {
+            self.emit(p + 1, 1)?;
+            self.emit(0, self.nines)
+        } else {
+            self.emit(p, 1)?;
+            self.emit(9, self.nines)
+        }
+    }
+}
+
+/// Formats `n/d` in `unit`s.
+///
+/// A leading 0 is pushed so that rounding up never carries past the first digit.
+fn fmt_decimal(
+    n: u128,
+    d: u128,
+    unit: DecimalUnit,
+    rounding: Rounding,
+    f: &mut Formatter<'_>,
+) -> Result {
+    let (n, d) = if n == 0 || d == 0 { (0, 1) } else { (n, d) };
+    let shift = unit.shift();
+    // division-safety: d != 0
+    let q = n / d;
+    let mut r = n % d;
+
+    let precision = match f.precision() {
+        Some(p) => p,
+        None => {
+            // minimum number of decimal places to be exact, if any
+            let mut r = r;
+            let mut places = 0;
+            while r != 0 && places < DEFAULT_MAX_PRECISION + shift {
+                r = next_digit(r, d).1;
+                places += 1;
+            }
+            places.saturating_sub(shift)
+        }
+    };
+
+    let mut q_digits = [0u8; 39];
+    let mut q_len = 0;
+    let mut rem = q;
+    loop {
+        q_digits[q_len] = (rem % 10) as u8;
+        q_len += 1;
+        rem /= 10;
+        if rem == 0 {
+            break;
+        }
+    }
+
+    let mut w = DigitWriter {
+        f: &mut *f,
+        int_len: 1 + q_len + shift,
+        emitted: 0,
+        started: false,
+        pending: None,
+        nines: 0,
+    };
+    w.push(0)?;
+    for digit in q_digits[..q_len].iter().rev() {
+        w.push(*digit)?;
+    }
+    // saturating since the precision is user-supplied and can be up to usize::MAX
+    for _ in 0..precision.saturating_add(shift) {
+        let (digit, new_r) = next_digit(r, d);
+        w.push(digit)?;
+        r = new_r;
+    }
+
+    // r < d so d - r does not underflow, and comparing r with d - r
+    // is comparing 2r with d without overflowing
+    let round_up = match rounding {
+        Rounding::Floor => false,
+        Rounding::Ceil => r != 0,
+        Rounding::HalfUp => r != 0 && r >= d - r,
+        Rounding::HalfEven => r != 0 && (r > d - r || (r == d - r && w.last_digit() % 2 == 1)),
+    };
+    w.finish(round_up)?;
+    f.write_str(unit.suffix())
+}
+
+macro_rules! impl_decimal_fmt {
+    ($N:ty, $D:ty) => {
+        impl_decimal_fmt!($N, $D, Floor);
+        impl_decimal_fmt!($N, $D, Ceil);
+        impl_decimal_fmt!($N, $D, HalfUp);
+        impl_decimal_fmt!($N, $D, HalfEven);
+    };
+    ($N:ty, $D:ty, $R:ident) => {
+        impl Display for DecimalFmt<$R<Ratio<$N, $D>>> {
+            #[inline]
+            fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+                let Ratio { n, d } = self.ratio.0;
+                fmt_decimal(n as u128, d as u128, self.unit, Rounding::$R, f)
+            }
+        }
+    };
+}
+
+impl_decimal_fmt!(u8, u8);
+impl_decimal_fmt!(u8, u16);
+impl_decimal_fmt!(u8, u32);
+impl_decimal_fmt!(u8, u64);
+impl_decimal_fmt!(u8, u128);
+
+impl_decimal_fmt!(u16, u8);
+impl_decimal_fmt!(u16, u16);
+impl_decimal_fmt!(u16, u32);
+impl_decimal_fmt!(u16, u64);
+impl_decimal_fmt!(u16, u128);
+
+impl_decimal_fmt!(u32, u8);
+impl_decimal_fmt!(u32, u16);
+impl_decimal_fmt!(u32, u32);
+impl_decimal_fmt!(u32, u64);
+impl_decimal_fmt!(u32, u128);
+
+impl_decimal_fmt!(u64, u8);
+impl_decimal_fmt!(u64, u16);
+impl_decimal_fmt!(u64, u32);
+impl_decimal_fmt!(u64, u64);
+impl_decimal_fmt!(u64, u128);
+
+impl_decimal_fmt!(u128, u8);
+impl_decimal_fmt!(u128, u16);
+impl_decimal_fmt!(u128, u32);
+impl_decimal_fmt!(u128, u64);
+impl_decimal_fmt!(u128, u128);
+
+#[cfg(test)]
+mod tests {
+    use core::cmp::Ordering;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    type Wide = Ratio<u128, u128>;
+
+    const UNITS: [(DecimalUnit, u128); 3] = [
+        (DecimalUnit::One, 1),
+        (DecimalUnit::Percent, 100),
+        (DecimalUnit::Bps, 10_000),
+    ];
+
+    macro_rules! test_suite {
+        ($N:ty, $D:ty, $test_mod:ident) => {
+            mod $test_mod {
+                use super::*;
+
+                type R = Ratio<$N, $D>;
+
+                proptest! {
+                    #[test]
+                    fn rounding_brackets_exact(n: $N, d: $D, precision in 0usize..=8) {
+                        let r = R::new(n, d);
+                        let exact = Wide::new(n.into(), d.into());
+
+                        for (unit, scale) in UNITS {
+                            let [floor, ceil, half_up, half_even] = [
+                                format!("{:.*}", precision, DecimalFmt { ratio: Floor(r), unit }),
+                                format!("{:.*}", precision, DecimalFmt { ratio: Ceil(r), unit }),
+                                format!("{:.*}", precision, DecimalFmt { ratio: HalfUp(r), unit }),
+                                format!("{:.*}", precision, DecimalFmt { ratio: HalfEven(r), unit }),
+                            ]
+                            .map(|s| s.parse::<Wide>().unwrap());
+
+                            prop_assert_ne!(floor.const_cmp(&exact), Ordering::Greater);
+                            prop_assert_ne!(ceil.const_cmp(&exact), Ordering::Less);
+                            for nearest in [half_up, half_even] {
+                                prop_assert_ne!(nearest.const_cmp(&floor), Ordering::Less);
+                                prop_assert_ne!(nearest.const_cmp(&ceil), Ordering::Greater);
+                            }
+
+                            // ceil - floor is either 0 or exactly 1 unit in the last place
+                            let ulp = Wide::new(1, 10u128.pow(precision as u32) * scale);
+                            let diff = ceil.checked_sub(&floor).unwrap();
+                            prop_assert!(diff.is_zero() || diff.const_cmp(&ulp) == Ordering::Equal);
+                            if floor.const_cmp(&exact) == Ordering::Equal {
+                                prop_assert!(diff.is_zero());
+                            }
+                        }
+                    }
+                }
+
+                proptest! {
+                    #[test]
+                    fn default_precision_exact_if_terminating(
+                        n: $N,
+                        pow2 in 0u32..=12,
+                        pow5 in 0u32..=8,
+                    ) {
+                        let mut d = 2u64.pow(pow2) * 5u64.pow(pow5);
+                        while d > <$D>::MAX as u64 {
+                            d /= if d % 2 == 0 { 2 } else { 5 };
+                        }
+                        let r = R::new(n, d as $D);
+                        let exact = Wide::new(n.into(), d.into());
+
+                        for (unit, _) in UNITS {
+                            for s in [
+                                DecimalFmt { ratio: Floor(r), unit }.to_string(),
+                                DecimalFmt { ratio: Ceil(r), unit }.to_string(),
+                            ] {
+                                let parsed: Wide = s.parse().unwrap();
+                                prop_assert_eq!(parsed.const_cmp(&exact), Ordering::Equal, "{}", s);
+                                prop_assert!(!s.contains('.') || !s.trim_end_matches(unit.suffix()).ends_with('0'));
+                            }
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, u8, decimal_fmt_u8_u8_tests);
+    test_suite!(u8, u16, decimal_fmt_u8_u16_tests);
+    test_suite!(u8, u32, decimal_fmt_u8_u32_tests);
+    test_suite!(u8, u64, decimal_fmt_u8_u64_tests);
+    test_suite!(u16, u8, decimal_fmt_u16_u8_tests);
+    test_suite!(u16, u16, decimal_fmt_u16_u16_tests);
+    test_suite!(u16, u32, decimal_fmt_u16_u32_tests);
+    test_suite!(u16, u64, decimal_fmt_u16_u64_tests);
+    test_suite!(u32, u8, decimal_fmt_u32_u8_tests);
+    test_suite!(u32, u16, decimal_fmt_u32_u16_tests);
+    test_suite!(u32, u32, decimal_fmt_u32_u32_tests);
+    test_suite!(u32, u64, decimal_fmt_u32_u64_tests);
+    test_suite!(u64, u8, decimal_fmt_u64_u8_tests);
+    test_suite!(u64, u16, decimal_fmt_u64_u16_tests);
+    test_suite!(u64, u32, decimal_fmt_u64_u32_tests);
+    test_suite!(u64, u64, decimal_fmt_u64_u64_tests);
+
+    #[test]
+    fn format_cases() {
+        type R = Ratio<u64, u64>;
+
+        for (s, expected) in [
+            (Floor(R::new(1, 400)).decimal().to_string(), "0.0025"),
+            (Floor(R::new(1, 400)).percent().to_string(), "0.25%"),
+            (Floor(R::new(1, 400)).bps().to_string(), "25bps"),
+            (Floor(R::new(0, 400)).decimal().to_string(), "0"),
+            (Floor(R::new(5, 0)).percent().to_string(), "0%"),
+            (Floor(R::new(0, 0)).percent().to_string(), "0%"),
+            (Floor(R::new(1, 1)).percent().to_string(), "100%"),
+            (Floor(R::new(123, 10)).decimal().to_string(), "12.3"),
+            (format!("{:.2}", Floor(R::new(1, 3)).decimal()), "0.33"),
+            (format!("{:.2}", Ceil(R::new(1, 3)).decimal()), "0.34"),
+            (format!("{:.2}", HalfUp(R::new(2, 3)).decimal()), "0.67"),
+            (format!("{:.2}", Ceil(R::new(999, 1000)).decimal()), "1.00"),
+            (
+                format!("{:.2}", Ceil(R::new(9995, 1000)).decimal()),
+                "10.00",
+            ),
+            (
+                format!("{:.2}", Floor(R::new(9995, 1000)).decimal()),
+                "9.99",
+            ),
+            (
+                format!("{:.0}", Ceil(R::new(9995, 1000)).percent()),
+                "1000%",
+            ),
+            (format!("{:.0}", Ceil(R::new(1, 3)).decimal()), "1"),
+            (format!("{:.0}", Floor(R::new(1, 3)).bps()), "3333bps"),
+            (format!("{:.2}", HalfUp(R::new(1, 8)).decimal()), "0.13"),
+            (format!("{:.2}", HalfEven(R::new(1, 8)).decimal()), "0.12"),
+            (format!("{:.2}", HalfEven(R::new(3, 8)).decimal()), "0.38"),
+            (format!("{:.1}", HalfEven(R::new(1, 400)).percent()), "0.2%"),
+            (format!("{:.1}", HalfEven(R::new(3, 400)).percent()), "0.8%"),
+            (format!("{:.3}", Floor(R::new(1, 4)).decimal()), "0.250"),
+            (
+                Floor(R::new(1, 3)).decimal().to_string(),
+                "0.333333333333333333",
+            ),
+            (
+                Ceil(R::new(1, 3)).decimal().to_string(),
+                "0.333333333333333334",
+            ),
+            (
+                Ceil(R::new(2, 3)).percent().to_string(),
+                "66.666666666666666667%",
+            ),
+        ] {
+            assert_eq!(s, expected);
+        }
+    }
+
+    #[test]
+    fn format_u128_extremes() {
+        type R = Ratio<u128, u128>;
+
+        let max = u128::MAX.to_string();
+        assert_eq!(Floor(R::new(u128::MAX, 1)).decimal().to_string(), max);
+        assert_eq!(
+            Floor(R::new(u128::MAX, 1)).bps().to_string(),
+            format!("{max}0000bps")
+        );
+        assert_eq!(
+            format!("{:.2}", Ceil(R::new(u128::MAX, u128::MAX - 1)).decimal()),
+            "1.01"
+        );
+        assert_eq!(
+            format!("{:.40}", Floor(R::new(u128::MAX - 1, u128::MAX)).decimal()),
+            format!("0.{}", "9".repeat(38) + "70")
+        );
+        assert_eq!(
+            format!("{:.1}", Ceil(R::new(u128::MAX - 1, u128::MAX)).decimal()),
+            "1.0"
+        );
+    }
+}
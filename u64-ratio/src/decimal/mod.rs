@@ -0,0 +1,8 @@
+//! Exact conversions between [`crate::Ratio`]s and their
+//! decimal, percent and basis points string representations
+
+mod format;
+mod parse;
+
+pub use format::*;
+pub use parse::*;
@@ -0,0 +1,356 @@
+use core::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use crate::{gcd_u128, Ratio};
+
+/// Error returned by [`Ratio`]'s [`FromStr`] implementation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseRatioError {
+    /// The string is not in any of the supported formats
+    Invalid,
+
+    /// The numerator of the ratio in its lowest form does not fit into `N`
+    Overflow,
+
+    /// The denominator of the ratio in its lowest form does not fit into `D`,
+    /// so the ratio cannot be represented exactly
+    PrecisionLoss,
+}
+
+impl Display for ParseRatioError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::Invalid => "invalid ratio string",
+            Self::Overflow => "ratio numerator too large",
+            Self::PrecisionLoss => "ratio cannot be represented exactly",
+        })
+    }
+}
+
+/// Parses a nonempty string of only ascii decimal digits
+#[inline]
+fn parse_digits(s: &str) -> Result<u128, ParseRatioError> {
+    if s.is_empty() {
+        return Err(ParseRatioError::Invalid);
+    }
+    s.bytes().try_fold(0u128, |acc, b| {
+        if !b.is_ascii_digit() {
+            return Err(ParseRatioError::Invalid);
+        }
+        acc.checked_mul(10)
+            .and_then(|acc| acc.checked_add((b - b'0') as u128))
+            .ok_or(ParseRatioError::Overflow)
+    })
+}
+
+/// Parses a decimal number with an optional decimal point
+/// e.g. `1`, `0.25`, `.25`, `1.`
+/// into `(n, d)`, where `d` is `scale * 10^(number of decimal places)`
+#[inline]
+fn parse_decimal(s: &str, scale: u128) -> Result<(u128, u128), ParseRatioError> {
+    let (int, frac) = s.split_once('.').unwrap_or((s, ""));
+    if int.is_empty() && frac.is_empty() {
+        return Err(ParseRatioError::Invalid);
+    }
+    let mut n = if int.is_empty() {
+        0
+    } else {
+        parse_digits(int)?
+    };
+    let mut d = scale;
+    // trailing zeros do not affect the value,
+    // strip them so that they do not cause spurious overflows
+    let frac_digits = frac.trim_end_matches('0');
+    if !frac.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ParseRatioError::Invalid);
+    }
+    for b in frac_digits.bytes() {
+        // overflow here is due to too many significant decimal places
+        n = n
+            .checked_mul(10)
+            .and_then(|n| n.checked_add((b - b'0') as u128))
+            .ok_or(ParseRatioError::PrecisionLoss)?;
+        d = d.checked_mul(10).ok_or(ParseRatioError::PrecisionLoss)?;
+    }
+    Ok((n, d))
+}
+
+/// Parses `s` into `(n, d)` and whether the ratio should be reduced to its lowest form.
+///
+/// Only ratios in the `n/d` format are not reduced.
+#[inline]
+fn parse_parts(s: &str) -> Result<(u128, u128, bool), ParseRatioError> {
+    let s = s.trim();
+    if let Some(s) = s.strip_suffix('%') {
+        let (n, d) = parse_decimal(s.trim_end(), 100)?;
+        Ok((n, d, true))
+    } else if let Some(s) = s.strip_suffix("bps") {
+        let (n, d) = parse_decimal(s.trim_end(), 10_000)?;
+        Ok((n, d, true))
+    } else if let Some((n, d)) = s.split_once('/') {
+        Ok((parse_digits(n.trim())?, parse_digits(d.trim())?, false))
+    } else {
+        let (n, d) = parse_decimal(s, 1)?;
+        Ok((n, d, true))
+    }
+}
+
+/// Fits `n/d` into `Ratio<N, D>`, reducing it to its lowest form first if
+/// `reduce` is set or it does not fit as-is.
+///
+/// Zero ratios that do not fit as-is are output as `0/1`
+#[inline]
+fn fit<N: TryFrom<u128>, D: TryFrom<u128>>(
+    n: u128,
+    d: u128,
+    reduce: bool,
+) -> Result<Ratio<N, D>, ParseRatioError> {
+    if !reduce {
+        if let (Ok(n), Ok(d)) = (N::try_from(n), D::try_from(d)) {
+            return Ok(Ratio { n, d });
+        }
+    }
+    let (n, d) = if n == 0 || d == 0 {
+        (0, 1)
+    } else {
+        // division-safety: gcd is nonzero since n and d are nonzero
+        let g = gcd_u128(n, d);
+        (n / g, d / g)
+    };
+    let n = N::try_from(n).map_err(|_| ParseRatioError::Overflow)?;
+    let d = D::try_from(d).map_err(|_| ParseRatioError::PrecisionLoss)?;
+    Ok(Ratio { n, d })
+}
+
+macro_rules! impl_from_str {
+    ($N:ty, $D:ty) => {
+        /// Exactly parses a ratio in any of the following formats:
+        ///
+        /// - `n/d` e.g. `1/400`. The ratio is kept as-is if it fits, else it is reduced to its lowest form.
+        /// - decimal e.g. `0.0025`
+        /// - percent e.g. `0.25%`
+        /// - basis points e.g. `25bps` or `25 bps`
+        ///
+        /// Decimal, percent and basis points ratios are reduced to their lowest forms.
+        ///
+        /// ```rust
+        /// use sanctum_u64_ratio::Ratio;
+        ///
+        #[doc = concat!("type R = Ratio<", stringify!($N), ", ", stringify!($D), ">;")]
+        ///
+        /// for s in ["1/200", "0.005", "0.5%", "50bps"] {
+        ///     assert_eq!(s.parse::<R>().unwrap(), R::new(1, 200));
+        /// }
+        /// ```
+        impl FromStr for Ratio<$N, $D> {
+            type Err = ParseRatioError;
+
+            #[inline]
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let (n, d, reduce) = parse_parts(s)?;
+                fit(n, d, reduce)
+            }
+        }
+    };
+}
+
+impl_from_str!(u8, u8);
+impl_from_str!(u8, u16);
+impl_from_str!(u8, u32);
+impl_from_str!(u8, u64);
+impl_from_str!(u8, u128);
+
+impl_from_str!(u16, u8);
+impl_from_str!(u16, u16);
+impl_from_str!(u16, u32);
+impl_from_str!(u16, u64);
+impl_from_str!(u16, u128);
+
+impl_from_str!(u32, u8);
+impl_from_str!(u32, u16);
+impl_from_str!(u32, u32);
+impl_from_str!(u32, u64);
+impl_from_str!(u32, u128);
+
+impl_from_str!(u64, u8);
+impl_from_str!(u64, u16);
+impl_from_str!(u64, u32);
+impl_from_str!(u64, u64);
+impl_from_str!(u64, u128);
+
+impl_from_str!(u128, u8);
+impl_from_str!(u128, u16);
+impl_from_str!(u128, u32);
+impl_from_str!(u128, u64);
+impl_from_str!(u128, u128);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// `n * 10^(shift - places)` as a decimal string with `places` zero-padded decimal places
+    fn with_point(n: u8, places: usize, shift: usize) -> String {
+        let digits = format!("{n:0>width$}", width = places + 1);
+        if shift >= places {
+            format!("{digits}{}", "0".repeat(shift - places))
+        } else {
+            let (int, frac) = digits.split_at(digits.len() - (places - shift));
+            format!("{int}.{frac}")
+        }
+    }
+
+    macro_rules! test_suite {
+        ($N:ty, $D:ty, $test_mod:ident) => {
+            mod $test_mod {
+                use super::*;
+
+                type R = Ratio<$N, $D>;
+
+                proptest! {
+                    #[test]
+                    fn display_round_trip(n: $N, d: $D) {
+                        let r = R::new(n, d);
+                        let parsed: R = r.to_string().parse().unwrap();
+                        prop_assert_eq!((parsed.n, parsed.d), (n, d));
+                    }
+                }
+
+                proptest! {
+                    #[test]
+                    fn units_same_value(n: u8, places in 0usize..=4) {
+                        // n * 10^-places
+                        let dec = with_point(n, places, 0);
+                        let pct = format!("{}%", with_point(n, places, 2));
+                        let bps = format!("{} bps", with_point(n, places, 4));
+                        let frac = format!("{n}/{}", 10u32.pow(places as u32));
+                        let [dec, pct, bps, frac] = [dec, pct, bps, frac].map(|s| s.parse::<R>());
+                        prop_assert_eq!(&dec, &pct);
+                        prop_assert_eq!(&dec, &bps);
+                        if let (Ok(dec), Ok(frac)) = (dec, frac) {
+                            prop_assert_eq!(dec.const_cmp(&frac), core::cmp::Ordering::Equal);
+                            let l = frac.lowest_form();
+                            if !frac.is_zero() {
+                                prop_assert_eq!((dec.n.into(), dec.d.into()), (l.n, l.d));
+                            }
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, u8, parse_u8_u8_tests);
+    test_suite!(u8, u16, parse_u8_u16_tests);
+    test_suite!(u8, u32, parse_u8_u32_tests);
+    test_suite!(u8, u64, parse_u8_u64_tests);
+    test_suite!(u8, u128, parse_u8_u128_tests);
+    test_suite!(u16, u8, parse_u16_u8_tests);
+    test_suite!(u16, u16, parse_u16_u16_tests);
+    test_suite!(u16, u32, parse_u16_u32_tests);
+    test_suite!(u16, u64, parse_u16_u64_tests);
+    test_suite!(u16, u128, parse_u16_u128_tests);
+    test_suite!(u32, u8, parse_u32_u8_tests);
+    test_suite!(u32, u16, parse_u32_u16_tests);
+    test_suite!(u32, u32, parse_u32_u32_tests);
+    test_suite!(u32, u64, parse_u32_u64_tests);
+    test_suite!(u32, u128, parse_u32_u128_tests);
+    test_suite!(u64, u8, parse_u64_u8_tests);
+    test_suite!(u64, u16, parse_u64_u16_tests);
+    test_suite!(u64, u32, parse_u64_u32_tests);
+    test_suite!(u64, u64, parse_u64_u64_tests);
+    test_suite!(u64, u128, parse_u64_u128_tests);
+    test_suite!(u128, u8, parse_u128_u8_tests);
+    test_suite!(u128, u16, parse_u128_u16_tests);
+    test_suite!(u128, u32, parse_u128_u32_tests);
+    test_suite!(u128, u64, parse_u128_u64_tests);
+    test_suite!(u128, u128, parse_u128_u128_tests);
+
+    #[test]
+    fn parse_cases() {
+        type R = Ratio<u8, u16>;
+
+        for (s, expected) in [
+            ("1/400", R::new(1, 400)),
+            ("2/800", R::new(2, 800)),
+            (" 1 / 400 ", R::new(1, 400)),
+            ("0.0025", R::new(1, 400)),
+            (".0025", R::new(1, 400)),
+            (
+                "0.00250000000000000000000000000000000000000000",
+                R::new(1, 400),
+            ),
+            ("0.25%", R::new(1, 400)),
+            ("0.25 %", R::new(1, 400)),
+            ("25bps", R::new(1, 400)),
+            ("25 bps", R::new(1, 400)),
+            ("100%", R::new(1, 1)),
+            ("1.", R::new(1, 1)),
+            ("255", R::new(255, 1)),
+            ("0", R::new(0, 1)),
+            ("0/0", R::new(0, 0)),
+            ("5/0", R::new(5, 0)),
+            ("0/100000", R::new(0, 1)),
+            // reduced to fit
+            ("256/512", R::new(1, 2)),
+        ] {
+            assert_eq!(s.parse::<R>(), Ok(expected), "{s}");
+        }
+
+        for (s, err) in [
+            ("", ParseRatioError::Invalid),
+            (".", ParseRatioError::Invalid),
+            ("%", ParseRatioError::Invalid),
+            ("bps", ParseRatioError::Invalid),
+            ("-1", ParseRatioError::Invalid),
+            ("+1", ParseRatioError::Invalid),
+            ("1/", ParseRatioError::Invalid),
+            ("/1", ParseRatioError::Invalid),
+            ("1/2/3", ParseRatioError::Invalid),
+            ("0.1.2", ParseRatioError::Invalid),
+            ("1e-3", ParseRatioError::Invalid),
+            ("1/4%", ParseRatioError::Invalid),
+            ("1 0", ParseRatioError::Invalid),
+            ("256", ParseRatioError::Overflow),
+            ("256/1", ParseRatioError::Overflow),
+            ("1/65537", ParseRatioError::PrecisionLoss),
+            ("0.000001", ParseRatioError::PrecisionLoss),
+            ("0.001bps", ParseRatioError::PrecisionLoss),
+            (
+                "340282366920938463463374607431768211456",
+                ParseRatioError::Overflow,
+            ),
+            (
+                "0.340282366920938463463374607431768211456",
+                ParseRatioError::PrecisionLoss,
+            ),
+        ] {
+            assert_eq!(s.parse::<R>(), Err(err), "{s}");
+        }
+    }
+
+    #[test]
+    fn parse_u128_extremes() {
+        type R = Ratio<u128, u128>;
+
+        let max = u128::MAX.to_string();
+        assert_eq!(max.parse::<R>(), Ok(R::new(u128::MAX, 1)));
+        assert_eq!(format!("1/{max}").parse::<R>(), Ok(R::new(1, u128::MAX)));
+        assert_eq!(
+            format!("{max}%").parse::<R>(),
+            Ok(R::new(u128::MAX / 5, 20))
+        );
+        assert_eq!(
+            format!("{max}.1%").parse::<R>(),
+            Err(ParseRatioError::PrecisionLoss)
+        );
+        assert_eq!(
+            "0.00000000000000000000000000000000000001".parse::<R>(),
+            Ok(R::new(1, 10u128.pow(38)))
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use core::ops::RangeInclusive;
+
+/// Common interface over the different ways a [`crate::Ratio`]
+/// can be applied to a [`u64`] e.g. [`crate::Floor`], [`crate::Ceil`].
+///
+/// Allows code to be generic over the rounding mode
+/// and the `Ratio<N, D>` type combination used.
+///
+/// The inherent methods of the same name should be preferred
+/// in non-generic contexts since they are `const`.
+pub trait RatioApply {
+    /// # Returns
+    ///
+    /// The ratio applied to `amount`, rounded according to the implementor.
+    ///
+    /// `None` if the result > `u64::MAX`.
+    ///
+    /// See [`crate::Floor::apply`] for more details
+    fn apply(&self, amount: u64) -> Option<u64>;
+
+    /// [`Self::reverse`], but returns an invalid/empty range
+    /// (e.g. `3..=2`) if the output is unattainable.
+    ///
+    /// See [`crate::Floor::reverse_est`] for more details
+    fn reverse_est(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>>;
+
+    /// # Returns
+    ///
+    /// `min..=max` the range of possible values that were fed into
+    /// [`Self::apply`] to get output `amt_after_apply`.
+    ///
+    /// See [`crate::Floor::reverse`] for more details
+    #[inline]
+    fn reverse(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+        self.reverse_est(amt_after_apply)
+            .filter(|r| *r.start() <= *r.end())
+    }
+}
+
+impl<T: RatioApply + ?Sized> RatioApply for &T {
+    #[inline]
+    fn apply(&self, amount: u64) -> Option<u64> {
+        (**self).apply(amount)
+    }
+
+    #[inline]
+    fn reverse_est(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+        (**self).reverse_est(amt_after_apply)
+    }
+
+    #[inline]
+    fn reverse(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+        (**self).reverse(amt_after_apply)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{Ceil, Floor, Ratio};
+
+    use super::*;
+
+    /// Generic round-trip check that should hold for any [`RatioApply`]
+    fn round_trip<R: RatioApply>(r: R, amt: u64) -> Result<(), TestCaseError> {
+        let app = match r.apply(amt) {
+            None => return Ok(()),
+            Some(a) => a,
+        };
+        let rt = r.reverse(app).unwrap();
+        prop_assert!(rt.contains(&amt), "{amt} {rt:?}");
+        for bound in [*rt.start(), *rt.end()] {
+            prop_assert_eq!(r.apply(bound), Some(app));
+        }
+        Ok(())
+    }
+
+    macro_rules! test_suite {
+        ($N:ty, $D:ty, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(n: $N, d: $D, amt: u64) {
+                    let ratio = Ratio::<$N, $D>::new(n, d);
+                    for (trait_app, inherent_app) in [
+                        (RatioApply::apply(&Floor(ratio), amt), Floor(ratio).apply(amt)),
+                        (RatioApply::apply(&Ceil(ratio), amt), Ceil(ratio).apply(amt)),
+                    ] {
+                        prop_assert_eq!(trait_app, inherent_app);
+                    }
+                    round_trip(Floor(ratio), amt)?;
+                    round_trip(&Ceil(ratio), amt)?;
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, u8, ratio_apply_u8_u8);
+    test_suite!(u8, u16, ratio_apply_u8_u16);
+    test_suite!(u8, u32, ratio_apply_u8_u32);
+    test_suite!(u8, u64, ratio_apply_u8_u64);
+
+    test_suite!(u16, u8, ratio_apply_u16_u8);
+    test_suite!(u16, u16, ratio_apply_u16_u16);
+    test_suite!(u16, u32, ratio_apply_u16_u32);
+    test_suite!(u16, u64, ratio_apply_u16_u64);
+
+    test_suite!(u32, u8, ratio_apply_u32_u8);
+    test_suite!(u32, u16, ratio_apply_u32_u16);
+    test_suite!(u32, u32, ratio_apply_u32_u32);
+    test_suite!(u32, u64, ratio_apply_u32_u64);
+
+    test_suite!(u64, u8, ratio_apply_u64_u8);
+    test_suite!(u64, u16, ratio_apply_u64_u16);
+    test_suite!(u64, u32, ratio_apply_u64_u32);
+    test_suite!(u64, u64, ratio_apply_u64_u64);
+}
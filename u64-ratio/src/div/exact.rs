@@ -0,0 +1,299 @@
+use core::fmt::{Display, Formatter};
+
+use crate::{utils::u128_to_u64_checked, Ratio};
+
+/// A ratio `(n/d)` applied to a u64 `x` where `xn/d` must be an integer.
+///
+/// Unlike [`crate::Floor`] and [`crate::Ceil`], no rounding is performed:
+/// applying the ratio to an `x` where `xn` is not divisible by `d` fails.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Exact<R>(pub R);
+
+/// Displayed as `Exact({{self.0})`
+impl<R: Display> Display for Exact<R> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!("Exact({})", self.0))
+    }
+}
+
+impl<R> Exact<R> {
+    /// Convenience constructor for better compatibility with type aliases
+    #[inline]
+    pub const fn new(r: R) -> Self {
+        Self(r)
+    }
+}
+
+/// The result of a non-integer [`Exact`] application,
+/// consisting of the 2 integers closest to `xn/d`.
+///
+/// invariant: `self.floor + 1 == self.ceil`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Inexact {
+    /// `floor(xn/d)`
+    pub floor: u64,
+
+    /// `ceil(xn/d)`
+    pub ceil: u64,
+}
+
+/// Displayed as `Inexact({self.floor}..{self.ceil})`
+impl Display for Inexact {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!("Inexact({}..{})", self.floor, self.ceil))
+    }
+}
+
+macro_rules! impl_exact_div {
+    ($N:ty, $D:ty) => {
+        impl Exact<Ratio<$N, $D>> {
+            /// # Returns
+            ///
+            /// - `Ok(amt * self.0.n / self.0.d)` if `amt * self.0.n` is divisible by `self.0.d`
+            /// - `Err(Inexact)` containing the floor and ceil of `amt * self.0.n / self.0.d` otherwise
+            ///
+            /// ## Special Case Returns
+            /// - `Ok(0)` if `self.0.is_zero()`
+            /// - `None` if `ceil(amt * self.0.n / self.0.d) > u64::MAX`
+            #[inline]
+            pub const fn apply(&self, amount: u64) -> Option<Result<u64, Inexact>> {
+                if self.0.is_zero() {
+                    return Some(Ok(0));
+                }
+                let Ratio { n, d } = self.0;
+                let d = d as u128;
+                let n = n as u128;
+                let x = amount as u128;
+                // unchecked-arith: mul will not overflow because
+                // both x and n are <= u64::MAX
+                let xn = x * n;
+                // unchecked-arith: ratio is not 0 so d != 0
+                let q = xn / d;
+                let r = xn % d;
+                let floor = match u128_to_u64_checked(q) {
+                    None => return None,
+                    Some(f) => f,
+                };
+                if r == 0 {
+                    return Some(Ok(floor));
+                }
+                match floor.checked_add(1) {
+                    None => None,
+                    Some(ceil) => Some(Err(Inexact { floor, ceil })),
+                }
+            }
+
+            /// # Returns
+            ///
+            /// The single value `x` such that `self.apply(x) == Some(Ok(amt_after_apply))`.
+            ///
+            /// ## Special Case Returns
+            ///
+            /// - `None` if `self.0.is_zero()`, since every `x` maps to `0`,
+            ///   and no `x` maps to any other value
+            /// - `None` if `amt_after_apply * self.0.d` is not divisible by `self.0.n`
+            ///   i.e. there is no `x` that maps exactly to `amt_after_apply`
+            /// - `None` if `x > u64::MAX`
+            ///
+            /// # Derivation
+            ///
+            /// ```md
+            /// let x = input amount we are trying to find
+            /// y = amt_after_apply
+            /// n = numerator
+            /// d = denominator
+            ///
+            /// y = xn / d, xn divisible by d
+            /// x = dy / n, dy divisible by n
+            /// ```
+            #[inline]
+            pub const fn reverse(&self, amt_after_apply: u64) -> Option<u64> {
+                if self.0.is_zero() {
+                    return None;
+                }
+                let Ratio { n, d } = self.0;
+                let d = d as u128;
+                let n = n as u128;
+                let y = amt_after_apply as u128;
+                // unchecked-arith: mul will not overflow because
+                // both d and y are <= u64::MAX
+                let dy = d * y;
+                // unchecked-arith: ratio is not 0 so n != 0
+                if dy % n != 0 {
+                    return None;
+                }
+                u128_to_u64_checked(dy / n)
+            }
+        }
+    };
+}
+
+impl_exact_div!(u8, u8);
+impl_exact_div!(u8, u16);
+impl_exact_div!(u8, u32);
+impl_exact_div!(u8, u64);
+
+impl_exact_div!(u16, u8);
+impl_exact_div!(u16, u16);
+impl_exact_div!(u16, u32);
+impl_exact_div!(u16, u64);
+
+impl_exact_div!(u32, u8);
+impl_exact_div!(u32, u16);
+impl_exact_div!(u32, u32);
+impl_exact_div!(u32, u64);
+
+impl_exact_div!(u64, u8);
+impl_exact_div!(u64, u16);
+impl_exact_div!(u64, u32);
+impl_exact_div!(u64, u64);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{Ceil, Floor};
+
+    use super::*;
+
+    macro_rules! test_suite {
+        (
+            $N:ty, $D:ty,
+            $nonzero_tests:ident,
+            $zero_tests:ident
+        ) => {
+            proptest! {
+                #[test]
+                fn $nonzero_tests(
+                    n in 1..=<$N>::MAX,
+                    d in 1..=<$D>::MAX,
+                    amt: u64,
+                    aaa: u64,
+                ) {
+                    let ratio = Ratio::<$N, $D>::new(n, d);
+                    let exact = Exact(ratio);
+
+                    // apply should agree with floor and ceil
+                    match (exact.apply(amt), Floor(ratio).apply(amt), Ceil(ratio).apply(amt)) {
+                        (Some(Ok(e)), Some(f), Some(c)) => {
+                            prop_assert_eq!(e, f);
+                            prop_assert_eq!(e, c);
+                        }
+                        (Some(Err(Inexact { floor, ceil })), Some(f), Some(c)) => {
+                            prop_assert!(f != c);
+                            prop_assert_eq!(floor, f);
+                            prop_assert_eq!(ceil, c);
+                        }
+                        (None, _, None) => (),
+                        (e, f, c) => {
+                            prop_assert!(false, "{} {} {:?} {:?} {:?}", exact, amt, e, f, c);
+                        }
+                    }
+
+                    // exact results round trip
+                    if let Some(Ok(app)) = exact.apply(amt) {
+                        prop_assert_eq!(exact.reverse(app), Some(amt));
+                    }
+
+                    // any reversal must apply exactly to the same value
+                    if let Some(x) = exact.reverse(aaa) {
+                        prop_assert_eq!(exact.apply(x), Some(Ok(aaa)));
+                    }
+                }
+            }
+
+            proptest! {
+                #[test]
+                fn $zero_tests(
+                    zer in <Ratio<$N, $D>>::prop_zero(),
+                    amt: u64,
+                ) {
+                    let zer = Exact(zer);
+                    prop_assert_eq!(zer.apply(amt), Some(Ok(0)));
+                    prop_assert!(zer.reverse(amt).is_none());
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, u8, exact_u8_u8_nonzero_tests, exact_u8_u8_zero_tests);
+    test_suite!(u8, u16, exact_u8_u16_nonzero_tests, exact_u8_u16_zero_tests);
+    test_suite!(u8, u32, exact_u8_u32_nonzero_tests, exact_u8_u32_zero_tests);
+    test_suite!(u8, u64, exact_u8_u64_nonzero_tests, exact_u8_u64_zero_tests);
+
+    test_suite!(u16, u8, exact_u16_u8_nonzero_tests, exact_u16_u8_zero_tests);
+    test_suite!(
+        u16,
+        u16,
+        exact_u16_u16_nonzero_tests,
+        exact_u16_u16_zero_tests
+    );
+    test_suite!(
+        u16,
+        u32,
+        exact_u16_u32_nonzero_tests,
+        exact_u16_u32_zero_tests
+    );
+    test_suite!(
+        u16,
+        u64,
+        exact_u16_u64_nonzero_tests,
+        exact_u16_u64_zero_tests
+    );
+
+    test_suite!(u32, u8, exact_u32_u8_nonzero_tests, exact_u32_u8_zero_tests);
+    test_suite!(
+        u32,
+        u16,
+        exact_u32_u16_nonzero_tests,
+        exact_u32_u16_zero_tests
+    );
+    test_suite!(
+        u32,
+        u32,
+        exact_u32_u32_nonzero_tests,
+        exact_u32_u32_zero_tests
+    );
+    test_suite!(
+        u32,
+        u64,
+        exact_u32_u64_nonzero_tests,
+        exact_u32_u64_zero_tests
+    );
+
+    test_suite!(u64, u8, exact_u64_u8_nonzero_tests, exact_u64_u8_zero_tests);
+    test_suite!(
+        u64,
+        u16,
+        exact_u64_u16_nonzero_tests,
+        exact_u64_u16_zero_tests
+    );
+    test_suite!(
+        u64,
+        u32,
+        exact_u64_u32_nonzero_tests,
+        exact_u64_u32_zero_tests
+    );
+    test_suite!(
+        u64,
+        u64,
+        exact_u64_u64_nonzero_tests,
+        exact_u64_u64_zero_tests
+    );
+
+    #[test]
+    fn exact_lot_size() {
+        // 1 lot = 100 units
+        let lots = Exact(Ratio::<u8, u8>::new(1, 100));
+        assert_eq!(lots.apply(500), Some(Ok(5)));
+        assert_eq!(lots.apply(550), Some(Err(Inexact { floor: 5, ceil: 6 })));
+        assert_eq!(lots.reverse(5), Some(500));
+
+        let units = Exact(Ratio::<u8, u8>::new(100, 1));
+        assert_eq!(units.reverse(550), None);
+        assert_eq!(units.reverse(500), Some(5));
+    }
+}
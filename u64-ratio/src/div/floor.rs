@@ -3,11 +3,20 @@ use core::{
     ops::RangeInclusive,
 };
 
-use crate::{utils::u128_to_u64_checked, Ratio};
+use crate::{
+    utils::{range_u128_to_u64, u128_to_u64_checked},
+    Ratio, RatioApply, U256,
+};
 
 /// A ratio `(n/d)` floor-applied to a u64 `x`. Output = `floor(xn/d)`
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Floor<R>(pub R);
 
 /// Displayed as `Floor({{self.0})`
@@ -169,6 +178,23 @@ macro_rules! impl_floor_div {
                 Some(min..=max)
             }
         }
+
+        impl RatioApply for Floor<Ratio<$N, $D>> {
+            #[inline]
+            fn apply(&self, amount: u64) -> Option<u64> {
+                Self::apply(self, amount)
+            }
+
+            #[inline]
+            fn reverse_est(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                Self::reverse_est(self, amt_after_apply)
+            }
+
+            #[inline]
+            fn reverse(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                Self::reverse(self, amt_after_apply)
+            }
+        }
     };
 }
 
@@ -192,6 +218,244 @@ impl_floor_div!(u64, u16);
 impl_floor_div!(u64, u32);
 impl_floor_div!(u64, u64);
 
+/// For ratios with a `u128` numerator or denominator,
+/// where `u128` is no longer sufficient as an intermediate
+/// for `u64` amounts.
+///
+/// Implemented in terms of the `_u128` methods
+macro_rules! impl_floor_div_wide {
+    ($N:ty, $D:ty) => {
+        impl Floor<Ratio<$N, $D>> {
+            /// # Returns
+            ///
+            /// `floor(amt * self.0.n / self.0.d)`
+            ///
+            /// ## Special Case Returns
+            /// - `0` if `self.0.is_zero()`
+            /// - `None` if `result > u64::MAX`
+            #[inline]
+            pub const fn apply(&self, amount: u64) -> Option<u64> {
+                match self.apply_u128(amount as u128) {
+                    None => None,
+                    Some(r) => u128_to_u64_checked(r),
+                }
+            }
+
+            /// # Returns
+            ///
+            /// `min..=max` the range of possible values that were fed into `self.apply()`
+            /// to get output `amt_after_apply`.
+            ///
+            /// See [`Self::reverse_u128`] for more details
+            #[inline]
+            pub const fn reverse(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                match self.reverse_est(amt_after_apply) {
+                    None => None,
+                    Some(r) => {
+                        if *r.start() > *r.end() {
+                            None
+                        } else {
+                            Some(r)
+                        }
+                    }
+                }
+            }
+
+            /// [`Self::reverse`], but returns an invalid/empty range
+            /// (e.g. `3..=2`) for the last special-case when `min > max`.
+            ///
+            /// While not numerically correct, this may be necessary to obtain
+            /// closest estimates
+            #[inline]
+            pub const fn reverse_est(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                match self.reverse_est_u128(amt_after_apply as u128) {
+                    None => None,
+                    Some(r) => range_u128_to_u64(r),
+                }
+            }
+        }
+
+        impl RatioApply for Floor<Ratio<$N, $D>> {
+            #[inline]
+            fn apply(&self, amount: u64) -> Option<u64> {
+                Self::apply(self, amount)
+            }
+
+            #[inline]
+            fn reverse_est(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                Self::reverse_est(self, amt_after_apply)
+            }
+
+            #[inline]
+            fn reverse(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                Self::reverse(self, amt_after_apply)
+            }
+        }
+    };
+}
+
+impl_floor_div_wide!(u8, u128);
+impl_floor_div_wide!(u16, u128);
+impl_floor_div_wide!(u32, u128);
+impl_floor_div_wide!(u64, u128);
+
+impl_floor_div_wide!(u128, u8);
+impl_floor_div_wide!(u128, u16);
+impl_floor_div_wide!(u128, u32);
+impl_floor_div_wide!(u128, u64);
+impl_floor_div_wide!(u128, u128);
+
+/// Application on `u128` amounts, using [`U256`] as the intermediate
+macro_rules! impl_floor_div_u128 {
+    ($N:ty, $D:ty) => {
+        impl Floor<Ratio<$N, $D>> {
+            /// [`Self::apply`] for `u128` amounts
+            ///
+            /// # Returns
+            ///
+            /// `floor(amt * self.0.n / self.0.d)`
+            ///
+            /// ## Special Case Returns
+            /// - `0` if `self.0.is_zero()`
+            /// - `None` if `result > u128::MAX`
+            #[inline]
+            pub const fn apply_u128(&self, amount: u128) -> Option<u128> {
+                if self.0.is_zero() {
+                    return Some(0);
+                }
+                let Ratio { n, d } = self.0;
+                let xn = U256::widening_mul(amount, n as u128);
+                // division-safety: ratio is not 0 so d != 0
+                let (res, _rem) = xn.div_rem_u128(d as u128);
+                res.to_u128_checked()
+            }
+
+            /// [`Self::reverse`] for `u128` amounts
+            ///
+            /// # Returns
+            ///
+            /// `min..=max` the range of possible values that were fed into `self.apply_u128()`
+            /// to get output `amt_after_apply`.
+            ///
+            /// `min` and `max` are saturated at `0` and `u128::MAX`.
+            ///
+            /// ## Special Case Returns
+            ///
+            /// - `0..=u128::MAX` if `self.0.is_zero()` and `amt_after_apply == 0`
+            /// - `None` if `self.0.is_zero()` but `amt_after_apply != 0`
+            /// - `None` if `min > u128::MAX`
+            /// - `None` if min > max. This can happen if N > D. e.g. `Floor(2/1).reverse_u128(any_odd_number)`
+            ///
+            /// See [`Self::reverse`] for derivation
+            #[inline]
+            pub const fn reverse_u128(
+                &self,
+                amt_after_apply: u128,
+            ) -> Option<RangeInclusive<u128>> {
+                match self.reverse_est_u128(amt_after_apply) {
+                    None => None,
+                    Some(r) => {
+                        if *r.start() > *r.end() {
+                            None
+                        } else {
+                            Some(r)
+                        }
+                    }
+                }
+            }
+
+            /// [`Self::reverse_u128`], but returns an invalid/empty range
+            /// (e.g. `3..=2`) for the last special-case when `min > max`.
+            ///
+            /// While not numerically correct, this may be necessary to obtain
+            /// closest estimates
+            #[inline]
+            pub const fn reverse_est_u128(
+                &self,
+                amt_after_apply: u128,
+            ) -> Option<RangeInclusive<u128>> {
+                if self.0.is_zero() {
+                    return if amt_after_apply == 0 {
+                        Some(0..=u128::MAX)
+                    } else {
+                        None
+                    };
+                }
+
+                let Ratio { n, d } = self.0;
+                let d = d as u128;
+                let n = n as u128;
+
+                let dy = U256::widening_mul(d, amt_after_apply);
+                // division-safety: ratio is not 0 so n != 0
+                let min = match dy.div_ceil_u128(n).to_u128_checked() {
+                    None => return None,
+                    Some(r) => r,
+                };
+
+                // unchecked-arith: even if d = y = u128::MAX,
+                // does not overflow u256, same as derivation
+                // in Self::reverse_est()
+                let dy_plus_d = match dy.checked_add_u128(d) {
+                    Some(x) => x,
+                    None => unreachable!(),
+                };
+                // division-safety: ratio is not 0 so n != 0
+                let (max, rem) = dy_plus_d.div_rem_u128(n);
+                let max = if rem == 0 {
+                    // range-exclusive, so must - 1
+                    //
+                    // unchecked-arith: if rem == 0
+                    // then max must be >0 since its
+                    // a non-zero multiple of n
+                    match max.checked_sub_u128(1) {
+                        Some(m) => m,
+                        None => unreachable!(),
+                    }
+                } else {
+                    max
+                };
+                let max = match max.to_u128_checked() {
+                    // saturation
+                    None => u128::MAX,
+                    Some(r) => r,
+                };
+                Some(min..=max)
+            }
+        }
+    };
+}
+
+impl_floor_div_u128!(u8, u8);
+impl_floor_div_u128!(u8, u16);
+impl_floor_div_u128!(u8, u32);
+impl_floor_div_u128!(u8, u64);
+impl_floor_div_u128!(u8, u128);
+
+impl_floor_div_u128!(u16, u8);
+impl_floor_div_u128!(u16, u16);
+impl_floor_div_u128!(u16, u32);
+impl_floor_div_u128!(u16, u64);
+impl_floor_div_u128!(u16, u128);
+
+impl_floor_div_u128!(u32, u8);
+impl_floor_div_u128!(u32, u16);
+impl_floor_div_u128!(u32, u32);
+impl_floor_div_u128!(u32, u64);
+impl_floor_div_u128!(u32, u128);
+
+impl_floor_div_u128!(u64, u8);
+impl_floor_div_u128!(u64, u16);
+impl_floor_div_u128!(u64, u32);
+impl_floor_div_u128!(u64, u64);
+impl_floor_div_u128!(u64, u128);
+
+impl_floor_div_u128!(u128, u8);
+impl_floor_div_u128!(u128, u16);
+impl_floor_div_u128!(u128, u32);
+impl_floor_div_u128!(u128, u64);
+impl_floor_div_u128!(u128, u128);
+
 #[cfg(test)]
 mod tests {
     use proptest::prelude::*;
@@ -422,4 +686,244 @@ mod tests {
         floor_u64_u64_nonzero_tests,
         floor_u64_u64_zero_tests
     );
+
+    macro_rules! test_suite_u128 {
+        (
+            $N:ty, $D:ty,
+            $nonzero_tests:ident,
+            $zero_tests:ident
+        ) => {
+            proptest! {
+                #[test]
+                fn $nonzero_tests(
+                    n in 1..=<$N>::MAX,
+                    d in 1..=<$D>::MAX,
+                    amt in prop_oneof![any::<u128>(), any::<u64>().prop_map(u128::from)],
+                    // aaa = amount_after_apply
+                    aaa in prop_oneof![any::<u128>(), any::<u64>().prop_map(u128::from)],
+                ) {
+                    let r = Floor(Ratio::<$N, $D>::new(n, d));
+
+                    if let Some(app) = r.apply_u128(amt) {
+                        // app <= xn/d < app + 1
+                        let xn = U256::widening_mul(amt, n.into());
+                        prop_assert!(U256::widening_mul(app, d.into()) <= xn);
+                        if let Some(app_plus_one) = app.checked_add(1) {
+                            prop_assert!(U256::widening_mul(app_plus_one, d.into()) > xn);
+                        }
+
+                        // any value derived from apply must have a valid reverse
+                        let rt = r.reverse_u128(app).unwrap();
+                        prop_assert!(rt.contains(&amt), "rt {} {} {:?}", r, amt, rt);
+                        // make sure that applying the ratio again
+                        // yields the same result
+                        prop_assert_eq!(r.apply_u128(*rt.start()), Some(app));
+                        prop_assert_eq!(r.apply_u128(*rt.end()), Some(app));
+                        // smaller valid reversal not in range should not exist
+                        if *rt.start() > 0 {
+                            prop_assert!(r.apply_u128(*rt.start() - 1) != Some(app));
+                        }
+                        // larger valid reversal not in range should not exist
+                        if *rt.end() < u128::MAX {
+                            prop_assert!(r.apply_u128(*rt.end() + 1) != Some(app));
+                        }
+                    }
+
+                    // u64 fns should agree with the u128 fns
+                    if let Ok(amt) = u64::try_from(amt) {
+                        prop_assert_eq!(
+                            r.apply(amt).map(u128::from),
+                            r.apply_u128(amt.into()).filter(|app| *app <= u64::MAX as u128),
+                        );
+                    }
+                    if let Ok(aaa) = u64::try_from(aaa) {
+                        let rev_u128 = r.reverse_est_u128(aaa.into());
+                        match r.reverse_est(aaa) {
+                            None => prop_assert!(
+                                rev_u128.is_none() || *rev_u128.unwrap().start() > u64::MAX as u128
+                            ),
+                            Some(rev) => {
+                                let rev_u128 = rev_u128.unwrap();
+                                prop_assert_eq!(u128::from(*rev.start()), *rev_u128.start());
+                                prop_assert_eq!(
+                                    u128::from(*rev.end()),
+                                    (*rev_u128.end()).min(u64::MAX as u128)
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+
+            proptest! {
+                #[test]
+                fn $zero_tests(
+                    zer in <Ratio<$N, $D>>::prop_zero(),
+                    amt: u128,
+                ) {
+                    let zer = Floor(zer);
+                    prop_assert_eq!(zer.apply_u128(amt).unwrap(), 0);
+                    if amt != 0 {
+                        prop_assert!(zer.reverse_u128(amt).is_none());
+                    }
+                    prop_assert_eq!(zer.reverse_u128(0).unwrap(), 0..=u128::MAX);
+                }
+            }
+        };
+    }
+
+    test_suite_u128!(
+        u8,
+        u8,
+        floor_u128_u8_u8_nonzero_tests,
+        floor_u128_u8_u8_zero_tests
+    );
+    test_suite_u128!(
+        u8,
+        u16,
+        floor_u128_u8_u16_nonzero_tests,
+        floor_u128_u8_u16_zero_tests
+    );
+    test_suite_u128!(
+        u8,
+        u32,
+        floor_u128_u8_u32_nonzero_tests,
+        floor_u128_u8_u32_zero_tests
+    );
+    test_suite_u128!(
+        u8,
+        u64,
+        floor_u128_u8_u64_nonzero_tests,
+        floor_u128_u8_u64_zero_tests
+    );
+    test_suite_u128!(
+        u8,
+        u128,
+        floor_u128_u8_u128_nonzero_tests,
+        floor_u128_u8_u128_zero_tests
+    );
+
+    test_suite_u128!(
+        u16,
+        u8,
+        floor_u128_u16_u8_nonzero_tests,
+        floor_u128_u16_u8_zero_tests
+    );
+    test_suite_u128!(
+        u16,
+        u16,
+        floor_u128_u16_u16_nonzero_tests,
+        floor_u128_u16_u16_zero_tests
+    );
+    test_suite_u128!(
+        u16,
+        u32,
+        floor_u128_u16_u32_nonzero_tests,
+        floor_u128_u16_u32_zero_tests
+    );
+    test_suite_u128!(
+        u16,
+        u64,
+        floor_u128_u16_u64_nonzero_tests,
+        floor_u128_u16_u64_zero_tests
+    );
+    test_suite_u128!(
+        u16,
+        u128,
+        floor_u128_u16_u128_nonzero_tests,
+        floor_u128_u16_u128_zero_tests
+    );
+
+    test_suite_u128!(
+        u32,
+        u8,
+        floor_u128_u32_u8_nonzero_tests,
+        floor_u128_u32_u8_zero_tests
+    );
+    test_suite_u128!(
+        u32,
+        u16,
+        floor_u128_u32_u16_nonzero_tests,
+        floor_u128_u32_u16_zero_tests
+    );
+    test_suite_u128!(
+        u32,
+        u32,
+        floor_u128_u32_u32_nonzero_tests,
+        floor_u128_u32_u32_zero_tests
+    );
+    test_suite_u128!(
+        u32,
+        u64,
+        floor_u128_u32_u64_nonzero_tests,
+        floor_u128_u32_u64_zero_tests
+    );
+    test_suite_u128!(
+        u32,
+        u128,
+        floor_u128_u32_u128_nonzero_tests,
+        floor_u128_u32_u128_zero_tests
+    );
+
+    test_suite_u128!(
+        u64,
+        u8,
+        floor_u128_u64_u8_nonzero_tests,
+        floor_u128_u64_u8_zero_tests
+    );
+    test_suite_u128!(
+        u64,
+        u16,
+        floor_u128_u64_u16_nonzero_tests,
+        floor_u128_u64_u16_zero_tests
+    );
+    test_suite_u128!(
+        u64,
+        u32,
+        floor_u128_u64_u32_nonzero_tests,
+        floor_u128_u64_u32_zero_tests
+    );
+    test_suite_u128!(
+        u64,
+        u64,
+        floor_u128_u64_u64_nonzero_tests,
+        floor_u128_u64_u64_zero_tests
+    );
+    test_suite_u128!(
+        u64,
+        u128,
+        floor_u128_u64_u128_nonzero_tests,
+        floor_u128_u64_u128_zero_tests
+    );
+
+    test_suite_u128!(
+        u128,
+        u8,
+        floor_u128_u128_u8_nonzero_tests,
+        floor_u128_u128_u8_zero_tests
+    );
+    test_suite_u128!(
+        u128,
+        u16,
+        floor_u128_u128_u16_nonzero_tests,
+        floor_u128_u128_u16_zero_tests
+    );
+    test_suite_u128!(
+        u128,
+        u32,
+        floor_u128_u128_u32_nonzero_tests,
+        floor_u128_u128_u32_zero_tests
+    );
+    test_suite_u128!(
+        u128,
+        u64,
+        floor_u128_u128_u64_nonzero_tests,
+        floor_u128_u128_u64_zero_tests
+    );
+    test_suite_u128!(
+        u128,
+        u128,
+        floor_u128_u128_u128_nonzero_tests,
+        floor_u128_u128_u128_zero_tests
+    );
 }
@@ -0,0 +1,423 @@
+use core::{
+    fmt::{Display, Formatter},
+    ops::RangeInclusive,
+};
+
+use crate::{utils::u128_to_u64_checked, Ratio, RatioApply};
+
+/// A ratio `(n/d)` applied to a u64 `x`, rounding to the nearest integer,
+/// with ties (`xn/d = k + 0.5`) rounded up. Output = `floor(xn/d + 1/2)`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct HalfUp<R>(pub R);
+
+/// Displayed as `HalfUp({{self.0})`
+impl<R: Display> Display for HalfUp<R> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!("HalfUp({})", self.0))
+    }
+}
+
+impl<R> HalfUp<R> {
+    /// Convenience constructor for better compatibility with type aliases
+    #[inline]
+    pub const fn new(r: R) -> Self {
+        Self(r)
+    }
+}
+
+macro_rules! impl_half_up_div {
+    ($N:ty, $D:ty) => {
+        impl HalfUp<Ratio<$N, $D>> {
+            /// # Returns
+            ///
+            /// `round(amt * self.0.n / self.0.d)`, with ties rounded up
+            ///
+            /// ## Special Case Returns
+            /// - `0` if `self.0.is_zero()`
+            /// - `None` if `result > u64::MAX`
+            #[inline]
+            pub const fn apply(&self, amount: u64) -> Option<u64> {
+                if self.0.is_zero() {
+                    return Some(0);
+                }
+                let Ratio { n, d } = self.0;
+                let d = d as u128;
+                let n = n as u128;
+                let x = amount as u128;
+                // unchecked-arith: mul will not overflow because
+                // both x and n are <= u64::MAX
+                let xn = x * n;
+                // unchecked-arith: ratio is not 0 so d != 0
+                let q = xn / d;
+                let r = xn % d;
+                // round up if r/d >= 1/2
+                // i.e. 2r >= d
+                // i.e. r >= d - r
+                //
+                // unchecked-arith: r < d
+                let res = if r >= d - r {
+                    // unchecked-arith: q <= xn < u128::MAX
+                    q + 1
+                } else {
+                    q
+                };
+                u128_to_u64_checked(res)
+            }
+
+            /// # Returns
+            ///
+            /// `min..=max` the range of possible values that were fed into `self.apply()`
+            /// to get output `amt_after_apply`.
+            ///
+            /// `min` and `max` are saturated at `0` and `u64::MAX`.
+            ///
+            /// `min` rounds up.
+            /// - Example: if the actual range has `min = 14.6`, then the range returned will be `15..=xx`
+            ///
+            /// `max` rounds down.
+            /// - Example: if the actual range has `max = 14.6`, then the range returned will be `xx..=14`
+            ///
+            /// ## Special Case Returns
+            ///
+            /// - `0..=u64::MAX` if `self.0.is_zero()` and `amt_after_apply == 0`
+            /// - `None` if `self.0.is_zero()` but `amt_after_apply != 0`
+            /// - `None` if `min > u64::MAX`
+            /// - `None` if min > max. This can happen if N > D. e.g. `HalfUp(2/1).reverse(any_odd_number)`
+            ///   NOTE: this makes `reverse` NOT a total function: excluding overflow, there are many values
+            ///   of `u64` where this fn would return `None`
+            ///
+            /// # Derivation
+            ///
+            /// ```md
+            /// let x = input amount we are trying to find
+            /// y = amt_after_apply
+            /// n = numerator
+            /// d = denominator
+            ///
+            /// y = floor(xn / d + 1/2)
+            /// y <= xn / d + 1/2 < y + 1
+            /// d(2y - 1) <= 2xn < d(2y + 1)
+            ///
+            /// d(2y + 1) may overflow u128, so let
+            /// dy = qn + r, 0 <= r < n
+            ///
+            /// LHS (min):
+            /// (2dy - d) / 2n <= x
+            /// q + (2r - d) / 2n <= x
+            ///
+            /// RHS (max):
+            /// x < (2dy + d) / 2n
+            /// x < q + (2r + d) / 2n
+            /// ```
+            #[inline]
+            pub const fn reverse(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                match self.reverse_est(amt_after_apply) {
+                    None => None,
+                    Some(r) => {
+                        if *r.start() > *r.end() {
+                            None
+                        } else {
+                            Some(r)
+                        }
+                    }
+                }
+            }
+
+            /// [`Self::reverse`], but returns an invalid/empty range
+            /// (e.g. `3..=2`) for the last special-case when `min > max`.
+            ///
+            /// While not numerically correct, this may be necessary to obtain
+            /// closest estimates
+            #[inline]
+            pub const fn reverse_est(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                if self.0.is_zero() {
+                    return if amt_after_apply == 0 {
+                        Some(0..=u64::MAX)
+                    } else {
+                        None
+                    };
+                }
+
+                let Ratio { n, d } = self.0;
+                let d = d as u128;
+                let n = n as u128;
+                let y = amt_after_apply as u128;
+
+                // unchecked-arith: mul will not overflow because
+                // both d and y are <= u64::MAX
+                let dy = d * y;
+                // unchecked-arith: ratio is not 0 so n != 0
+                let q = dy / n;
+                let r = dy % n;
+                // unchecked-arith: r < n <= u64::MAX, so no overflow
+                let two_r = 2 * r;
+                let two_n = 2 * n;
+
+                let min = if y == 0 {
+                    // LHS is negative
+                    0
+                } else if two_r > d {
+                    // 0 < 2r - d < 2n, so ceil((2r - d) / 2n) = 1
+                    //
+                    // unchecked-arith: q <= dy < u128::MAX
+                    q + 1
+                } else {
+                    // ceil((2r - d) / 2n) = -floor((d - 2r) / 2n)
+                    //
+                    // unchecked-arith: y >= 1 so
+                    // (2dy - d) / 2n > 0 and q >= floor((d - 2r) / 2n)
+                    q - (d - two_r) / two_n
+                };
+                let min = match u128_to_u64_checked(min) {
+                    None => return None,
+                    Some(r) => r,
+                };
+
+                // range-exclusive, so must - 1
+                //
+                // unchecked-arith: 2r + d > 0, so the ceil div >= 1.
+                // q + ceil((2r + d) / 2n) <= dy + d < u128::MAX,
+                // see derivation in Floor::reverse_est()
+                let max = q + (two_r + d).div_ceil(two_n) - 1;
+                let max = match u128_to_u64_checked(max) {
+                    // saturation
+                    None => u64::MAX,
+                    Some(r) => r,
+                };
+                Some(min..=max)
+            }
+        }
+
+        impl RatioApply for HalfUp<Ratio<$N, $D>> {
+            #[inline]
+            fn apply(&self, amount: u64) -> Option<u64> {
+                Self::apply(self, amount)
+            }
+
+            #[inline]
+            fn reverse_est(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                Self::reverse_est(self, amt_after_apply)
+            }
+
+            #[inline]
+            fn reverse(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                Self::reverse(self, amt_after_apply)
+            }
+        }
+    };
+}
+
+impl_half_up_div!(u8, u8);
+impl_half_up_div!(u8, u16);
+impl_half_up_div!(u8, u32);
+impl_half_up_div!(u8, u64);
+
+impl_half_up_div!(u16, u8);
+impl_half_up_div!(u16, u16);
+impl_half_up_div!(u16, u32);
+impl_half_up_div!(u16, u64);
+
+impl_half_up_div!(u32, u8);
+impl_half_up_div!(u32, u16);
+impl_half_up_div!(u32, u32);
+impl_half_up_div!(u32, u64);
+
+impl_half_up_div!(u64, u8);
+impl_half_up_div!(u64, u16);
+impl_half_up_div!(u64, u32);
+impl_half_up_div!(u64, u64);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{utils::test_utils::dist_from_exact, Ceil, Floor};
+
+    use super::*;
+
+    macro_rules! test_suite {
+        (
+            $N:ty, $D:ty,
+            $nonzero_tests:ident,
+            $zero_tests:ident
+        ) => {
+            proptest! {
+                #[test]
+                fn $nonzero_tests(
+                    // HalfUp(x) <= Ceil(x), so no overflow
+                    (amt, _amt_max, gte) in Ceil::<Ratio<$N, $D>>::prop_ratio_gte_one_amt_no_overflow(),
+                    // min of HalfUp.reverse() <= min of Floor.reverse(), so no overflow
+                    (aaa, _aaa_max, lte) in Floor::<Ratio<$N, $D>>::prop_ratio_lte_one_rev_no_overflow(),
+                    any_u64: u64,
+                ) {
+                    let gte = HalfUp(gte.0);
+                    let lte = HalfUp(lte.0);
+
+                    for (amt, r) in [(amt, gte), (any_u64, lte)] {
+                        let app = r.apply(amt).unwrap();
+                        // |app - xn/d| <= 1/2, with ties rounded up
+                        let (below, dist, d) = dist_from_exact(amt, app, r.0.n.into(), r.0.d.into());
+                        prop_assert!(
+                            2 * dist < d || (2 * dist == d && !below),
+                            "{} {} {}", r, amt, app,
+                        );
+
+                        let rt = r.reverse(app).unwrap();
+                        prop_assert!(rt.contains(&amt), "{} {} {:?}", r, amt, rt);
+                        // make sure that applying the ratio again
+                        // yields the same result
+                        let app_min = r.apply(*rt.start()).unwrap();
+                        let app_max = r.apply(*rt.end()).unwrap();
+                        prop_assert!(
+                            app == app_min &&
+                            app == app_max,
+                            "rt {} {} {:?}", r, amt, rt,
+                        );
+                        // smaller valid reversal not in range should not exist
+                        if *rt.start() > 0 {
+                            let app_smaller = r.apply(*rt.start() - 1).unwrap();
+                            prop_assert!(app != app_smaller);
+                        }
+                        // larger valid reversal not in range should not exist
+                        if *rt.end() < u64::MAX {
+                            if let Some(app_larger) = r.apply(*rt.end() + 1) {
+                                prop_assert!(app != app_larger);
+                            }
+                        }
+                    }
+
+                    // lte reverse is a total function
+                    // so reverse should work on any amount
+                    lte.reverse(aaa).unwrap();
+                }
+            }
+
+            proptest! {
+                #[test]
+                fn $zero_tests(
+                    zer in <Ratio<$N, $D>>::prop_zero(),
+                    amt: u64,
+                ) {
+                    let zer = HalfUp(zer);
+                    prop_assert_eq!(zer.apply(amt).unwrap(), 0);
+                    if amt != 0 {
+                        prop_assert!(zer.reverse(amt).is_none());
+                    }
+                    prop_assert_eq!(zer.reverse(0).unwrap(), 0..=u64::MAX);
+                }
+            }
+        };
+    }
+
+    test_suite!(
+        u8,
+        u8,
+        half_up_u8_u8_nonzero_tests,
+        half_up_u8_u8_zero_tests
+    );
+    test_suite!(
+        u8,
+        u16,
+        half_up_u8_u16_nonzero_tests,
+        half_up_u8_u16_zero_tests
+    );
+    test_suite!(
+        u8,
+        u32,
+        half_up_u8_u32_nonzero_tests,
+        half_up_u8_u32_zero_tests
+    );
+    test_suite!(
+        u8,
+        u64,
+        half_up_u8_u64_nonzero_tests,
+        half_up_u8_u64_zero_tests
+    );
+
+    test_suite!(
+        u16,
+        u8,
+        half_up_u16_u8_nonzero_tests,
+        half_up_u16_u8_zero_tests
+    );
+    test_suite!(
+        u16,
+        u16,
+        half_up_u16_u16_nonzero_tests,
+        half_up_u16_u16_zero_tests
+    );
+    test_suite!(
+        u16,
+        u32,
+        half_up_u16_u32_nonzero_tests,
+        half_up_u16_u32_zero_tests
+    );
+    test_suite!(
+        u16,
+        u64,
+        half_up_u16_u64_nonzero_tests,
+        half_up_u16_u64_zero_tests
+    );
+
+    test_suite!(
+        u32,
+        u8,
+        half_up_u32_u8_nonzero_tests,
+        half_up_u32_u8_zero_tests
+    );
+    test_suite!(
+        u32,
+        u16,
+        half_up_u32_u16_nonzero_tests,
+        half_up_u32_u16_zero_tests
+    );
+    test_suite!(
+        u32,
+        u32,
+        half_up_u32_u32_nonzero_tests,
+        half_up_u32_u32_zero_tests
+    );
+    test_suite!(
+        u32,
+        u64,
+        half_up_u32_u64_nonzero_tests,
+        half_up_u32_u64_zero_tests
+    );
+
+    test_suite!(
+        u64,
+        u8,
+        half_up_u64_u8_nonzero_tests,
+        half_up_u64_u8_zero_tests
+    );
+    test_suite!(
+        u64,
+        u16,
+        half_up_u64_u16_nonzero_tests,
+        half_up_u64_u16_zero_tests
+    );
+    test_suite!(
+        u64,
+        u32,
+        half_up_u64_u32_nonzero_tests,
+        half_up_u64_u32_zero_tests
+    );
+    test_suite!(
+        u64,
+        u64,
+        half_up_u64_u64_nonzero_tests,
+        half_up_u64_u64_zero_tests
+    );
+
+    #[test]
+    fn half_up_ties() {
+        let r = HalfUp(Ratio::<u8, u8>::new(1, 2));
+        for (amt, exp) in [(0, 0), (1, 1), (2, 1), (3, 2), (4, 2), (5, 3)] {
+            assert_eq!(r.apply(amt), Some(exp), "{amt}");
+        }
+        assert_eq!(r.reverse(1), Some(1..=2));
+        assert_eq!(r.reverse(2), Some(3..=4));
+    }
+}
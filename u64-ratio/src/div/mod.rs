@@ -1,8 +1,21 @@
+mod apply;
 mod ceil;
+mod exact;
 mod floor;
+mod half_even;
+mod half_up;
+mod overflow;
+mod signed;
+mod trunc;
+mod try_div;
 
+pub use apply::*;
 pub use ceil::*;
+pub use exact::*;
 pub use floor::*;
+pub use half_even::*;
+pub use half_up::*;
+pub use trunc::*;
 
 #[cfg(test)]
 mod tests {
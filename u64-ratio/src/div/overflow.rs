@@ -0,0 +1,262 @@
+use crate::{Ceil, Floor, Ratio};
+
+macro_rules! impl_overflow_policies {
+    ($R:ident, $N:ty, $D:ty) => {
+        impl $R<Ratio<$N, $D>> {
+            /// [`Self::apply`], but saturates at `u64::MAX` instead of returning `None`
+            #[inline]
+            pub const fn apply_saturating(&self, amount: u64) -> u64 {
+                match self.apply(amount) {
+                    // saturation
+                    None => u64::MAX,
+                    Some(x) => x,
+                }
+            }
+
+            /// [`Self::apply`], but on overflow, applies the ratio to the largest input
+            /// `<= amount` whose result fits into a `u64` instead of returning `None`
+            ///
+            /// # Returns
+            ///
+            /// `(input, output)`, where `input` is the amount the ratio was actually applied to
+            /// and `output = self.apply(input)`. `input == amount` if there was no overflow.
+            #[inline]
+            pub const fn apply_or_max_input(&self, amount: u64) -> (u64, u64) {
+                if let Some(x) = self.apply(amount) {
+                    return (amount, x);
+                }
+                // apply() only overflows if ratio > 1.0, so
+                // no input results in an output beyond u64::MAX and
+                // min <= u64::MAX.
+                // max is the largest input with output <= u64::MAX,
+                // even if u64::MAX itself is unattainable
+                let input = match self.reverse_est(u64::MAX) {
+                    Some(r) => *r.end(),
+                    None => unreachable!(),
+                };
+                match self.apply(input) {
+                    Some(x) => (input, x),
+                    None => unreachable!(),
+                }
+            }
+        }
+    };
+}
+
+/// Only for ratios with numerators <= `u64::MAX`,
+/// where the result of application to a `u64` always fits into a `u128`
+macro_rules! impl_apply_wide {
+    ($R:ident, $N:ty, $D:ty) => {
+        impl $R<Ratio<$N, $D>> {
+            /// [`Self::apply`], but returns the full `u128` result instead of `None`
+            /// if the result does not fit into a `u64`.
+            ///
+            /// Use [`Self::apply_u128`] for ratios with `u128` numerators.
+            #[inline]
+            pub const fn apply_wide(&self, amount: u64) -> u128 {
+                match self.apply_u128(amount as u128) {
+                    Some(x) => x,
+                    // amount * n <= (2^64 - 1)^2 < u128::MAX and d >= 1 so result <= amount * n,
+                    // so this is always Some
+                    None => unreachable!(),
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_all {
+    ($R:ident) => {
+        impl_overflow_policies!($R, u8, u8);
+        impl_overflow_policies!($R, u8, u16);
+        impl_overflow_policies!($R, u8, u32);
+        impl_overflow_policies!($R, u8, u64);
+        impl_overflow_policies!($R, u8, u128);
+
+        impl_overflow_policies!($R, u16, u8);
+        impl_overflow_policies!($R, u16, u16);
+        impl_overflow_policies!($R, u16, u32);
+        impl_overflow_policies!($R, u16, u64);
+        impl_overflow_policies!($R, u16, u128);
+
+        impl_overflow_policies!($R, u32, u8);
+        impl_overflow_policies!($R, u32, u16);
+        impl_overflow_policies!($R, u32, u32);
+        impl_overflow_policies!($R, u32, u64);
+        impl_overflow_policies!($R, u32, u128);
+
+        impl_overflow_policies!($R, u64, u8);
+        impl_overflow_policies!($R, u64, u16);
+        impl_overflow_policies!($R, u64, u32);
+        impl_overflow_policies!($R, u64, u64);
+        impl_overflow_policies!($R, u64, u128);
+
+        impl_overflow_policies!($R, u128, u8);
+        impl_overflow_policies!($R, u128, u16);
+        impl_overflow_policies!($R, u128, u32);
+        impl_overflow_policies!($R, u128, u64);
+        impl_overflow_policies!($R, u128, u128);
+
+        impl_apply_wide!($R, u8, u8);
+        impl_apply_wide!($R, u8, u16);
+        impl_apply_wide!($R, u8, u32);
+        impl_apply_wide!($R, u8, u64);
+        impl_apply_wide!($R, u8, u128);
+
+        impl_apply_wide!($R, u16, u8);
+        impl_apply_wide!($R, u16, u16);
+        impl_apply_wide!($R, u16, u32);
+        impl_apply_wide!($R, u16, u64);
+        impl_apply_wide!($R, u16, u128);
+
+        impl_apply_wide!($R, u32, u8);
+        impl_apply_wide!($R, u32, u16);
+        impl_apply_wide!($R, u32, u32);
+        impl_apply_wide!($R, u32, u64);
+        impl_apply_wide!($R, u32, u128);
+
+        impl_apply_wide!($R, u64, u8);
+        impl_apply_wide!($R, u64, u16);
+        impl_apply_wide!($R, u64, u32);
+        impl_apply_wide!($R, u64, u64);
+        impl_apply_wide!($R, u64, u128);
+    };
+}
+
+impl_all!(Floor);
+impl_all!(Ceil);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn overflow_policies_special_cases() {
+        let double = Floor(Ratio::<u8, u8> { n: 2, d: 1 });
+        assert_eq!(double.apply_saturating(u64::MAX), u64::MAX);
+        assert_eq!(double.apply_wide(u64::MAX), u64::MAX as u128 * 2);
+        assert_eq!(
+            double.apply_or_max_input(u64::MAX),
+            (u64::MAX / 2, u64::MAX - 1)
+        );
+
+        let three_halves = Ceil(Ratio::<u8, u8> { n: 3, d: 2 });
+        // ceil(3x/2) <= u64::MAX => x <= floor(2 * u64::MAX / 3)
+        let max_input = (2 * u64::MAX as u128 / 3) as u64;
+        assert_eq!(
+            three_halves.apply_or_max_input(u64::MAX),
+            (max_input, three_halves.apply(max_input).unwrap())
+        );
+        assert!(three_halves.apply(max_input + 1).is_none());
+    }
+
+    macro_rules! check_overflow_policies {
+        ($r:expr, $amt:expr) => {{
+            let r = $r;
+            let amt = $amt;
+            let res = r.apply(amt);
+            prop_assert_eq!(r.apply_saturating(amt), res.unwrap_or(u64::MAX));
+            let (input, output) = r.apply_or_max_input(amt);
+            prop_assert_eq!(r.apply(input), Some(output));
+            match res {
+                Some(x) => {
+                    prop_assert_eq!((input, output), (amt, x));
+                }
+                None => {
+                    prop_assert!(input < amt);
+                    prop_assert!(r.apply(input + 1).is_none());
+                }
+            }
+        }};
+    }
+
+    macro_rules! test_suite {
+        ($N:ty, $D:ty, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(n: $N, d: $D, amt: u64) {
+                    let ratio = Ratio::<$N, $D> { n, d };
+                    check_overflow_policies!(Floor(ratio), amt);
+                    check_overflow_policies!(Ceil(ratio), amt);
+                }
+            }
+        };
+    }
+
+    macro_rules! test_suite_wide {
+        ($N:ty, $D:ty, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(n: $N, d: $D, amt: u64) {
+                    let ratio = Ratio::<$N, $D> { n, d };
+                    let (floor, ceil) = if ratio.is_zero() {
+                        (0, 0)
+                    } else {
+                        let xn = amt as u128 * n as u128;
+                        (xn / d as u128, xn.div_ceil(d as u128))
+                    };
+                    prop_assert_eq!(Floor(ratio).apply_wide(amt), floor);
+                    prop_assert_eq!(Ceil(ratio).apply_wide(amt), ceil);
+                    prop_assert_eq!(Floor(ratio).apply(amt), u64::try_from(floor).ok());
+                    prop_assert_eq!(Ceil(ratio).apply(amt), u64::try_from(ceil).ok());
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, u8, overflow_policies_u8_u8);
+    test_suite!(u8, u16, overflow_policies_u8_u16);
+    test_suite!(u8, u32, overflow_policies_u8_u32);
+    test_suite!(u8, u64, overflow_policies_u8_u64);
+    test_suite!(u8, u128, overflow_policies_u8_u128);
+
+    test_suite!(u16, u8, overflow_policies_u16_u8);
+    test_suite!(u16, u16, overflow_policies_u16_u16);
+    test_suite!(u16, u32, overflow_policies_u16_u32);
+    test_suite!(u16, u64, overflow_policies_u16_u64);
+    test_suite!(u16, u128, overflow_policies_u16_u128);
+
+    test_suite!(u32, u8, overflow_policies_u32_u8);
+    test_suite!(u32, u16, overflow_policies_u32_u16);
+    test_suite!(u32, u32, overflow_policies_u32_u32);
+    test_suite!(u32, u64, overflow_policies_u32_u64);
+    test_suite!(u32, u128, overflow_policies_u32_u128);
+
+    test_suite!(u64, u8, overflow_policies_u64_u8);
+    test_suite!(u64, u16, overflow_policies_u64_u16);
+    test_suite!(u64, u32, overflow_policies_u64_u32);
+    test_suite!(u64, u64, overflow_policies_u64_u64);
+    test_suite!(u64, u128, overflow_policies_u64_u128);
+
+    test_suite!(u128, u8, overflow_policies_u128_u8);
+    test_suite!(u128, u16, overflow_policies_u128_u16);
+    test_suite!(u128, u32, overflow_policies_u128_u32);
+    test_suite!(u128, u64, overflow_policies_u128_u64);
+    test_suite!(u128, u128, overflow_policies_u128_u128);
+
+    test_suite_wide!(u8, u8, apply_wide_u8_u8);
+    test_suite_wide!(u8, u16, apply_wide_u8_u16);
+    test_suite_wide!(u8, u32, apply_wide_u8_u32);
+    test_suite_wide!(u8, u64, apply_wide_u8_u64);
+    test_suite_wide!(u8, u128, apply_wide_u8_u128);
+
+    test_suite_wide!(u16, u8, apply_wide_u16_u8);
+    test_suite_wide!(u16, u16, apply_wide_u16_u16);
+    test_suite_wide!(u16, u32, apply_wide_u16_u32);
+    test_suite_wide!(u16, u64, apply_wide_u16_u64);
+    test_suite_wide!(u16, u128, apply_wide_u16_u128);
+
+    test_suite_wide!(u32, u8, apply_wide_u32_u8);
+    test_suite_wide!(u32, u16, apply_wide_u32_u16);
+    test_suite_wide!(u32, u32, apply_wide_u32_u32);
+    test_suite_wide!(u32, u64, apply_wide_u32_u64);
+    test_suite_wide!(u32, u128, apply_wide_u32_u128);
+
+    test_suite_wide!(u64, u8, apply_wide_u64_u8);
+    test_suite_wide!(u64, u16, apply_wide_u64_u16);
+    test_suite_wide!(u64, u32, apply_wide_u64_u32);
+    test_suite_wide!(u64, u64, apply_wide_u64_u64);
+    test_suite_wide!(u64, u128, apply_wide_u64_u128);
+}
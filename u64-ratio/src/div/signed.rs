@@ -0,0 +1,433 @@
+use core::ops::RangeInclusive;
+
+use crate::{Ceil, Floor, Ratio};
+
+/// `i64::MIN.unsigned_abs()`
+const I64_MIN_ABS: u128 = 1 << 63;
+
+/// # Returns
+///
+/// `m` if `neg` is false, `-m` otherwise.
+///
+/// `None` if the result does not fit into an `i64`
+#[inline]
+pub(crate) const fn signed_i64(neg: bool, m: u128) -> Option<i64> {
+    if neg {
+        if m > I64_MIN_ABS {
+            None
+        } else {
+            // m <= 2^63 so -m fits into i64
+            Some((0 - m as i128) as i64)
+        }
+    } else if m > i64::MAX as u128 {
+        None
+    } else {
+        Some(m as i64)
+    }
+}
+
+/// Converts a reversal range of magnitudes of non-negative inputs
+/// into the corresponding `i64` range.
+///
+/// # Returns
+///
+/// - `None` if `min > i64::MAX` or `min > max`
+/// - `max` is saturated at `i64::MAX`
+#[inline]
+pub(crate) const fn pos_range(r: Option<RangeInclusive<u128>>) -> Option<RangeInclusive<i64>> {
+    let r = match r {
+        None => return None,
+        Some(r) => r,
+    };
+    let min = match signed_i64(false, *r.start()) {
+        None => return None,
+        Some(m) => m,
+    };
+    let max = match signed_i64(false, *r.end()) {
+        // saturation
+        None => i64::MAX,
+        Some(m) => m,
+    };
+    if min > max {
+        None
+    } else {
+        Some(min..=max)
+    }
+}
+
+/// Converts a reversal range of magnitudes of non-positive inputs
+/// into the corresponding `i64` range.
+///
+/// # Returns
+///
+/// - `None` if `-min < i64::MIN` or `min > max`
+/// - `-max` is saturated at `i64::MIN`
+#[inline]
+pub(crate) const fn neg_range(r: Option<RangeInclusive<u128>>) -> Option<RangeInclusive<i64>> {
+    let r = match r {
+        None => return None,
+        Some(r) => r,
+    };
+    let max = match signed_i64(true, *r.start()) {
+        None => return None,
+        Some(m) => m,
+    };
+    let min = match signed_i64(true, *r.end()) {
+        // saturation
+        None => i64::MIN,
+        Some(m) => m,
+    };
+    if min > max {
+        None
+    } else {
+        Some(min..=max)
+    }
+}
+
+macro_rules! impl_signed_div {
+    ($N:ty, $D:ty) => {
+        impl Floor<Ratio<$N, $D>> {
+            /// [`Self::apply`] for signed amounts, rounding toward negative infinity.
+            ///
+            /// # Returns
+            ///
+            /// `floor(amt * self.0.n / self.0.d)`
+            ///
+            /// ## Special Case Returns
+            /// - `0` if `self.0.is_zero()`
+            /// - `None` if the result does not fit into an `i64`
+            #[inline]
+            pub const fn apply_i64(&self, amount: i64) -> Option<i64> {
+                let m = amount.unsigned_abs() as u128;
+                // floor(-x) = -ceil(x)
+                let res = if amount < 0 {
+                    Ceil(self.0).apply_u128(m)
+                } else {
+                    self.apply_u128(m)
+                };
+                match res {
+                    None => None,
+                    Some(r) => signed_i64(amount < 0, r),
+                }
+            }
+
+            /// [`Self::reverse`] for signed amounts
+            ///
+            /// # Returns
+            ///
+            /// `min..=max` the range of possible values that were fed into `self.apply_i64()`
+            /// to get output `amt_after_apply`.
+            ///
+            /// `min` and `max` are saturated at `i64::MIN` and `i64::MAX`.
+            ///
+            /// ## Special Case Returns
+            ///
+            /// - `i64::MIN..=i64::MAX` if `self.0.is_zero()` and `amt_after_apply == 0`
+            /// - `None` if `self.0.is_zero()` but `amt_after_apply != 0`
+            /// - `None` if `min < i64::MIN` or `min > i64::MAX`
+            /// - `None` if min > max, same as [`Self::reverse`]
+            #[inline]
+            pub const fn reverse_i64(&self, amt_after_apply: i64) -> Option<RangeInclusive<i64>> {
+                if self.0.is_zero() {
+                    return if amt_after_apply == 0 {
+                        Some(i64::MIN..=i64::MAX)
+                    } else {
+                        None
+                    };
+                }
+                let m = amt_after_apply.unsigned_abs() as u128;
+                // nonzero ratio: negative inputs have negative outputs,
+                // non-negative inputs have non-negative outputs
+                if amt_after_apply < 0 {
+                    // floor(-x) = -ceil(x)
+                    neg_range(Ceil(self.0).reverse_u128(m))
+                } else {
+                    pos_range(self.reverse_u128(m))
+                }
+            }
+        }
+
+        impl Ceil<Ratio<$N, $D>> {
+            /// [`Self::apply`] for signed amounts, rounding toward positive infinity.
+            ///
+            /// # Returns
+            ///
+            /// `ceil(amt * self.0.n / self.0.d)`
+            ///
+            /// ## Special Case Returns
+            /// - `0` if `self.0.is_zero()`
+            /// - `None` if the result does not fit into an `i64`
+            #[inline]
+            pub const fn apply_i64(&self, amount: i64) -> Option<i64> {
+                let m = amount.unsigned_abs() as u128;
+                // ceil(-x) = -floor(x)
+                let res = if amount < 0 {
+                    Floor(self.0).apply_u128(m)
+                } else {
+                    self.apply_u128(m)
+                };
+                match res {
+                    None => None,
+                    Some(r) => signed_i64(amount < 0, r),
+                }
+            }
+
+            /// [`Self::reverse`] for signed amounts
+            ///
+            /// # Returns
+            ///
+            /// `min..=max` the range of possible values that were fed into `self.apply_i64()`
+            /// to get output `amt_after_apply`.
+            ///
+            /// See [`Floor::reverse_i64`] for more details
+            #[inline]
+            pub const fn reverse_i64(&self, amt_after_apply: i64) -> Option<RangeInclusive<i64>> {
+                if self.0.is_zero() {
+                    return if amt_after_apply == 0 {
+                        Some(i64::MIN..=i64::MAX)
+                    } else {
+                        None
+                    };
+                }
+                let m = amt_after_apply.unsigned_abs() as u128;
+                // nonzero ratio: positive inputs have positive outputs,
+                // non-positive inputs have non-positive outputs
+                if amt_after_apply > 0 {
+                    pos_range(self.reverse_u128(m))
+                } else {
+                    // ceil(-x) = -floor(x)
+                    neg_range(Floor(self.0).reverse_u128(m))
+                }
+            }
+        }
+    };
+}
+
+impl_signed_div!(u8, u8);
+impl_signed_div!(u8, u16);
+impl_signed_div!(u8, u32);
+impl_signed_div!(u8, u64);
+impl_signed_div!(u8, u128);
+
+impl_signed_div!(u16, u8);
+impl_signed_div!(u16, u16);
+impl_signed_div!(u16, u32);
+impl_signed_div!(u16, u64);
+impl_signed_div!(u16, u128);
+
+impl_signed_div!(u32, u8);
+impl_signed_div!(u32, u16);
+impl_signed_div!(u32, u32);
+impl_signed_div!(u32, u64);
+impl_signed_div!(u32, u128);
+
+impl_signed_div!(u64, u8);
+impl_signed_div!(u64, u16);
+impl_signed_div!(u64, u32);
+impl_signed_div!(u64, u64);
+impl_signed_div!(u64, u128);
+
+impl_signed_div!(u128, u8);
+impl_signed_div!(u128, u16);
+impl_signed_div!(u128, u32);
+impl_signed_div!(u128, u64);
+impl_signed_div!(u128, u128);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::Trunc;
+
+    use super::*;
+
+    #[test]
+    fn signed_special_cases() {
+        let r = Ratio::<u8, u8> { n: 1, d: 2 };
+        assert_eq!(Floor(r).apply_i64(-3), Some(-2));
+        assert_eq!(Ceil(r).apply_i64(-3), Some(-1));
+        assert_eq!(Trunc(r).apply_i64(-3), Some(-1));
+        assert_eq!(Floor(r).apply_i64(3), Some(1));
+        assert_eq!(Ceil(r).apply_i64(3), Some(2));
+        assert_eq!(Trunc(r).apply_i64(3), Some(1));
+
+        assert_eq!(Floor(r).reverse_i64(-2), Some(-4..=-3));
+        assert_eq!(Ceil(r).reverse_i64(-1), Some(-3..=-2));
+        assert_eq!(Trunc(r).reverse_i64(0), Some(-1..=1));
+        assert_eq!(Floor(r).reverse_i64(0), Some(0..=1));
+        assert_eq!(Ceil(r).reverse_i64(0), Some(-1..=0));
+
+        let double = Ratio::<u8, u8> { n: 2, d: 1 };
+        assert_eq!(Floor(double).apply_i64(i64::MIN / 2), Some(i64::MIN));
+        assert_eq!(Floor(double).apply_i64(i64::MIN / 2 - 1), None);
+        assert_eq!(Floor(double).apply_i64(i64::MAX / 2 + 1), None);
+        assert_eq!(
+            Floor(double).reverse_i64(i64::MIN),
+            Some(i64::MIN / 2..=i64::MIN / 2)
+        );
+        assert_eq!(Floor(double).reverse_i64(-3), None);
+
+        let zero = Ratio::<u8, u8>::ZERO;
+        assert_eq!(Floor(zero).reverse_i64(0), Some(i64::MIN..=i64::MAX));
+        assert_eq!(Trunc(zero).reverse_i64(-1), None);
+    }
+
+    /// Checks that `rev` is exactly the set of inputs that `app` maps to `y`
+    fn check_reverse(
+        app: impl Fn(i64) -> Option<i64>,
+        rev: Option<RangeInclusive<i64>>,
+        x: i64,
+    ) -> Result<(), TestCaseError> {
+        let y = match app(x) {
+            None => return Ok(()),
+            Some(y) => y,
+        };
+        let rev = rev.unwrap();
+        prop_assert!(rev.contains(&x), "{x} {rev:?}");
+        for bound in [*rev.start(), *rev.end()] {
+            prop_assert_eq!(app(bound), Some(y));
+        }
+        if let Some(smaller) = rev.start().checked_sub(1) {
+            prop_assert!(app(smaller) != Some(y));
+        }
+        if let Some(larger) = rev.end().checked_add(1) {
+            prop_assert!(app(larger) != Some(y));
+        }
+        Ok(())
+    }
+
+    macro_rules! check_all {
+        ($ratio:expr, $x:expr) => {{
+            let r = $ratio;
+            let x = $x;
+            let floor = Floor(r);
+            let ceil = Ceil(r);
+            let trunc = Trunc(r);
+            check_reverse(
+                |x| floor.apply_i64(x),
+                floor.apply_i64(x).and_then(|y| floor.reverse_i64(y)),
+                x,
+            )?;
+            check_reverse(
+                |x| ceil.apply_i64(x),
+                ceil.apply_i64(x).and_then(|y| ceil.reverse_i64(y)),
+                x,
+            )?;
+            check_reverse(
+                |x| trunc.apply_i64(x),
+                trunc.apply_i64(x).and_then(|y| trunc.reverse_i64(y)),
+                x,
+            )?;
+            // unsigned consistency
+            if x >= 0 {
+                prop_assert_eq!(
+                    floor.apply_i64(x),
+                    floor.apply(x as u64).and_then(|y| i64::try_from(y).ok())
+                );
+                prop_assert_eq!(
+                    ceil.apply_i64(x),
+                    ceil.apply(x as u64).and_then(|y| i64::try_from(y).ok())
+                );
+            }
+            // symmetry
+            if x != i64::MIN {
+                prop_assert_eq!(
+                    floor.apply_i64(-x),
+                    ceil.apply_i64(x).and_then(|y| y.checked_neg())
+                );
+                prop_assert_eq!(
+                    trunc.apply_i64(-x),
+                    trunc.apply_i64(x).and_then(|y| y.checked_neg())
+                );
+            }
+        }};
+    }
+
+    macro_rules! test_suite {
+        ($N:ty, $D:ty, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(n: $N, d: $D, x: i64, small_x in -256i64..=256) {
+                    let r = Ratio::<$N, $D> { n, d };
+                    check_all!(r, x);
+                    check_all!(r, small_x);
+                }
+            }
+        };
+    }
+
+    /// Compares against `i128` reference implementations
+    /// for ratios whose numerators fit into `u64`
+    macro_rules! test_suite_ref {
+        ($N:ty, $D:ty, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(n: $N, d: $D, x: i64) {
+                    let r = Ratio::<$N, $D> { n, d };
+                    let (floor, ceil, trunc) = if r.is_zero() {
+                        (0, 0, 0)
+                    } else {
+                        let xn = x as i128 * n as i128;
+                        let d = i128::try_from(d).unwrap();
+                        let floor = xn.div_euclid(d);
+                        let ceil = if xn % d == 0 { floor } else { floor + 1 };
+                        (floor, ceil, xn / d)
+                    };
+                    prop_assert_eq!(Floor(r).apply_i64(x), i64::try_from(floor).ok());
+                    prop_assert_eq!(Ceil(r).apply_i64(x), i64::try_from(ceil).ok());
+                    prop_assert_eq!(Trunc(r).apply_i64(x), i64::try_from(trunc).ok());
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, u8, signed_u8_u8);
+    test_suite!(u8, u16, signed_u8_u16);
+    test_suite!(u8, u32, signed_u8_u32);
+    test_suite!(u8, u64, signed_u8_u64);
+    test_suite!(u8, u128, signed_u8_u128);
+
+    test_suite!(u16, u8, signed_u16_u8);
+    test_suite!(u16, u16, signed_u16_u16);
+    test_suite!(u16, u32, signed_u16_u32);
+    test_suite!(u16, u64, signed_u16_u64);
+    test_suite!(u16, u128, signed_u16_u128);
+
+    test_suite!(u32, u8, signed_u32_u8);
+    test_suite!(u32, u16, signed_u32_u16);
+    test_suite!(u32, u32, signed_u32_u32);
+    test_suite!(u32, u64, signed_u32_u64);
+    test_suite!(u32, u128, signed_u32_u128);
+
+    test_suite!(u64, u8, signed_u64_u8);
+    test_suite!(u64, u16, signed_u64_u16);
+    test_suite!(u64, u32, signed_u64_u32);
+    test_suite!(u64, u64, signed_u64_u64);
+    test_suite!(u64, u128, signed_u64_u128);
+
+    test_suite!(u128, u8, signed_u128_u8);
+    test_suite!(u128, u16, signed_u128_u16);
+    test_suite!(u128, u32, signed_u128_u32);
+    test_suite!(u128, u64, signed_u128_u64);
+    test_suite!(u128, u128, signed_u128_u128);
+
+    test_suite_ref!(u8, u8, signed_ref_u8_u8);
+    test_suite_ref!(u8, u16, signed_ref_u8_u16);
+    test_suite_ref!(u8, u32, signed_ref_u8_u32);
+    test_suite_ref!(u8, u64, signed_ref_u8_u64);
+
+    test_suite_ref!(u16, u8, signed_ref_u16_u8);
+    test_suite_ref!(u16, u16, signed_ref_u16_u16);
+    test_suite_ref!(u16, u32, signed_ref_u16_u32);
+    test_suite_ref!(u16, u64, signed_ref_u16_u64);
+
+    test_suite_ref!(u32, u8, signed_ref_u32_u8);
+    test_suite_ref!(u32, u16, signed_ref_u32_u16);
+    test_suite_ref!(u32, u32, signed_ref_u32_u32);
+    test_suite_ref!(u32, u64, signed_ref_u32_u64);
+
+    test_suite_ref!(u64, u8, signed_ref_u64_u8);
+    test_suite_ref!(u64, u16, signed_ref_u64_u16);
+    test_suite_ref!(u64, u32, signed_ref_u64_u32);
+    test_suite_ref!(u64, u64, signed_ref_u64_u64);
+}
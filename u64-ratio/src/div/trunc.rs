@@ -0,0 +1,124 @@
+use core::{
+    fmt::{Display, Formatter},
+    ops::RangeInclusive,
+};
+
+use crate::{
+    div::signed::{neg_range, pos_range, signed_i64},
+    Floor, Ratio,
+};
+
+/// A ratio `(n/d)` applied to an i64 `x`, rounding toward zero. Output = `trunc(xn/d)`
+///
+/// For non-negative amounts, this is the same as [`Floor`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Trunc<R>(pub R);
+
+/// Displayed as `Trunc({{self.0})`
+impl<R: Display> Display for Trunc<R> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!("Trunc({})", self.0))
+    }
+}
+
+impl<R> Trunc<R> {
+    /// Convenience constructor for better compatibility with type aliases
+    #[inline]
+    pub const fn new(r: R) -> Self {
+        Self(r)
+    }
+}
+
+macro_rules! impl_trunc_div {
+    ($N:ty, $D:ty) => {
+        impl Trunc<Ratio<$N, $D>> {
+            /// # Returns
+            ///
+            /// `trunc(amt * self.0.n / self.0.d)`
+            ///
+            /// ## Special Case Returns
+            /// - `0` if `self.0.is_zero()`
+            /// - `None` if the result does not fit into an `i64`
+            #[inline]
+            pub const fn apply_i64(&self, amount: i64) -> Option<i64> {
+                // trunc(-x) = -floor(x)
+                match Floor(self.0).apply_u128(amount.unsigned_abs() as u128) {
+                    None => None,
+                    Some(r) => signed_i64(amount < 0, r),
+                }
+            }
+
+            /// # Returns
+            ///
+            /// `min..=max` the range of possible values that were fed into `self.apply_i64()`
+            /// to get output `amt_after_apply`.
+            ///
+            /// `min` and `max` are saturated at `i64::MIN` and `i64::MAX`.
+            ///
+            /// ## Special Case Returns
+            ///
+            /// - `i64::MIN..=i64::MAX` if `self.0.is_zero()` and `amt_after_apply == 0`
+            /// - `None` if `self.0.is_zero()` but `amt_after_apply != 0`
+            /// - `None` if `min < i64::MIN` or `min > i64::MAX`
+            /// - `None` if min > max, same as [`Floor::reverse`]
+            #[inline]
+            pub const fn reverse_i64(&self, amt_after_apply: i64) -> Option<RangeInclusive<i64>> {
+                if self.0.is_zero() {
+                    return if amt_after_apply == 0 {
+                        Some(i64::MIN..=i64::MAX)
+                    } else {
+                        None
+                    };
+                }
+                let floor = Floor(self.0);
+                let m = amt_after_apply.unsigned_abs() as u128;
+                if amt_after_apply > 0 {
+                    pos_range(floor.reverse_u128(m))
+                } else if amt_after_apply < 0 {
+                    neg_range(floor.reverse_u128(m))
+                } else {
+                    // both negative and non-negative inputs can output 0
+                    match (
+                        neg_range(floor.reverse_u128(0)),
+                        pos_range(floor.reverse_u128(0)),
+                    ) {
+                        (Some(neg), Some(pos)) => Some(*neg.start()..=*pos.end()),
+                        _ => None,
+                    }
+                }
+            }
+        }
+    };
+}
+
+impl_trunc_div!(u8, u8);
+impl_trunc_div!(u8, u16);
+impl_trunc_div!(u8, u32);
+impl_trunc_div!(u8, u64);
+impl_trunc_div!(u8, u128);
+
+impl_trunc_div!(u16, u8);
+impl_trunc_div!(u16, u16);
+impl_trunc_div!(u16, u32);
+impl_trunc_div!(u16, u64);
+impl_trunc_div!(u16, u128);
+
+impl_trunc_div!(u32, u8);
+impl_trunc_div!(u32, u16);
+impl_trunc_div!(u32, u32);
+impl_trunc_div!(u32, u64);
+impl_trunc_div!(u32, u128);
+
+impl_trunc_div!(u64, u8);
+impl_trunc_div!(u64, u16);
+impl_trunc_div!(u64, u32);
+impl_trunc_div!(u64, u64);
+impl_trunc_div!(u64, u128);
+
+impl_trunc_div!(u128, u8);
+impl_trunc_div!(u128, u16);
+impl_trunc_div!(u128, u32);
+impl_trunc_div!(u128, u64);
+impl_trunc_div!(u128, u128);
@@ -0,0 +1,274 @@
+use core::ops::RangeInclusive;
+
+use crate::{Ceil, Floor, HalfEven, HalfUp, Ratio, RatioError};
+
+macro_rules! impl_try_div {
+    ($R:ident, $N:ty, $D:ty) => {
+        impl $R<Ratio<$N, $D>> {
+            /// [`Self::apply`], but returns a [`RatioError`] instead of `None`
+            ///
+            /// # Errors
+            ///
+            /// - [`RatioError::Overflow`] if `result > u64::MAX`
+            #[inline]
+            pub const fn try_apply(&self, amount: u64) -> Result<u64, RatioError> {
+                match self.apply(amount) {
+                    None => Err(RatioError::Overflow),
+                    Some(x) => Ok(x),
+                }
+            }
+
+            /// [`Self::reverse`], but returns a [`RatioError`] describing
+            /// why no range could be returned instead of `None`
+            ///
+            /// # Errors
+            ///
+            /// - [`RatioError::ZeroRatio`] if `self.0.is_zero()` but `amt_after_apply != 0`
+            /// - [`RatioError::Overflow`] if `min > u64::MAX`
+            /// - [`RatioError::Unattainable`] if `min > max`, containing
+            ///   whichever of the 2 inputs around the gap outputs the value closest to `amt_after_apply`
+            #[inline]
+            pub const fn try_reverse(
+                &self,
+                amt_after_apply: u64,
+            ) -> Result<RangeInclusive<u64>, RatioError> {
+                if self.0.is_zero() && amt_after_apply != 0 {
+                    return Err(RatioError::ZeroRatio);
+                }
+                let r = match self.reverse_est(amt_after_apply) {
+                    None => return Err(RatioError::Overflow),
+                    Some(r) => r,
+                };
+                let min = *r.start();
+                let max = *r.end();
+                if min <= max {
+                    return Ok(r);
+                }
+                // min > max: self.apply(max) < amt_after_apply < self.apply(min)
+                let nearest = match (self.apply(max), self.apply(min)) {
+                    (Some(below), Some(above)) => {
+                        if amt_after_apply.abs_diff(below) <= above.abs_diff(amt_after_apply) {
+                            max
+                        } else {
+                            min
+                        }
+                    }
+                    // self.apply(min) > u64::MAX so is further away than self.apply(max)
+                    _ => max,
+                };
+                Err(RatioError::Unattainable { nearest })
+            }
+        }
+    };
+}
+
+macro_rules! impl_try_div_all {
+    ($R:ident) => {
+        impl_try_div!($R, u8, u8);
+        impl_try_div!($R, u8, u16);
+        impl_try_div!($R, u8, u32);
+        impl_try_div!($R, u8, u64);
+
+        impl_try_div!($R, u16, u8);
+        impl_try_div!($R, u16, u16);
+        impl_try_div!($R, u16, u32);
+        impl_try_div!($R, u16, u64);
+
+        impl_try_div!($R, u32, u8);
+        impl_try_div!($R, u32, u16);
+        impl_try_div!($R, u32, u32);
+        impl_try_div!($R, u32, u64);
+
+        impl_try_div!($R, u64, u8);
+        impl_try_div!($R, u64, u16);
+        impl_try_div!($R, u64, u32);
+        impl_try_div!($R, u64, u64);
+    };
+}
+
+impl_try_div_all!(Floor);
+impl_try_div_all!(Ceil);
+impl_try_div_all!(HalfUp);
+impl_try_div_all!(HalfEven);
+
+impl_try_div!(Floor, u8, u128);
+impl_try_div!(Floor, u16, u128);
+impl_try_div!(Floor, u32, u128);
+impl_try_div!(Floor, u64, u128);
+
+impl_try_div!(Floor, u128, u8);
+impl_try_div!(Floor, u128, u16);
+impl_try_div!(Floor, u128, u32);
+impl_try_div!(Floor, u128, u64);
+impl_try_div!(Floor, u128, u128);
+
+impl_try_div!(Ceil, u8, u128);
+impl_try_div!(Ceil, u16, u128);
+impl_try_div!(Ceil, u32, u128);
+impl_try_div!(Ceil, u64, u128);
+
+impl_try_div!(Ceil, u128, u8);
+impl_try_div!(Ceil, u128, u16);
+impl_try_div!(Ceil, u128, u32);
+impl_try_div!(Ceil, u128, u64);
+impl_try_div!(Ceil, u128, u128);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn try_reverse_special_cases() {
+        assert_eq!(
+            Floor(Ratio::<u8, u8>::ZERO).try_reverse(1),
+            Err(RatioError::ZeroRatio)
+        );
+        assert_eq!(
+            Floor(Ratio::<u8, u8>::ZERO).try_reverse(0),
+            Ok(0..=u64::MAX)
+        );
+        assert_eq!(
+            Ceil(Ratio::<u8, u8> { n: 1, d: 2 }).try_reverse(u64::MAX),
+            Err(RatioError::Overflow)
+        );
+        assert_eq!(Floor(Ratio::<u8, u8> { n: 1, d: 2 }).try_apply(1), Ok(0));
+        assert_eq!(
+            Floor(Ratio::<u8, u8> { n: 2, d: 1 }).try_apply(u64::MAX),
+            Err(RatioError::Overflow)
+        );
+        // 1 -> 2, 2 -> 4: tie, smaller input
+        assert_eq!(
+            Floor(Ratio::<u8, u8> { n: 2, d: 1 }).try_reverse(3),
+            Err(RatioError::Unattainable { nearest: 1 })
+        );
+        // 1 -> 3, 2 -> 6
+        for (aaf, nearest) in [(4, 1), (5, 2)] {
+            assert_eq!(
+                Floor(Ratio::<u8, u8> { n: 3, d: 1 }).try_reverse(aaf),
+                Err(RatioError::Unattainable { nearest })
+            );
+        }
+        // u64::MAX - 1 -> u64::MAX - 1, u64::MAX -> overflow
+        assert_eq!(
+            Floor(Ratio::<u8, u8> { n: 2, d: 1 }).try_reverse(u64::MAX),
+            Err(RatioError::Unattainable {
+                nearest: u64::MAX / 2
+            })
+        );
+    }
+
+    macro_rules! check_try_div {
+        ($r:expr, $amt:expr, $aaf:expr) => {{
+            let r = $r;
+            prop_assert_eq!(r.try_apply($amt).ok(), r.apply($amt));
+
+            let res = r.try_reverse($aaf);
+            prop_assert_eq!(res.clone().ok(), r.reverse($aaf));
+            match res {
+                Ok(_) => (),
+                Err(RatioError::ZeroRatio) => {
+                    prop_assert!(r.0.is_zero() && $aaf != 0);
+                }
+                Err(RatioError::Overflow) => {
+                    prop_assert!(!r.0.is_zero());
+                    prop_assert!(r.reverse_est($aaf).is_none());
+                }
+                Err(RatioError::Unattainable { nearest }) => {
+                    let est = r.reverse_est($aaf).unwrap();
+                    let (min, max) = (*est.start(), *est.end());
+                    prop_assert!(min > max);
+                    prop_assert!(nearest == min || nearest == max);
+                    let other = if nearest == min { max } else { min };
+                    let dist = r.apply(nearest).unwrap().abs_diff($aaf);
+                    if let Some(o) = r.apply(other) {
+                        let other_dist = o.abs_diff($aaf);
+                        prop_assert!(dist < other_dist || (dist == other_dist && nearest < other));
+                    }
+                }
+                Err(e) => return Err(TestCaseError::fail(format!("unexpected {e}"))),
+            }
+        }};
+    }
+
+    macro_rules! test_suite {
+        ($N:ty, $D:ty, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(n: $N, d: $D, amt: u64, aaf: u64, small_aaf in 0..=u8::MAX as u64) {
+                    let ratio = Ratio::<$N, $D> { n, d };
+                    for aaf in [aaf, small_aaf] {
+                        check_try_div!(Floor(ratio), amt, aaf);
+                        check_try_div!(Ceil(ratio), amt, aaf);
+                    }
+                }
+            }
+        };
+    }
+
+    macro_rules! test_suite_nearest {
+        ($N:ty, $D:ty, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(n: $N, d: $D, amt: u64, aaf: u64, small_aaf in 0..=u8::MAX as u64) {
+                    let ratio = Ratio::<$N, $D> { n, d };
+                    for aaf in [aaf, small_aaf] {
+                        check_try_div!(HalfUp(ratio), amt, aaf);
+                        check_try_div!(HalfEven(ratio), amt, aaf);
+                    }
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, u8, try_div_u8_u8);
+    test_suite!(u8, u16, try_div_u8_u16);
+    test_suite!(u8, u32, try_div_u8_u32);
+    test_suite!(u8, u64, try_div_u8_u64);
+    test_suite!(u8, u128, try_div_u8_u128);
+
+    test_suite!(u16, u8, try_div_u16_u8);
+    test_suite!(u16, u16, try_div_u16_u16);
+    test_suite!(u16, u32, try_div_u16_u32);
+    test_suite!(u16, u64, try_div_u16_u64);
+    test_suite!(u16, u128, try_div_u16_u128);
+
+    test_suite!(u32, u8, try_div_u32_u8);
+    test_suite!(u32, u16, try_div_u32_u16);
+    test_suite!(u32, u32, try_div_u32_u32);
+    test_suite!(u32, u64, try_div_u32_u64);
+    test_suite!(u32, u128, try_div_u32_u128);
+
+    test_suite!(u64, u8, try_div_u64_u8);
+    test_suite!(u64, u16, try_div_u64_u16);
+    test_suite!(u64, u32, try_div_u64_u32);
+    test_suite!(u64, u64, try_div_u64_u64);
+    test_suite!(u64, u128, try_div_u64_u128);
+
+    test_suite!(u128, u8, try_div_u128_u8);
+    test_suite!(u128, u16, try_div_u128_u16);
+    test_suite!(u128, u32, try_div_u128_u32);
+    test_suite!(u128, u64, try_div_u128_u64);
+    test_suite!(u128, u128, try_div_u128_u128);
+
+    test_suite_nearest!(u8, u8, try_div_nearest_u8_u8);
+    test_suite_nearest!(u8, u16, try_div_nearest_u8_u16);
+    test_suite_nearest!(u8, u32, try_div_nearest_u8_u32);
+    test_suite_nearest!(u8, u64, try_div_nearest_u8_u64);
+
+    test_suite_nearest!(u16, u8, try_div_nearest_u16_u8);
+    test_suite_nearest!(u16, u16, try_div_nearest_u16_u16);
+    test_suite_nearest!(u16, u32, try_div_nearest_u16_u32);
+    test_suite_nearest!(u16, u64, try_div_nearest_u16_u64);
+
+    test_suite_nearest!(u32, u8, try_div_nearest_u32_u8);
+    test_suite_nearest!(u32, u16, try_div_nearest_u32_u16);
+    test_suite_nearest!(u32, u32, try_div_nearest_u32_u32);
+    test_suite_nearest!(u32, u64, try_div_nearest_u32_u64);
+
+    test_suite_nearest!(u64, u8, try_div_nearest_u64_u8);
+    test_suite_nearest!(u64, u16, try_div_nearest_u64_u16);
+    test_suite_nearest!(u64, u32, try_div_nearest_u64_u32);
+    test_suite_nearest!(u64, u64, try_div_nearest_u64_u64);
+}
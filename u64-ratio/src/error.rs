@@ -0,0 +1,58 @@
+use core::fmt::{Display, Formatter};
+
+#[cfg(feature = "std")]
+use crate::{ParseRatioError, TryFromRatioError};
+
+/// Error returned by the `try_*` methods of this crate and of `sanctum-fee-ratio`,
+/// distinguishing between the different reasons their
+/// `Option`-returning counterparts return `None`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RatioError {
+    /// The result does not fit into the output type
+    Overflow,
+
+    /// The ratio is zero, so no input could have resulted in the nonzero output
+    ZeroRatio,
+
+    /// No input results in the given output when the ratio is applied.
+    ///
+    /// This can happen if the ratio > 1.0, e.g. `Floor(2/1).reverse(any_odd_number)`
+    Unattainable {
+        /// The input whose output is closest to the given output.
+        ///
+        /// If 2 inputs are equally close, this is the smaller one.
+        nearest: u64,
+    },
+
+    /// The fee ratio is > 1.0
+    InvalidFee,
+
+    /// The fee ratio's denominator is 0
+    ZeroDenominator,
+}
+
+impl Display for RatioError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Overflow => f.write_str("arithmetic overflow"),
+            Self::ZeroRatio => f.write_str("nonzero output from zero ratio"),
+            Self::Unattainable { nearest } => f.write_fmt(format_args!(
+                "output unattainable, nearest input is {nearest}"
+            )),
+            Self::InvalidFee => f.write_str("fee ratio > 1.0"),
+            Self::ZeroDenominator => f.write_str("fee ratio has zero denominator"),
+        }
+    }
+}
+
+/// `core::error::Error` is only stable from rustc 1.81, above our MSRV,
+/// so this is gated behind the `std` feature for now
+#[cfg(feature = "std")]
+impl std::error::Error for RatioError {}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseRatioError {}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryFromRatioError {}
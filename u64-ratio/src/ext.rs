@@ -0,0 +1,78 @@
+//! Helper macros for arithmetic on a ratio's [`crate::ArithTypes::Ext`],
+//! which is either a native unsigned int or [`crate::U256`].
+//!
+//! [`crate::U256`] arms must come first since `U256` would otherwise
+//! also match the native `$EXT:ident` arms.
+
+/// Compares `a * b` with `c * d`,
+/// with all multiplication done in the extended type `$EXT`
+macro_rules! ext_mul_cmp {
+    (U256, $a:expr, $b:expr, $c:expr, $d:expr) => {
+        U256::widening_mul($a as u128, $b as u128)
+            .const_cmp(&U256::widening_mul($c as u128, $d as u128))
+    };
+    ($EXT:ident, $a:expr, $b:expr, $c:expr, $d:expr) => {{
+        let lhs = ($a as $EXT) * ($b as $EXT);
+        let rhs = ($c as $EXT) * ($d as $EXT);
+        if lhs == rhs {
+            Ordering::Equal
+        } else if lhs < rhs {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        }
+    }};
+}
+
+/// `a * b` in the extended type `$EXT`.
+/// `a` and `b` must be no larger than `$EXT`'s `Max`
+macro_rules! ext_mul {
+    (U256, $a:expr, $b:expr) => {
+        U256::widening_mul($a as u128, $b as u128)
+    };
+    ($EXT:ident, $a:expr, $b:expr) => {
+        ($a as $EXT) * ($b as $EXT)
+    };
+}
+
+/// `(x / m, x % m)` for `x: $EXT`.
+///
+/// The remainder may be cast to the ratio's `Max` type since it is `< m`
+macro_rules! ext_div_rem {
+    (U256, $x:expr, $m:expr) => {
+        $x.div_rem_u128($m as u128)
+    };
+    ($EXT:ident, $x:expr, $m:expr) => {{
+        let x = $x;
+        let m = $m as $EXT;
+        (x / m, x % m)
+    }};
+}
+
+/// `true` if `x: $EXT` is 0
+macro_rules! ext_is_zero {
+    (U256, $x:expr) => {
+        $x.is_zero()
+    };
+    ($EXT:ident, $x:expr) => {
+        $x == 0
+    };
+}
+
+/// Converts `x: $EXT` to `$T`, returning `None` if it does not fit
+macro_rules! ext_to_checked {
+    (U256, $x:expr, $T:ty) => {
+        match $x.to_u128_checked() {
+            Some(x) if x <= <$T>::MAX as u128 => Some(x as $T),
+            _ => None,
+        }
+    };
+    ($EXT:ident, $x:expr, $T:ty) => {{
+        let x = $x;
+        if x > <$T>::MAX as $EXT {
+            None
+        } else {
+            Some(x as $T)
+        }
+    }};
+}
@@ -0,0 +1,124 @@
+use core::mem::size_of;
+
+use crate::{
+    utils::{concat_bytes, split_bytes},
+    Ratio,
+};
+
+macro_rules! impl_le_bytes {
+    ($N:ty, $D:ty) => {
+        impl Ratio<$N, $D> {
+            /// Length of the output of [`Self::to_le_bytes`]
+            pub const LE_BYTES_LEN: usize = size_of::<$N>() + size_of::<$D>();
+
+            /// `n` followed by `d`, both in little-endian byte order
+            #[inline]
+            pub const fn to_le_bytes(&self) -> [u8; Self::LE_BYTES_LEN] {
+                concat_bytes(self.n.to_le_bytes(), self.d.to_le_bytes())
+            }
+
+            /// Inverse of [`Self::to_le_bytes`]
+            #[inline]
+            pub const fn from_le_bytes(bytes: [u8; Self::LE_BYTES_LEN]) -> Self {
+                let (n, d) = split_bytes(bytes);
+                Self {
+                    n: <$N>::from_le_bytes(n),
+                    d: <$D>::from_le_bytes(d),
+                }
+            }
+        }
+    };
+}
+
+impl_le_bytes!(u8, u8);
+impl_le_bytes!(u8, u16);
+impl_le_bytes!(u8, u32);
+impl_le_bytes!(u8, u64);
+impl_le_bytes!(u8, u128);
+
+impl_le_bytes!(u16, u8);
+impl_le_bytes!(u16, u16);
+impl_le_bytes!(u16, u32);
+impl_le_bytes!(u16, u64);
+impl_le_bytes!(u16, u128);
+
+impl_le_bytes!(u32, u8);
+impl_le_bytes!(u32, u16);
+impl_le_bytes!(u32, u32);
+impl_le_bytes!(u32, u64);
+impl_le_bytes!(u32, u128);
+
+impl_le_bytes!(u64, u8);
+impl_le_bytes!(u64, u16);
+impl_le_bytes!(u64, u32);
+impl_le_bytes!(u64, u64);
+impl_le_bytes!(u64, u128);
+
+impl_le_bytes!(u128, u8);
+impl_le_bytes!(u128, u16);
+impl_le_bytes!(u128, u32);
+impl_le_bytes!(u128, u64);
+impl_le_bytes!(u128, u128);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    macro_rules! test_suite {
+        ($N:ty, $D:ty, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(n: $N, d: $D) {
+                    type R = Ratio<$N, $D>;
+
+                    let r = R::new(n, d);
+                    let bytes = r.to_le_bytes();
+                    prop_assert_eq!(
+                        bytes.as_slice(),
+                        [n.to_le_bytes().as_slice(), d.to_le_bytes().as_slice()].concat()
+                    );
+                    let de = R::from_le_bytes(bytes);
+                    prop_assert_eq!((de.n, de.d), (n, d));
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, u8, le_bytes_u8_u8);
+    test_suite!(u8, u16, le_bytes_u8_u16);
+    test_suite!(u8, u32, le_bytes_u8_u32);
+    test_suite!(u8, u64, le_bytes_u8_u64);
+    test_suite!(u8, u128, le_bytes_u8_u128);
+    test_suite!(u16, u8, le_bytes_u16_u8);
+    test_suite!(u16, u16, le_bytes_u16_u16);
+    test_suite!(u16, u32, le_bytes_u16_u32);
+    test_suite!(u16, u64, le_bytes_u16_u64);
+    test_suite!(u16, u128, le_bytes_u16_u128);
+    test_suite!(u32, u8, le_bytes_u32_u8);
+    test_suite!(u32, u16, le_bytes_u32_u16);
+    test_suite!(u32, u32, le_bytes_u32_u32);
+    test_suite!(u32, u64, le_bytes_u32_u64);
+    test_suite!(u32, u128, le_bytes_u32_u128);
+    test_suite!(u64, u8, le_bytes_u64_u8);
+    test_suite!(u64, u16, le_bytes_u64_u16);
+    test_suite!(u64, u32, le_bytes_u64_u32);
+    test_suite!(u64, u64, le_bytes_u64_u64);
+    test_suite!(u64, u128, le_bytes_u64_u128);
+    test_suite!(u128, u8, le_bytes_u128_u8);
+    test_suite!(u128, u16, le_bytes_u128_u16);
+    test_suite!(u128, u32, le_bytes_u128_u32);
+    test_suite!(u128, u64, le_bytes_u128_u64);
+    test_suite!(u128, u128, le_bytes_u128_u128);
+
+    #[test]
+    fn le_bytes_const() {
+        const R: Ratio<u8, u16> = Ratio::new(1, 400);
+        const BYTES: [u8; 3] = R.to_le_bytes();
+        const DE: Ratio<u8, u16> = Ratio::<u8, u16>::from_le_bytes(BYTES);
+
+        assert_eq!(BYTES, [1, 144, 1]);
+        assert_eq!((DE.n, DE.d), (1, 400));
+    }
+}
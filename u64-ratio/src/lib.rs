@@ -1,23 +1,67 @@
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 #![doc = include_str!("../README.md")]
 
 use core::cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd};
 use core::fmt::{Display, Formatter};
 use core::hash::{Hash, Hasher};
 
+#[macro_use]
+mod ext;
+
+mod allocation;
+mod any;
+mod approx;
+mod arith;
+mod const_ratio;
+mod convert;
+mod cross_cmp;
+mod decimal;
 mod div;
+mod error;
+mod le_bytes;
+mod reduced;
+
+#[doc(hidden)]
+pub mod macros;
+
+mod u256;
+mod validated;
 
 pub(crate) mod utils;
 
+#[cfg(feature = "bytemuck")]
+mod pod;
+
+#[cfg(feature = "serde")]
+pub mod serde_str;
+
+pub use allocation::*;
+pub use any::*;
+pub use const_ratio::*;
+pub use convert::*;
+pub use decimal::*;
 pub use div::*;
+pub use error::*;
+pub use reduced::*;
+pub use u256::*;
+pub use validated::*;
 
 /// A ratio that is applied to a u64 token amount.
 ///
 /// A zero denominator ratio (`self.d == 0`) is treated as zero.
 ///
-/// Must use with [`crate::Ceil`] or [`crate::Floor`]
+/// Must use with [`crate::Ceil`], [`crate::Floor`],
+/// [`crate::HalfUp`] or [`crate::HalfEven`]
 /// for application on [`u64`]s
+///
+/// `repr(C)`: laid out as `n` followed by `d`, with no padding if `N` and `D` are the same type.
 #[derive(Debug, Copy, Clone)]
+#[repr(C)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ratio<N, D> {
     /// Numerator
     pub n: N,
@@ -28,17 +72,36 @@ pub struct Ratio<N, D> {
 
 macro_rules! impl_gcd {
     ($f:ident, $T:ty) => {
-        /// Takes 1 fewer iteration if a > b compared to b > a.
+        /// Binary (Stein's) gcd, which avoids the `%` operator
+        /// that is expensive for wide integers on some targets e.g. BPF.
         ///
         /// Never returns 0 unless both args are 0
         #[inline]
         const fn $f(mut a: $T, mut b: $T) -> $T {
-            while b > 0 {
-                let r = a % b;
-                a = b;
-                b = r;
+            if a == 0 {
+                return b;
+            }
+            if b == 0 {
+                return a;
+            }
+            // largest power of 2 dividing both
+            let shift = (a | b).trailing_zeros();
+            a >>= a.trailing_zeros();
+            // invariant: a is odd
+            loop {
+                b >>= b.trailing_zeros();
+                // invariant: a and b are both odd
+                if a > b {
+                    let t = a;
+                    a = b;
+                    b = t;
+                }
+                // unchecked-arith: a <= b
+                b -= a;
+                if b == 0 {
+                    return a << shift;
+                }
             }
-            a
         }
     };
 }
@@ -47,6 +110,7 @@ impl_gcd!(gcd_u8, u8);
 impl_gcd!(gcd_u16, u16);
 impl_gcd!(gcd_u32, u32);
 impl_gcd!(gcd_u64, u64);
+impl_gcd!(gcd_u128, u128);
 
 /// Associated types of a [`Ratio`] for use in arithmetic operations
 ///
@@ -60,10 +124,28 @@ pub trait ArithTypes {
 
     /// The unsigned type that `Ratio::N` and `Ratio::D` must be
     /// bit-extended (cast) into to avoid overflows on multiplication
-    /// of `Ratio::N` and `Ratio::D`
+    /// of `Ratio::N` and `Ratio::D`.
+    ///
+    /// This is [`U256`] if either of them is `u128`
     type Ext;
 }
 
+/// Conversion of a [`Ratio`] to and from its numerator and denominator as [`u128`]s,
+/// for use in operations that are generic over the `Ratio<N, D>` type combination
+pub trait U128Parts: Sized {
+    /// `N::MAX`
+    const N_MAX: u128;
+
+    /// `D::MAX`
+    const D_MAX: u128;
+
+    /// `(n, d)`
+    fn to_u128_parts(&self) -> (u128, u128);
+
+    /// Returns `None` if `n > Self::N_MAX` or `d > Self::D_MAX`
+    fn from_u128_parts(n: u128, d: u128) -> Option<Self>;
+}
+
 impl<N, D> Ratio<N, D> {
     /// Convenience constructor for better compatibility with type aliases
     #[inline]
@@ -73,13 +155,35 @@ impl<N, D> Ratio<N, D> {
 }
 
 macro_rules! impl_ratio {
-    ($N:ty, $D:ty, [$gcd:expr, $MIN: ty, $MAX:ty, $EXT:ty]) => {
+    ($N:ty, $D:ty, [$gcd:expr, $MIN: ty, $MAX:ty, $EXT:ident]) => {
         impl ArithTypes for Ratio<$N, $D> {
             type Min = $MIN;
             type Max = $MAX;
             type Ext = $EXT;
         }
 
+        impl U128Parts for Ratio<$N, $D> {
+            const N_MAX: u128 = <$N>::MAX as u128;
+            const D_MAX: u128 = <$D>::MAX as u128;
+
+            #[inline]
+            fn to_u128_parts(&self) -> (u128, u128) {
+                (self.n as u128, self.d as u128)
+            }
+
+            #[inline]
+            fn from_u128_parts(n: u128, d: u128) -> Option<Self> {
+                if n > Self::N_MAX || d > Self::D_MAX {
+                    None
+                } else {
+                    Some(Self {
+                        n: n as $N,
+                        d: d as $D,
+                    })
+                }
+            }
+        }
+
         impl Ratio<$N, $D> {
             pub const ZERO: Self = Self { n: 0, d: 0 };
             pub const ONE: Self = Self { n: 1, d: 1 };
@@ -123,8 +227,6 @@ macro_rules! impl_ratio {
             /// ```
             #[inline]
             pub const fn const_cmp(&self, other: &Self) -> Ordering {
-                type Ext = <Ratio<$N, $D> as ArithTypes>::Ext;
-
                 match (self.is_zero(), other.is_zero()) {
                     (true, true) => return Ordering::Equal,
                     (true, false) => return Ordering::Less,
@@ -132,15 +234,7 @@ macro_rules! impl_ratio {
                     (false, false) => (),
                 };
 
-                let lhs = (self.n as Ext) * (other.d as Ext);
-                let rhs = (other.n as Ext) * (self.d as Ext);
-                if lhs == rhs {
-                    Ordering::Equal
-                } else if lhs < rhs {
-                    Ordering::Less
-                } else {
-                    Ordering::Greater
-                }
+                ext_mul_cmp!($EXT, self.n, other.d, other.n, self.d)
             }
 
             /// Returns the fraction's lowest form.
@@ -157,8 +251,7 @@ macro_rules! impl_ratio {
                 }
                 let n = self.n as Max;
                 let d = self.d as Max;
-                // usually the denominator is larger, so put it first
-                let gcd = $gcd(d, n);
+                let gcd = $gcd(n, d);
                 // division-safety: gcd is never 0 due to early return above
                 Ratio {
                     n: n / gcd,
@@ -248,6 +341,17 @@ impl_ratio!(u64, u16, [gcd_u64, u16, u64, u128]);
 impl_ratio!(u64, u32, [gcd_u64, u32, u64, u128]);
 impl_ratio!(u64, u64, [gcd_u64, u64, u64, u128]);
 
+impl_ratio!(u8, u128, [gcd_u128, u8, u128, U256]);
+impl_ratio!(u16, u128, [gcd_u128, u16, u128, U256]);
+impl_ratio!(u32, u128, [gcd_u128, u32, u128, U256]);
+impl_ratio!(u64, u128, [gcd_u128, u64, u128, U256]);
+
+impl_ratio!(u128, u8, [gcd_u128, u8, u128, U256]);
+impl_ratio!(u128, u16, [gcd_u128, u16, u128, U256]);
+impl_ratio!(u128, u32, [gcd_u128, u32, u128, U256]);
+impl_ratio!(u128, u64, [gcd_u128, u64, u128, U256]);
+impl_ratio!(u128, u128, [gcd_u128, u128, u128, U256]);
+
 #[cfg(test)]
 mod tests {
     use proptest::prelude::*;
@@ -376,10 +480,35 @@ mod tests {
         };
     }
 
+    macro_rules! gcd_eq_euclid {
+        ($T:ty, $gcd:ident, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(a: $T, b: $T) {
+                    let (mut x, mut y) = (a, b);
+                    while y > 0 {
+                        let r = x % y;
+                        x = y;
+                        y = r;
+                    }
+                    prop_assert_eq!($gcd(a, b), x);
+                    prop_assert_eq!($gcd(b, a), x);
+                }
+            }
+        };
+    }
+
+    gcd_eq_euclid!(u8, gcd_u8, gcd_eq_euclid_u8);
+    gcd_eq_euclid!(u16, gcd_u16, gcd_eq_euclid_u16);
+    gcd_eq_euclid!(u32, gcd_u32, gcd_eq_euclid_u32);
+    gcd_eq_euclid!(u64, gcd_u64, gcd_eq_euclid_u64);
+    gcd_eq_euclid!(u128, gcd_u128, gcd_eq_euclid_u128);
+
     ord!(u8, ord_u8);
     ord!(u16, ord_u16);
     ord!(u32, ord_u32);
     ord!(u64, ord_u64);
+    ord!(u128, ord_u128);
 
     zero_eq!(u8, u8, zero_eq_u8_u8);
     zero_eq!(u8, u16, zero_eq_u8_u16);
@@ -401,6 +530,17 @@ mod tests {
     zero_eq!(u64, u32, zero_eq_u64_u32);
     zero_eq!(u64, u64, zero_eq_u64_u64);
 
+    zero_eq!(u8, u128, zero_eq_u8_u128);
+    zero_eq!(u16, u128, zero_eq_u16_u128);
+    zero_eq!(u32, u128, zero_eq_u32_u128);
+    zero_eq!(u64, u128, zero_eq_u64_u128);
+
+    zero_eq!(u128, u8, zero_eq_u128_u8);
+    zero_eq!(u128, u16, zero_eq_u128_u16);
+    zero_eq!(u128, u32, zero_eq_u128_u32);
+    zero_eq!(u128, u64, zero_eq_u128_u64);
+    zero_eq!(u128, u128, zero_eq_u128_u128);
+
     lowest_form_ord_iff_ord!(u8, u8, lowest_form_iff_u8_u8);
     lowest_form_ord_iff_ord!(u8, u16, lowest_form_iff_u8_u16);
     lowest_form_ord_iff_ord!(u8, u32, lowest_form_iff_u8_u32);
@@ -421,6 +561,17 @@ mod tests {
     lowest_form_ord_iff_ord!(u64, u32, lowest_form_iff_u64_u32);
     lowest_form_ord_iff_ord!(u64, u64, lowest_form_iff_u64_u64);
 
+    lowest_form_ord_iff_ord!(u8, u128, lowest_form_iff_u8_u128);
+    lowest_form_ord_iff_ord!(u16, u128, lowest_form_iff_u16_u128);
+    lowest_form_ord_iff_ord!(u32, u128, lowest_form_iff_u32_u128);
+    lowest_form_ord_iff_ord!(u64, u128, lowest_form_iff_u64_u128);
+
+    lowest_form_ord_iff_ord!(u128, u8, lowest_form_iff_u128_u8);
+    lowest_form_ord_iff_ord!(u128, u16, lowest_form_iff_u128_u16);
+    lowest_form_ord_iff_ord!(u128, u32, lowest_form_iff_u128_u32);
+    lowest_form_ord_iff_ord!(u128, u64, lowest_form_iff_u128_u64);
+    lowest_form_ord_iff_ord!(u128, u128, lowest_form_iff_u128_u128);
+
     eq_implies_hash_eq!(u8, u8, eq_hash_eq_u8_u8);
     eq_implies_hash_eq!(u8, u16, eq_hash_eq_u8_u16);
     eq_implies_hash_eq!(u8, u32, eq_hash_eq_u8_u32);
@@ -440,4 +591,32 @@ mod tests {
     eq_implies_hash_eq!(u64, u16, eq_hash_eq_u64_u16);
     eq_implies_hash_eq!(u64, u32, eq_hash_eq_u64_u32);
     eq_implies_hash_eq!(u64, u64, eq_hash_eq_u64_u64);
+
+    eq_implies_hash_eq!(u8, u128, eq_hash_eq_u8_u128);
+    eq_implies_hash_eq!(u16, u128, eq_hash_eq_u16_u128);
+    eq_implies_hash_eq!(u32, u128, eq_hash_eq_u32_u128);
+    eq_implies_hash_eq!(u64, u128, eq_hash_eq_u64_u128);
+
+    eq_implies_hash_eq!(u128, u8, eq_hash_eq_u128_u8);
+    eq_implies_hash_eq!(u128, u16, eq_hash_eq_u128_u16);
+    eq_implies_hash_eq!(u128, u32, eq_hash_eq_u128_u32);
+    eq_implies_hash_eq!(u128, u64, eq_hash_eq_u128_u64);
+    eq_implies_hash_eq!(u128, u128, eq_hash_eq_u128_u128);
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn borsh_layout() {
+        let r: Ratio<u8, u16> = Ratio::new(1, 400);
+        let bytes = borsh::to_vec(&r).unwrap();
+        assert_eq!(bytes, [1, 144, 1]);
+        assert_eq!(borsh::to_vec(&Floor(r)).unwrap(), bytes);
+        assert_eq!(borsh::to_vec(&Ceil(r)).unwrap(), bytes);
+
+        let de: Ratio<u8, u16> = borsh::from_slice(&bytes).unwrap();
+        assert_eq!((de.n, de.d), (1, 400));
+        let Floor(de) = borsh::from_slice::<Floor<Ratio<u8, u16>>>(&bytes).unwrap();
+        assert_eq!((de.n, de.d), (1, 400));
+        let Ceil(de) = borsh::from_slice::<Ceil<Ratio<u8, u16>>>(&bytes).unwrap();
+        assert_eq!((de.n, de.d), (1, 400));
+    }
 }
@@ -0,0 +1,214 @@
+//! Support for the [`crate::ratio!`] macro. Not public API.
+
+use crate::gcd_u128;
+
+/// Maps a byte width to the unsigned integer type of that width
+pub struct Width<const BYTES: u8>;
+
+pub trait Uint {
+    type T;
+}
+
+impl Uint for Width<1> {
+    type T = u8;
+}
+
+impl Uint for Width<2> {
+    type T = u16;
+}
+
+impl Uint for Width<4> {
+    type T = u32;
+}
+
+impl Uint for Width<8> {
+    type T = u64;
+}
+
+impl Uint for Width<16> {
+    type T = u128;
+}
+
+/// # Returns
+///
+/// The byte width of the narrowest unsigned integer type that can hold `x`
+#[inline]
+pub const fn width(x: u128) -> u8 {
+    if x <= u8::MAX as u128 {
+        1
+    } else if x <= u16::MAX as u128 {
+        2
+    } else if x <= u32::MAX as u128 {
+        4
+    } else if x <= u64::MAX as u128 {
+        8
+    } else {
+        16
+    }
+}
+
+/// # Panics
+///
+/// If `d == 0`, which, when evaluated in a const, results in a compile error
+#[inline]
+pub const fn checked_parts(n: u128, d: u128) -> (u128, u128) {
+    if d == 0 {
+        panic!("ratio denominator must be nonzero");
+    }
+    (n, d)
+}
+
+/// # Returns
+///
+/// The stringified decimal literal `s` divided by `10^scale`,
+/// as a `(numerator, denominator)` pair in its lowest form.
+///
+/// # Panics
+///
+/// If `s` is not a decimal literal with only digits, `_`s and at most 1 decimal point
+/// or the result overflows `u128`, which, when evaluated in a const, results in a compile error
+pub const fn decimal_parts(s: &str, mut scale: u32) -> (u128, u128) {
+    let s = s.as_bytes();
+    let mut n: u128 = 0;
+    let mut seen_digit = false;
+    let mut seen_point = false;
+    let mut i = 0;
+    while i < s.len() {
+        let b = s[i];
+        i += 1;
+        match b {
+            b'_' => continue,
+            b'.' if !seen_point => {
+                seen_point = true;
+                continue;
+            }
+            b'0'..=b'9' => (),
+            _ => panic!("unsupported ratio literal: expected a decimal number"),
+        }
+        seen_digit = true;
+        n = match n.checked_mul(10) {
+            Some(x) => x,
+            None => panic!("ratio literal overflows u128"),
+        };
+        n = match n.checked_add((b - b'0') as u128) {
+            Some(x) => x,
+            None => panic!("ratio literal overflows u128"),
+        };
+        if seen_point {
+            scale += 1;
+        }
+    }
+    if !seen_digit {
+        panic!("unsupported ratio literal: expected a decimal number");
+    }
+    let d = match 10u128.checked_pow(scale) {
+        Some(d) => d,
+        None => panic!("ratio literal has too many decimal places"),
+    };
+    if n == 0 {
+        return (0, 1);
+    }
+    let gcd = gcd_u128(n, d);
+    (n / gcd, d / gcd)
+}
+
+/// Expands to a const `Ratio` of the narrowest types holding `$parts`' `(n, d)`
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ratio_from_parts {
+    ($parts:expr) => {{
+        const PARTS: (u128, u128) = $parts;
+        const RATIO: $crate::Ratio<
+            <$crate::macros::Width<{ $crate::macros::width(PARTS.0) }> as $crate::macros::Uint>::T,
+            <$crate::macros::Width<{ $crate::macros::width(PARTS.1) }> as $crate::macros::Uint>::T,
+        > = $crate::Ratio {
+            n: PARTS.0 as _,
+            d: PARTS.1 as _,
+        };
+        RATIO
+    }};
+}
+
+/// Expands to the `(n, d)` of the `ratio!` input as `u128`s
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ratio_parts {
+    ($n:literal / $d:literal) => {
+        $crate::macros::checked_parts($n, $d)
+    };
+    ($x:literal %) => {
+        $crate::macros::decimal_parts(stringify!($x), 2)
+    };
+    ($x:literal bps) => {
+        $crate::macros::decimal_parts(stringify!($x), 4)
+    };
+    ($x:literal) => {
+        $crate::macros::decimal_parts(stringify!($x), 0)
+    };
+}
+
+/// Creates a [`Ratio`](crate::Ratio) in const context,
+/// with the narrowest numerator and denominator types that can hold it.
+///
+/// Accepted forms:
+/// - `ratio!(1 / 400)`: numerator and denominator as-is
+/// - `ratio!(0.0025)`: decimal, in its lowest form
+/// - `ratio!(0.25 %)`: percent, in its lowest form
+/// - `ratio!(25 bps)`: basis points, in its lowest form
+///
+/// Fails to compile if the denominator is zero.
+///
+/// ```rust
+/// use sanctum_u64_ratio::{ratio, Ratio};
+///
+/// const R: Ratio<u8, u16> = ratio!(25 bps);
+///
+/// assert_eq!(R, Ratio { n: 1, d: 400 });
+/// assert_eq!(ratio!(0.25 %), R);
+/// assert_eq!(ratio!(1 / 400), R);
+/// ```
+///
+/// ```compile_fail
+/// use sanctum_u64_ratio::ratio;
+///
+/// let zero_denom = ratio!(1 / 0);
+/// ```
+#[macro_export]
+macro_rules! ratio {
+    ($($t:tt)+) => {
+        $crate::__ratio_from_parts!($crate::__ratio_parts!($($t)+))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Ratio;
+
+    #[test]
+    fn ratio_macro_narrowest() {
+        const A: Ratio<u8, u16> = ratio!(1 / 400);
+        const B: Ratio<u8, u16> = ratio!(25 bps);
+        const C: Ratio<u8, u16> = ratio!(0.25 %);
+        const D: Ratio<u8, u16> = ratio!(0.0025);
+        for r in [A, B, C, D] {
+            assert_eq!(r, Ratio { n: 1, d: 400 });
+        }
+
+        let r: Ratio<u8, u8> = ratio!(2 / 4);
+        assert_eq!((r.n, r.d), (2, 4));
+        let r: Ratio<u8, u8> = ratio!(0 / 1);
+        assert!(r.is_zero());
+        let r: Ratio<u8, u8> = ratio!(0 bps);
+        assert_eq!((r.n, r.d), (0, 1));
+        let r: Ratio<u32, u64> = ratio!(100_000 / 10_000_000_000);
+        assert_eq!((r.n, r.d), (100_000, 10_000_000_000));
+        let r: Ratio<u128, u8> = ratio!(340282366920938463463374607431768211455 / 1);
+        assert_eq!((r.n, r.d), (u128::MAX, 1));
+        let r: Ratio<u8, u8> = ratio!(150 %);
+        assert_eq!((r.n, r.d), (3, 2));
+        let r: Ratio<u16, u8> = ratio!(1_000.5);
+        assert_eq!((r.n, r.d), (2001, 2));
+        let r: Ratio<u8, u32> = ratio!(0.1 bps);
+        assert_eq!((r.n, r.d), (1, 100_000));
+    }
+}
@@ -0,0 +1,112 @@
+//! [`bytemuck`] impls for zero-copy (de)serialization.
+//!
+//! All [`Ratio`]s are [`Zeroable`], but only those whose `N` and `D` are the same type
+//! are [`Pod`], since [`Ratio`] is `repr(C)` and would otherwise contain padding bytes.
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::{Ceil, Floor, Ratio};
+
+macro_rules! impl_zeroable {
+    ($N:ty, $D:ty) => {
+        // Safety: all-zeroes is a valid bit pattern for both fields
+        unsafe impl Zeroable for Ratio<$N, $D> {}
+        // Safety: repr(transparent) over Ratio
+        unsafe impl Zeroable for Floor<Ratio<$N, $D>> {}
+        // Safety: repr(transparent) over Ratio
+        unsafe impl Zeroable for Ceil<Ratio<$N, $D>> {}
+    };
+}
+
+impl_zeroable!(u8, u8);
+impl_zeroable!(u8, u16);
+impl_zeroable!(u8, u32);
+impl_zeroable!(u8, u64);
+impl_zeroable!(u8, u128);
+
+impl_zeroable!(u16, u8);
+impl_zeroable!(u16, u16);
+impl_zeroable!(u16, u32);
+impl_zeroable!(u16, u64);
+impl_zeroable!(u16, u128);
+
+impl_zeroable!(u32, u8);
+impl_zeroable!(u32, u16);
+impl_zeroable!(u32, u32);
+impl_zeroable!(u32, u64);
+impl_zeroable!(u32, u128);
+
+impl_zeroable!(u64, u8);
+impl_zeroable!(u64, u16);
+impl_zeroable!(u64, u32);
+impl_zeroable!(u64, u64);
+impl_zeroable!(u64, u128);
+
+impl_zeroable!(u128, u8);
+impl_zeroable!(u128, u16);
+impl_zeroable!(u128, u32);
+impl_zeroable!(u128, u64);
+impl_zeroable!(u128, u128);
+
+macro_rules! impl_pod {
+    ($T:ty) => {
+        // Safety: repr(C) with 2 fields of the same type, so there is no padding,
+        // and all bit patterns of both fields are valid
+        unsafe impl Pod for Ratio<$T, $T> {}
+        // Safety: repr(transparent) over Ratio
+        unsafe impl Pod for Floor<Ratio<$T, $T>> {}
+        // Safety: repr(transparent) over Ratio
+        unsafe impl Pod for Ceil<Ratio<$T, $T>> {}
+    };
+}
+
+impl_pod!(u8);
+impl_pod!(u16);
+impl_pod!(u32);
+impl_pod!(u64);
+impl_pod!(u128);
+
+#[cfg(test)]
+mod tests {
+    use core::mem::size_of;
+
+    use super::*;
+
+    macro_rules! test_pod {
+        ($T:ty, $test:ident) => {
+            #[test]
+            fn $test() {
+                type R = Ratio<$T, $T>;
+
+                assert_eq!(size_of::<R>(), 2 * size_of::<$T>());
+                assert_eq!(size_of::<Floor<R>>(), size_of::<R>());
+                assert_eq!(size_of::<Ceil<R>>(), size_of::<R>());
+
+                let r = R::new(1, <$T>::MAX);
+                let bytes = bytemuck::bytes_of(&r);
+                assert_eq!(
+                    bytes,
+                    [1 as $T, <$T>::MAX]
+                        .map(<$T>::to_ne_bytes)
+                        .concat()
+                        .as_slice()
+                );
+                let cast: &R = bytemuck::from_bytes(bytes);
+                assert_eq!((cast.n, cast.d), (1, <$T>::MAX));
+                let Floor(cast) = bytemuck::pod_read_unaligned::<Floor<R>>(bytes);
+                assert_eq!((cast.n, cast.d), (1, <$T>::MAX));
+                let Ceil(cast) = bytemuck::pod_read_unaligned::<Ceil<R>>(bytes);
+                assert_eq!((cast.n, cast.d), (1, <$T>::MAX));
+
+                let zero: R = Zeroable::zeroed();
+                assert!(zero.is_zero());
+            }
+        };
+    }
+
+    test_pod!(u8, pod_u8);
+    test_pod!(u16, pod_u16);
+    test_pod!(u32, pod_u32);
+    test_pod!(u64, pod_u64);
+    test_pod!(u128, pod_u128);
+}
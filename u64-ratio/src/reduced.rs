@@ -0,0 +1,280 @@
+use core::{
+    cmp::Ordering,
+    fmt::{Display, Formatter},
+    hash::{Hash, Hasher},
+};
+
+use crate::{ArithTypes, Ratio};
+
+/// A [`Ratio`] that is guaranteed to be in its lowest form.
+///
+/// All zero ratios are represented by the single canonical zero `0/0`,
+/// so two `Reduced`s represent the same value iff their numerators and denominators are equal.
+/// This makes [`PartialEq`] and [`Hash`] simple field-wise operations
+/// instead of a cross-multiplication and a gcd computation respectively,
+/// which is useful for e.g. using ratios as `HashMap` keys.
+///
+/// ```rust
+/// use sanctum_u64_ratio::{Ratio, Reduced};
+///
+/// type R = Ratio<u8, u16>;
+///
+/// let r = Reduced::<R>::new(R::new(2, 800));
+/// assert_eq!((r.as_inner_ref().n, r.as_inner_ref().d), (1, 400));
+///
+/// // every zero ratio is reduced to 0/0
+/// let z = Reduced::<R>::new(R::new(0, 5));
+/// assert_eq!((z.as_inner_ref().n, z.as_inner_ref().d), (0, 0));
+/// assert_eq!(z, Reduced::<R>::ZERO);
+/// ```
+#[derive(Debug, Copy, Clone)]
+#[repr(transparent)]
+pub struct Reduced<R>(R);
+
+impl<R> Reduced<R> {
+    #[inline]
+    pub const fn as_inner_ref(&self) -> &R {
+        &self.0
+    }
+}
+
+impl<R: Copy> Reduced<R> {
+    #[inline]
+    pub const fn to_inner(self) -> R {
+        self.0
+    }
+}
+
+impl<R> AsRef<R> for Reduced<R> {
+    #[inline]
+    fn as_ref(&self) -> &R {
+        self.as_inner_ref()
+    }
+}
+
+macro_rules! impl_reduced {
+    ($N:ty, $D:ty) => {
+        impl Reduced<Ratio<$N, $D>> {
+            pub const ZERO: Self = Self(Ratio::<$N, $D>::ZERO);
+            pub const ONE: Self = Self(Ratio::<$N, $D>::ONE);
+
+            /// Reduces `ratio` to its lowest form.
+            ///
+            /// Zero ratios are all reduced to `0/0`
+            #[inline]
+            pub const fn new(ratio: Ratio<$N, $D>) -> Self {
+                Self(ratio.lowest_form_self())
+            }
+
+            /// # Safety
+            /// - `ratio` must be in its lowest form i.e. `gcd(n, d) == 1`,
+            ///   or be exactly `0/0` if it represents zero
+            #[inline]
+            pub const unsafe fn new_unchecked(ratio: Ratio<$N, $D>) -> Self {
+                Self(ratio)
+            }
+
+            /// Field-wise comparison of numerator and denominator
+            #[inline]
+            pub const fn const_eq(&self, other: &Self) -> bool {
+                self.0.n == other.0.n && self.0.d == other.0.d
+            }
+
+            /// See [`Ratio::const_cmp`]
+            #[inline]
+            pub const fn const_cmp(&self, other: &Self) -> Ordering {
+                self.0.const_cmp(&other.0)
+            }
+        }
+
+        impl Default for Reduced<Ratio<$N, $D>> {
+            #[inline]
+            fn default() -> Self {
+                Self::ZERO
+            }
+        }
+
+        impl From<Ratio<$N, $D>> for Reduced<Ratio<$N, $D>> {
+            #[inline]
+            fn from(ratio: Ratio<$N, $D>) -> Self {
+                Self::new(ratio)
+            }
+        }
+
+        impl From<Reduced<Ratio<$N, $D>>> for Ratio<$N, $D> {
+            #[inline]
+            fn from(r: Reduced<Ratio<$N, $D>>) -> Self {
+                r.to_inner()
+            }
+        }
+
+        /// Uses [`Self::const_eq`], see its docs for more info
+        impl PartialEq for Reduced<Ratio<$N, $D>> {
+            #[inline]
+            fn eq(&self, rhs: &Self) -> bool {
+                self.const_eq(rhs)
+            }
+        }
+
+        /// Uses [`Self::const_eq`], see its docs for more info
+        impl Eq for Reduced<Ratio<$N, $D>> {}
+
+        /// Uses [`Self::const_cmp`], see its docs for more info
+        impl PartialOrd for Reduced<Ratio<$N, $D>> {
+            #[inline]
+            fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+                Some(self.cmp(rhs))
+            }
+        }
+
+        /// Uses [`Self::const_cmp`], see its docs for more info
+        impl Ord for Reduced<Ratio<$N, $D>> {
+            #[inline]
+            fn cmp(&self, rhs: &Self) -> Ordering {
+                self.const_cmp(rhs)
+            }
+        }
+
+        /// Hashes the same as the inner [`Ratio`] since it is already in its lowest form
+        impl Hash for Reduced<Ratio<$N, $D>> {
+            #[inline]
+            fn hash<H>(&self, state: &mut H)
+            where
+                H: Hasher,
+            {
+                type Max = <Ratio<$N, $D> as ArithTypes>::Max;
+
+                (self.0.n as Max).hash(state);
+                (self.0.d as Max).hash(state);
+            }
+        }
+
+        /// Displayed as `{numerator}/{denominator}`
+        impl Display for Reduced<Ratio<$N, $D>> {
+            #[inline]
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    };
+}
+
+impl_reduced!(u8, u8);
+impl_reduced!(u8, u16);
+impl_reduced!(u8, u32);
+impl_reduced!(u8, u64);
+
+impl_reduced!(u16, u8);
+impl_reduced!(u16, u16);
+impl_reduced!(u16, u32);
+impl_reduced!(u16, u64);
+
+impl_reduced!(u32, u8);
+impl_reduced!(u32, u16);
+impl_reduced!(u32, u32);
+impl_reduced!(u32, u64);
+
+impl_reduced!(u64, u8);
+impl_reduced!(u64, u16);
+impl_reduced!(u64, u32);
+impl_reduced!(u64, u64);
+
+impl_reduced!(u8, u128);
+impl_reduced!(u16, u128);
+impl_reduced!(u32, u128);
+impl_reduced!(u64, u128);
+
+impl_reduced!(u128, u8);
+impl_reduced!(u128, u16);
+impl_reduced!(u128, u32);
+impl_reduced!(u128, u64);
+impl_reduced!(u128, u128);
+
+#[cfg(test)]
+mod tests {
+    use std::hash::DefaultHasher;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn hash_of<T: Hash>(t: &T) -> u64 {
+        let mut h = DefaultHasher::new();
+        t.hash(&mut h);
+        h.finish()
+    }
+
+    macro_rules! test_suite {
+        ($N:ty, $D:ty, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(n1: $N, d1: $D, n2: $N, d2: $D, k in 1..=8u8) {
+                    type R = Ratio<$N, $D>;
+
+                    let [(r1, x1), (r2, x2)] = [(n1, d1), (n2, d2)].map(|(n, d)| {
+                        let r = R::new(n, d);
+                        (r, Reduced::<R>::new(r))
+                    });
+
+                    // same value as the original ratio
+                    prop_assert_eq!(x1.to_inner(), r1);
+                    // idempotent
+                    prop_assert_eq!(Reduced::<R>::new(x1.to_inner()).to_inner().n, x1.to_inner().n);
+                    prop_assert_eq!(Reduced::<R>::new(x1.to_inner()).to_inner().d, x1.to_inner().d);
+                    // field-wise eq iff value eq
+                    prop_assert_eq!(x1 == x2, r1 == r2);
+                    prop_assert_eq!(x1.cmp(&x2), r1.cmp(&r2));
+                    // hash consistent with both Eq and the inner ratio's Hash
+                    prop_assert_eq!(hash_of(&x1), hash_of(&r1));
+                    if x1 == x2 {
+                        prop_assert_eq!(hash_of(&x1), hash_of(&x2));
+                    }
+
+                    // scaling up yields the same Reduced
+                    let k = k as $N;
+                    if let (Some(n), Some(d)) = (n1.checked_mul(k), d1.checked_mul(k as $D)) {
+                        let scaled = Reduced::<R>::new(R::new(n, d));
+                        prop_assert_eq!(scaled, x1);
+                        prop_assert_eq!(hash_of(&scaled), hash_of(&x1));
+                    }
+
+                    if r1.is_zero() {
+                        prop_assert!(x1.const_eq(&Reduced::<R>::ZERO));
+                        prop_assert_eq!((x1.to_inner().n, x1.to_inner().d), (0, 0));
+                    }
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, u8, reduced_u8_u8);
+    test_suite!(u8, u16, reduced_u8_u16);
+    test_suite!(u8, u32, reduced_u8_u32);
+    test_suite!(u8, u64, reduced_u8_u64);
+
+    test_suite!(u16, u8, reduced_u16_u8);
+    test_suite!(u16, u16, reduced_u16_u16);
+    test_suite!(u16, u32, reduced_u16_u32);
+    test_suite!(u16, u64, reduced_u16_u64);
+
+    test_suite!(u32, u8, reduced_u32_u8);
+    test_suite!(u32, u16, reduced_u32_u16);
+    test_suite!(u32, u32, reduced_u32_u32);
+    test_suite!(u32, u64, reduced_u32_u64);
+
+    test_suite!(u64, u8, reduced_u64_u8);
+    test_suite!(u64, u16, reduced_u64_u16);
+    test_suite!(u64, u32, reduced_u64_u32);
+    test_suite!(u64, u64, reduced_u64_u64);
+
+    test_suite!(u8, u128, reduced_u8_u128);
+    test_suite!(u16, u128, reduced_u16_u128);
+    test_suite!(u32, u128, reduced_u32_u128);
+    test_suite!(u64, u128, reduced_u64_u128);
+
+    test_suite!(u128, u8, reduced_u128_u8);
+    test_suite!(u128, u16, reduced_u128_u16);
+    test_suite!(u128, u32, reduced_u128_u32);
+    test_suite!(u128, u64, reduced_u128_u64);
+    test_suite!(u128, u128, reduced_u128_u128);
+}
@@ -0,0 +1,180 @@
+//! String form (de)serialization of [`crate::Ratio`]s for use with
+//! `#[serde(with = "sanctum_u64_ratio::serde_str")]`.
+//!
+//! Ratios are serialized as `"n/d"` strings using their [`core::fmt::Display`] implementation
+//! and deserialized using their [`core::str::FromStr`] implementation, which also accepts
+//! decimal, percent and basis points strings.
+//!
+//! ```rust
+//! use sanctum_u64_ratio::Ratio;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Config {
+//!     #[serde(with = "sanctum_u64_ratio::serde_str")]
+//!     rate: Ratio<u8, u16>,
+//! }
+//!
+//! let config: Config = serde_json::from_str(r#"{ "rate": "0.25%" }"#).unwrap();
+//! assert_eq!(config, Config { rate: Ratio { n: 1, d: 400 } });
+//! assert_eq!(serde_json::to_string(&config).unwrap(), r#"{"rate":"1/400"}"#);
+//! ```
+//!
+//! Only bare [`crate::Ratio`]s are supported, since the [`core::fmt::Display`] output of
+//! wrappers such as [`crate::Floor`] cannot be parsed back.
+//!
+//! ```compile_fail
+//! use sanctum_u64_ratio::{Floor, Ratio};
+//! use serde::Serialize;
+//!
+//! #[derive(Serialize)]
+//! struct Config {
+//!     #[serde(serialize_with = "sanctum_u64_ratio::serde_str::serialize")]
+//!     rate: Floor<Ratio<u8, u16>>,
+//! }
+//! ```
+
+use core::{
+    fmt::{Display, Formatter},
+    marker::PhantomData,
+    str::FromStr,
+};
+
+use serde::{de::Visitor, Deserializer, Serializer};
+
+use crate::{ParseRatioError, Ratio};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// A [`Ratio`] type that can be (de)serialized in the string form.
+///
+/// Sealed so that only bare `Ratio`s, whose [`Display`] output can be parsed back
+/// by their [`FromStr`] implementation, can be used with this module.
+pub trait RatioStr: private::Sealed + Display + FromStr<Err = ParseRatioError> {}
+
+macro_rules! impl_ratio_str {
+    ($N:ty, $D:ty) => {
+        impl private::Sealed for Ratio<$N, $D> {}
+
+        impl RatioStr for Ratio<$N, $D> {}
+    };
+}
+
+impl_ratio_str!(u8, u8);
+impl_ratio_str!(u8, u16);
+impl_ratio_str!(u8, u32);
+impl_ratio_str!(u8, u64);
+impl_ratio_str!(u8, u128);
+
+impl_ratio_str!(u16, u8);
+impl_ratio_str!(u16, u16);
+impl_ratio_str!(u16, u32);
+impl_ratio_str!(u16, u64);
+impl_ratio_str!(u16, u128);
+
+impl_ratio_str!(u32, u8);
+impl_ratio_str!(u32, u16);
+impl_ratio_str!(u32, u32);
+impl_ratio_str!(u32, u64);
+impl_ratio_str!(u32, u128);
+
+impl_ratio_str!(u64, u8);
+impl_ratio_str!(u64, u16);
+impl_ratio_str!(u64, u32);
+impl_ratio_str!(u64, u64);
+impl_ratio_str!(u64, u128);
+
+impl_ratio_str!(u128, u8);
+impl_ratio_str!(u128, u16);
+impl_ratio_str!(u128, u32);
+impl_ratio_str!(u128, u64);
+impl_ratio_str!(u128, u128);
+
+/// Serializes `ratio` as a `"n/d"` string
+#[inline]
+pub fn serialize<T: RatioStr, S: Serializer>(ratio: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(ratio)
+}
+
+/// Deserializes a ratio from any of the string formats accepted by its [`FromStr`] implementation
+#[inline]
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: RatioStr,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(StrVisitor(PhantomData))
+}
+
+struct StrVisitor<T>(PhantomData<T>);
+
+impl<T: RatioStr> Visitor<'_> for StrVisitor<T> {
+    type Value = T;
+
+    #[inline]
+    fn expecting(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("a ratio string e.g. \"1/400\", \"0.0025\", \"0.25%\" or \"25bps\"")
+    }
+
+    #[inline]
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<T, E> {
+        v.parse().map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use crate::{Ceil, Floor, Ratio};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Config {
+        rate: Ratio<u64, u128>,
+
+        floor: Floor<Ratio<u8, u8>>,
+
+        ceil: Ceil<Ratio<u16, u32>>,
+
+        #[serde(with = "crate::serde_str")]
+        rate_str: Ratio<u128, u64>,
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let config = Config {
+            rate: Ratio::new(u64::MAX, u128::MAX),
+            floor: Floor(Ratio::new(1, 2)),
+            ceil: Ceil(Ratio::new(3, 4)),
+            rate_str: Ratio::new(5, 0),
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"rate":{{"n":{},"d":{}}},"floor":{{"n":1,"d":2}},"ceil":{{"n":3,"d":4}},"rate_str":"5/0"}}"#,
+                u64::MAX,
+                u128::MAX
+            )
+        );
+        let de: Config = serde_json::from_str(&json).unwrap();
+        assert_eq!((de.rate_str.n, de.rate_str.d), (5, 0));
+        assert_eq!(de, config);
+    }
+
+    #[test]
+    fn str_form_errors() {
+        for (json, err) in [
+            (r#""256/1""#, "ratio numerator too large"),
+            (r#""1/65537""#, "ratio cannot be represented exactly"),
+            (r#""abc""#, "invalid ratio string"),
+            (r#"{"n":1,"d":2}"#, "expected a ratio string"),
+        ] {
+            let mut de = serde_json::Deserializer::from_str(json);
+            let res: Result<Ratio<u8, u16>, _> = crate::serde_str::deserialize(&mut de);
+            assert!(res.unwrap_err().to_string().contains(err), "{json}");
+        }
+    }
+}
@@ -0,0 +1,314 @@
+use core::{
+    cmp::Ordering,
+    fmt::{Display, Formatter},
+};
+
+/// A minimal unsigned 256-bit integer.
+///
+/// This is the [`crate::ArithTypes::Ext`] of ratios with a `u128`
+/// numerator or denominator, and is used as the intermediate
+/// for `u128` amounts (e.g. [`crate::Floor::apply_u128`])
+/// since `u128 * u128` may overflow `u128`.
+///
+/// Only implements the operations required by this crate.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct U256 {
+    // field order matters for derived Ord
+    hi: u128,
+    lo: u128,
+}
+
+const U64_MASK: u128 = u64::MAX as u128;
+
+impl U256 {
+    pub const ZERO: Self = Self { hi: 0, lo: 0 };
+    pub const MAX: Self = Self {
+        hi: u128::MAX,
+        lo: u128::MAX,
+    };
+
+    #[inline]
+    pub const fn from_u128(x: u128) -> Self {
+        Self { hi: 0, lo: x }
+    }
+
+    /// The most significant 128 bits
+    #[inline]
+    pub const fn hi(&self) -> u128 {
+        self.hi
+    }
+
+    /// The least significant 128 bits
+    #[inline]
+    pub const fn lo(&self) -> u128 {
+        self.lo
+    }
+
+    /// `a * b` without overflow
+    #[inline]
+    pub const fn widening_mul(a: u128, b: u128) -> Self {
+        let (a1, a0) = (a >> 64, a & U64_MASK);
+        let (b1, b0) = (b >> 64, b & U64_MASK);
+
+        // unchecked-arith: all factors are <= u64::MAX,
+        // so none of the products overflow
+        let p00 = a0 * b0;
+        let p01 = a0 * b1;
+        let p10 = a1 * b0;
+        let p11 = a1 * b1;
+
+        // unchecked-arith: sum of 3 values <= u64::MAX
+        let mid = (p00 >> 64) + (p01 & U64_MASK) + (p10 & U64_MASK);
+        let lo = (p00 & U64_MASK) | (mid << 64);
+        // unchecked-arith: the full product is <= u256::MAX
+        let hi = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+        Self { hi, lo }
+    }
+
+    /// Returns `None` on overflow
+    #[inline]
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        let (lo, carry) = self.lo.overflowing_add(rhs.lo);
+        let hi = match self.hi.checked_add(rhs.hi) {
+            None => return None,
+            Some(h) => h,
+        };
+        let hi = match hi.checked_add(carry as u128) {
+            None => return None,
+            Some(h) => h,
+        };
+        Some(Self { hi, lo })
+    }
+
+    /// Returns `None` on underflow
+    #[inline]
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        let (lo, borrow) = self.lo.overflowing_sub(rhs.lo);
+        let hi = match self.hi.checked_sub(rhs.hi) {
+            None => return None,
+            Some(h) => h,
+        };
+        let hi = match hi.checked_sub(borrow as u128) {
+            None => return None,
+            Some(h) => h,
+        };
+        Some(Self { hi, lo })
+    }
+
+    /// Returns `None` on overflow
+    #[inline]
+    pub const fn checked_add_u128(self, rhs: u128) -> Option<Self> {
+        let (lo, carry) = self.lo.overflowing_add(rhs);
+        let hi = match self.hi.checked_add(carry as u128) {
+            None => return None,
+            Some(h) => h,
+        };
+        Some(Self { hi, lo })
+    }
+
+    /// Returns `None` on underflow
+    #[inline]
+    pub const fn checked_sub_u128(self, rhs: u128) -> Option<Self> {
+        let (lo, borrow) = self.lo.overflowing_sub(rhs);
+        let hi = match self.hi.checked_sub(borrow as u128) {
+            None => return None,
+            Some(h) => h,
+        };
+        Some(Self { hi, lo })
+    }
+
+    /// Returns `None` if `self > u128::MAX`
+    #[inline]
+    pub const fn to_u128_checked(self) -> Option<u128> {
+        if self.hi == 0 {
+            Some(self.lo)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub const fn is_zero(&self) -> bool {
+        self.hi == 0 && self.lo == 0
+    }
+
+    #[inline]
+    pub const fn const_cmp(&self, other: &Self) -> Ordering {
+        if self.hi < other.hi {
+            Ordering::Less
+        } else if self.hi > other.hi {
+            Ordering::Greater
+        } else if self.lo < other.lo {
+            Ordering::Less
+        } else if self.lo > other.lo {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    /// # Returns
+    ///
+    /// `(self / d, self % d)`
+    ///
+    /// # Panics
+    ///
+    /// If `d == 0`
+    #[inline]
+    pub const fn div_rem_u128(self, d: u128) -> (Self, u128) {
+        if self.hi == 0 {
+            return (Self::from_u128(self.lo / d), self.lo % d);
+        }
+        let q_hi = self.hi / d;
+        let r = self.hi % d;
+        let (q_lo, r) = if d <= U64_MASK {
+            div_rem_u64_limbs(r, self.lo, d)
+        } else {
+            div_rem_bitwise(r, self.lo, d)
+        };
+        (Self { hi: q_hi, lo: q_lo }, r)
+    }
+
+    /// `ceil(self / d)`
+    ///
+    /// # Panics
+    ///
+    /// If `d == 0`
+    #[inline]
+    pub const fn div_ceil_u128(self, d: u128) -> Self {
+        let (q, r) = self.div_rem_u128(d);
+        if r == 0 {
+            q
+        } else {
+            match q.checked_add_u128(1) {
+                Some(q) => q,
+                // r != 0 means d > 1, so q < u256::MAX and this is always Some
+                None => unreachable!(),
+            }
+        }
+    }
+}
+
+/// Divides `(hi * 2^128 + lo)` by `d` where `hi < d <= u64::MAX`,
+/// by dividing 64 bits of `lo` at a time.
+///
+/// # Returns
+///
+/// `(quotient, remainder)`. Quotient fits in `u128` since `hi < d`
+#[inline]
+const fn div_rem_u64_limbs(hi: u128, lo: u128, d: u128) -> (u128, u128) {
+    // unchecked-arith: r < d <= u64::MAX so (r << 64) | limb does not overflow
+    let n1 = (hi << 64) | (lo >> 64);
+    let q1 = n1 / d;
+    let r = n1 % d;
+    let n0 = (r << 64) | (lo & U64_MASK);
+    let q0 = n0 / d;
+    let r = n0 % d;
+    ((q1 << 64) | q0, r)
+}
+
+/// Divides `(hi * 2^128 + lo)` by `d` where `hi < d`,
+/// using binary long division.
+///
+/// # Returns
+///
+/// `(quotient, remainder)`. Quotient fits in `u128` since `hi < d`
+#[inline]
+const fn div_rem_bitwise(hi: u128, lo: u128, d: u128) -> (u128, u128) {
+    let mut r = hi;
+    let mut q = 0;
+    let mut i = 128;
+    while i > 0 {
+        i -= 1;
+        // if the msb is set, r * 2 >= 2^128 > d
+        let carry = r >> 127;
+        r = (r << 1) | ((lo >> i) & 1);
+        if carry == 1 || r >= d {
+            // if carry == 1, true r = 2^128 + r so the wrapping sub
+            // gives the correct result, which is < d
+            r = r.wrapping_sub(d);
+            q |= 1 << i;
+        }
+    }
+    (q, r)
+}
+
+/// Displayed as `0x{hi:032x}{lo:032x}`
+impl Display for U256 {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!("0x{:032x}{:032x}", self.hi, self.lo))
+    }
+}
+
+impl From<u128> for U256 {
+    #[inline]
+    fn from(x: u128) -> Self {
+        Self::from_u128(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn mul_div_round_trip(a: u128, b: u128, d in 1..=u128::MAX, small_d in 1..=u64::MAX as u128) {
+            let p = U256::widening_mul(a, b);
+            if b != 0 {
+                let (q, r) = p.div_rem_u128(b);
+                prop_assert_eq!(q, U256::from_u128(a));
+                prop_assert_eq!(r, 0);
+            }
+            for d in [d, small_d] {
+                // p = qd + r
+                let (q, r) = p.div_rem_u128(d);
+                prop_assert!(r < d);
+                let qd_lo = U256::widening_mul(q.lo(), d);
+                let qd_hi = U256::widening_mul(q.hi(), d);
+                // q.hi() * d * 2^128 must not overflow
+                prop_assert_eq!(qd_hi.hi(), 0);
+                let sum = U256 {
+                    hi: qd_lo.hi() + qd_hi.lo(),
+                    lo: qd_lo.lo(),
+                }
+                .checked_add_u128(r)
+                .unwrap();
+                prop_assert_eq!(sum, p);
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn small_mul_matches_u128(a: u64, b: u64, c: u128) {
+            let p = U256::widening_mul(a.into(), b.into());
+            prop_assert_eq!(p.to_u128_checked(), Some(a as u128 * b as u128));
+            let c = U256::from_u128(c);
+            prop_assert_eq!(p.const_cmp(&c), p.cmp(&c));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn add_sub_round_trip(hi: u128, lo: u128, x: u128, y_hi: u128, y_lo: u128) {
+            let a = U256 { hi, lo };
+            match a.checked_add_u128(x) {
+                Some(s) => prop_assert_eq!(s.checked_sub_u128(x), Some(a)),
+                None => prop_assert_eq!(hi, u128::MAX),
+            }
+            let b = U256 { hi: y_hi, lo: y_lo };
+            match a.checked_add(b) {
+                Some(s) => {
+                    prop_assert_eq!(s.checked_sub(b), Some(a));
+                    prop_assert_eq!(s.checked_sub(a), Some(b));
+                }
+                None => prop_assert!(hi.checked_add(y_hi).and_then(|h| h.checked_add(lo.overflowing_add(y_lo).1.into())).is_none()),
+            }
+            prop_assert_eq!(a.checked_sub(b).is_some(), a >= b);
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use core::ops::RangeInclusive;
+
 #[inline]
 pub(crate) const fn u128_to_u64_checked(x: u128) -> Option<u64> {
     if x > u64::MAX as u128 {
@@ -7,6 +9,65 @@ pub(crate) const fn u128_to_u64_checked(x: u128) -> Option<u64> {
     }
 }
 
+/// Converts a reversal range of `u128`s to one of `u64`s
+/// to obtain the `u64` reversal range from the `u128` one.
+///
+/// # Returns
+///
+/// - `None` if `min > u64::MAX`
+/// - `max` is saturated at `u64::MAX`
+#[inline]
+pub(crate) const fn range_u128_to_u64(r: RangeInclusive<u128>) -> Option<RangeInclusive<u64>> {
+    let min = match u128_to_u64_checked(*r.start()) {
+        None => return None,
+        Some(m) => m,
+    };
+    let max = match u128_to_u64_checked(*r.end()) {
+        // saturation
+        None => u64::MAX,
+        Some(m) => m,
+    };
+    Some(min..=max)
+}
+
+/// Concatenates `a` and `b`. `C` must be `A + B`
+#[inline]
+pub(crate) const fn concat_bytes<const A: usize, const B: usize, const C: usize>(
+    a: [u8; A],
+    b: [u8; B],
+) -> [u8; C] {
+    let mut res = [0u8; C];
+    let mut i = 0;
+    while i < A {
+        res[i] = a[i];
+        i += 1;
+    }
+    while i < C {
+        res[i] = b[i - A];
+        i += 1;
+    }
+    res
+}
+
+/// Splits `c` into its first `A` bytes and the remaining `B` bytes. `C` must be `A + B`
+#[inline]
+pub(crate) const fn split_bytes<const A: usize, const B: usize, const C: usize>(
+    c: [u8; C],
+) -> ([u8; A], [u8; B]) {
+    let mut a = [0u8; A];
+    let mut b = [0u8; B];
+    let mut i = 0;
+    while i < A {
+        a[i] = c[i];
+        i += 1;
+    }
+    while i < C {
+        b[i - A] = c[i];
+        i += 1;
+    }
+    (a, b)
+}
+
 #[cfg(test)]
 pub mod test_utils {
     use proptest::prelude::*;
@@ -14,6 +75,22 @@ pub mod test_utils {
 
     use crate::{ArithTypes, Ratio};
 
+    /// # Returns
+    ///
+    /// `(below, |yd - xn|, d)`, where `below` is true if
+    /// `y < xn/d` i.e. `y` is below the exact value.
+    ///
+    /// `|yd - xn| / d` is the absolute distance of `y` from the exact value.
+    pub fn dist_from_exact(x: u64, y: u64, n: u128, d: u128) -> (bool, u128, u128) {
+        let xn = x as u128 * n;
+        let yd = y as u128 * d;
+        if yd < xn {
+            (true, xn - yd, d)
+        } else {
+            (false, yd - xn, d)
+        }
+    }
+
     macro_rules! ratio_cases {
         (
             $N:ty, $D:ty
@@ -81,4 +158,15 @@ pub mod test_utils {
     ratio_cases!(u64, u16);
     ratio_cases!(u64, u32);
     ratio_cases!(u64, u64);
+
+    ratio_cases!(u8, u128);
+    ratio_cases!(u16, u128);
+    ratio_cases!(u32, u128);
+    ratio_cases!(u64, u128);
+
+    ratio_cases!(u128, u8);
+    ratio_cases!(u128, u16);
+    ratio_cases!(u128, u32);
+    ratio_cases!(u128, u64);
+    ratio_cases!(u128, u128);
 }
@@ -0,0 +1,611 @@
+use core::{
+    cmp::Ordering,
+    fmt::{Display, Formatter},
+    hash::{Hash, Hasher},
+    ops::RangeInclusive,
+};
+
+use crate::{ArithTypes, Ceil, Floor, Ratio, RatioApply};
+
+/// A [`Ratio`] with a nonzero denominator that is `<= 1.0`,
+/// e.g. a discount factor, utilisation rate or weight.
+///
+/// Applying it never overflows.
+#[derive(Debug, Copy, Clone)]
+#[repr(transparent)]
+pub struct UnitRatio<N, D>(Ratio<N, D>);
+
+/// A [`Ratio`] with a nonzero denominator, i.e. a well-defined number.
+///
+/// Its reciprocal is defined whenever it is not `0.0`.
+#[derive(Debug, Copy, Clone)]
+#[repr(transparent)]
+pub struct NonZeroRatio<N, D>(Ratio<N, D>);
+
+/// A [`Ratio`] with a nonzero denominator that is `>= 1.0`,
+/// e.g. a markup or the exchange rate of an appreciating asset.
+///
+/// Reversing its application never overflows.
+#[derive(Debug, Copy, Clone)]
+#[repr(transparent)]
+pub struct GteOneRatio<N, D>(Ratio<N, D>);
+
+macro_rules! impl_validated_common {
+    ($V:ident) => {
+        impl<N, D> $V<N, D> {
+            #[inline]
+            pub const fn as_inner_ref(&self) -> &Ratio<N, D> {
+                &self.0
+            }
+        }
+
+        impl<N: Copy, D: Copy> $V<N, D> {
+            #[inline]
+            pub const fn to_inner(self) -> Ratio<N, D> {
+                self.0
+            }
+        }
+
+        impl<N, D> AsRef<Ratio<N, D>> for $V<N, D> {
+            #[inline]
+            fn as_ref(&self) -> &Ratio<N, D> {
+                self.as_inner_ref()
+            }
+        }
+    };
+}
+
+impl_validated_common!(UnitRatio);
+impl_validated_common!(NonZeroRatio);
+impl_validated_common!(GteOneRatio);
+
+/// Trait impls that delegate to the inner [`Ratio`]
+macro_rules! impl_validated_traits {
+    ($V:ident, $N:ty, $D:ty) => {
+        impl From<$V<$N, $D>> for Ratio<$N, $D> {
+            #[inline]
+            fn from(r: $V<$N, $D>) -> Self {
+                r.to_inner()
+            }
+        }
+
+        /// Uses [`Ratio::const_cmp`], see its docs for more info
+        impl PartialEq for $V<$N, $D> {
+            #[inline]
+            fn eq(&self, rhs: &Self) -> bool {
+                self.0.const_cmp(&rhs.0).is_eq()
+            }
+        }
+
+        /// Uses [`Ratio::const_cmp`], see its docs for more info
+        impl Eq for $V<$N, $D> {}
+
+        /// Uses [`Ratio::const_cmp`], see its docs for more info
+        impl PartialOrd for $V<$N, $D> {
+            #[inline]
+            fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+                Some(self.cmp(rhs))
+            }
+        }
+
+        /// Uses [`Ratio::const_cmp`], see its docs for more info
+        impl Ord for $V<$N, $D> {
+            #[inline]
+            fn cmp(&self, rhs: &Self) -> Ordering {
+                self.0.const_cmp(&rhs.0)
+            }
+        }
+
+        /// Hashes the same as the inner [`Ratio`]
+        impl Hash for $V<$N, $D> {
+            #[inline]
+            fn hash<H>(&self, state: &mut H)
+            where
+                H: Hasher,
+            {
+                self.0.hash(state)
+            }
+        }
+
+        /// Displayed as `{numerator}/{denominator}`
+        impl Display for $V<$N, $D> {
+            #[inline]
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    };
+}
+
+macro_rules! impl_validated {
+    ($N:ty, $D:ty) => {
+        impl UnitRatio<$N, $D> {
+            pub const ZERO: Self = Self(Ratio { n: 0, d: 1 });
+            pub const ONE: Self = Self(Ratio { n: 1, d: 1 });
+
+            /// Returns `None` if `ratio.d == 0` or `ratio > 1.0`
+            #[inline]
+            pub const fn new(ratio: Ratio<$N, $D>) -> Option<Self> {
+                type Max = <Ratio<$N, $D> as ArithTypes>::Max;
+
+                if ratio.d == 0 || ratio.n as Max > ratio.d as Max {
+                    None
+                } else {
+                    Some(Self(ratio))
+                }
+            }
+
+            /// # Safety
+            /// - `ratio.d` must be nonzero
+            /// - `ratio` must be `<= 1.0`
+            #[inline]
+            pub const unsafe fn new_unchecked(ratio: Ratio<$N, $D>) -> Self {
+                Self(ratio)
+            }
+
+            /// # Returns
+            /// `1.0 - self`, which is exact since `self <= 1.0`
+            #[inline]
+            pub const fn one_minus(&self) -> UnitRatio<$D, $D> {
+                let Ratio { n, d } = self.0;
+                // unchecked-arith: n <= d, so n also fits into D
+                UnitRatio(Ratio { n: d - n as $D, d })
+            }
+
+            /// # Returns
+            /// `1.0 / self`, or `None` if `self` is zero
+            #[inline]
+            pub const fn recip(&self) -> Option<GteOneRatio<$D, $N>> {
+                let Ratio { n, d } = self.0;
+                if n == 0 {
+                    None
+                } else {
+                    Some(GteOneRatio(Ratio { n: d, d: n }))
+                }
+            }
+        }
+
+        impl NonZeroRatio<$N, $D> {
+            pub const ZERO: Self = Self(Ratio { n: 0, d: 1 });
+            pub const ONE: Self = Self(Ratio { n: 1, d: 1 });
+
+            /// Returns `None` if `ratio.d == 0`
+            #[inline]
+            pub const fn new(ratio: Ratio<$N, $D>) -> Option<Self> {
+                if ratio.d == 0 {
+                    None
+                } else {
+                    Some(Self(ratio))
+                }
+            }
+
+            /// # Safety
+            /// - `ratio.d` must be nonzero
+            #[inline]
+            pub const unsafe fn new_unchecked(ratio: Ratio<$N, $D>) -> Self {
+                Self(ratio)
+            }
+
+            /// # Returns
+            /// `1.0 / self`, or `None` if `self` is zero
+            #[inline]
+            pub const fn recip(&self) -> Option<NonZeroRatio<$D, $N>> {
+                let Ratio { n, d } = self.0;
+                if n == 0 {
+                    None
+                } else {
+                    Some(NonZeroRatio(Ratio { n: d, d: n }))
+                }
+            }
+        }
+
+        impl GteOneRatio<$N, $D> {
+            pub const ONE: Self = Self(Ratio { n: 1, d: 1 });
+
+            /// Returns `None` if `ratio.d == 0` or `ratio < 1.0`
+            #[inline]
+            pub const fn new(ratio: Ratio<$N, $D>) -> Option<Self> {
+                type Max = <Ratio<$N, $D> as ArithTypes>::Max;
+
+                if ratio.d == 0 || (ratio.n as Max) < (ratio.d as Max) {
+                    None
+                } else {
+                    Some(Self(ratio))
+                }
+            }
+
+            /// # Safety
+            /// - `ratio.d` must be nonzero
+            /// - `ratio` must be `>= 1.0`
+            #[inline]
+            pub const unsafe fn new_unchecked(ratio: Ratio<$N, $D>) -> Self {
+                Self(ratio)
+            }
+
+            /// # Returns
+            /// `1.0 / self`
+            #[inline]
+            pub const fn recip(&self) -> UnitRatio<$D, $N> {
+                let Ratio { n, d } = self.0;
+                UnitRatio(Ratio { n: d, d: n })
+            }
+        }
+
+        impl_validated_traits!(UnitRatio, $N, $D);
+        impl_validated_traits!(NonZeroRatio, $N, $D);
+        impl_validated_traits!(GteOneRatio, $N, $D);
+
+        impl_validated_div!(Floor, $N, $D);
+        impl_validated_div!(Ceil, $N, $D);
+    };
+}
+
+/// [`Ratio`] application and reversal, with the invariants of each validated type
+/// making some of their special cases impossible
+macro_rules! impl_validated_div {
+    ($R:ident, $N:ty, $D:ty) => {
+        impl $R<UnitRatio<$N, $D>> {
+            #[doc = concat!("[`", stringify!($R), "::apply`] that never overflows since the ratio is `<= 1.0`")]
+            #[inline]
+            pub const fn apply(&self, amount: u64) -> u64 {
+                match $R(self.0 .0).apply(amount) {
+                    Some(x) => x,
+                    // result <= amount, so this is always Some
+                    None => unreachable!(),
+                }
+            }
+
+            #[doc = concat!("[`", stringify!($R), "::apply_u128`] that never overflows since the ratio is `<= 1.0`")]
+            #[inline]
+            pub const fn apply_u128(&self, amount: u128) -> u128 {
+                match $R(self.0 .0).apply_u128(amount) {
+                    Some(x) => x,
+                    // result <= amount, so this is always Some
+                    None => unreachable!(),
+                }
+            }
+
+            #[doc = concat!("[`", stringify!($R), "::reverse`].")]
+            ///
+            /// ## Special Case Returns
+            ///
+            /// - `None` if `amt_after_apply` is greater than the result of applying to `u64::MAX`
+            ///
+            /// Every other `amt_after_apply` is attainable since the ratio is `<= 1.0`
+            #[inline]
+            pub const fn reverse(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                $R(self.0 .0).reverse(amt_after_apply)
+            }
+
+            #[doc = concat!("[`", stringify!($R), "::reverse_est`]")]
+            #[inline]
+            pub const fn reverse_est(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                $R(self.0 .0).reverse_est(amt_after_apply)
+            }
+
+            #[doc = concat!("[`", stringify!($R), "::reverse_u128`]")]
+            #[inline]
+            pub const fn reverse_u128(&self, amt_after_apply: u128) -> Option<RangeInclusive<u128>> {
+                $R(self.0 .0).reverse_u128(amt_after_apply)
+            }
+
+            #[doc = concat!("[`", stringify!($R), "::reverse_est_u128`]")]
+            #[inline]
+            pub const fn reverse_est_u128(&self, amt_after_apply: u128) -> Option<RangeInclusive<u128>> {
+                $R(self.0 .0).reverse_est_u128(amt_after_apply)
+            }
+        }
+
+        impl RatioApply for $R<UnitRatio<$N, $D>> {
+            #[inline]
+            fn apply(&self, amount: u64) -> Option<u64> {
+                Some(Self::apply(self, amount))
+            }
+
+            #[inline]
+            fn reverse_est(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                Self::reverse_est(self, amt_after_apply)
+            }
+
+            #[inline]
+            fn reverse(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                Self::reverse(self, amt_after_apply)
+            }
+        }
+
+        impl $R<NonZeroRatio<$N, $D>> {
+            #[doc = concat!("[`", stringify!($R), "::apply`].")]
+            ///
+            /// ## Special Case Returns
+            /// - `None` if `result > u64::MAX`
+            #[inline]
+            pub const fn apply(&self, amount: u64) -> Option<u64> {
+                $R(self.0 .0).apply(amount)
+            }
+
+            #[doc = concat!("[`", stringify!($R), "::apply_u128`]")]
+            #[inline]
+            pub const fn apply_u128(&self, amount: u128) -> Option<u128> {
+                $R(self.0 .0).apply_u128(amount)
+            }
+
+            #[doc = concat!("[`", stringify!($R), "::reverse`].")]
+            ///
+            /// ## Special Case Returns
+            ///
+            /// - `0..=u64::MAX` if the ratio is zero and `amt_after_apply == 0`
+            /// - `None` if the ratio is zero but `amt_after_apply != 0`
+            /// - `None` if `min > u64::MAX`
+            /// - `None` if min > max. This can happen if the ratio is `> 1.0`
+            #[inline]
+            pub const fn reverse(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                $R(self.0 .0).reverse(amt_after_apply)
+            }
+
+            #[doc = concat!("[`", stringify!($R), "::reverse_est`]")]
+            #[inline]
+            pub const fn reverse_est(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                $R(self.0 .0).reverse_est(amt_after_apply)
+            }
+
+            #[doc = concat!("[`", stringify!($R), "::reverse_u128`]")]
+            #[inline]
+            pub const fn reverse_u128(&self, amt_after_apply: u128) -> Option<RangeInclusive<u128>> {
+                $R(self.0 .0).reverse_u128(amt_after_apply)
+            }
+
+            #[doc = concat!("[`", stringify!($R), "::reverse_est_u128`]")]
+            #[inline]
+            pub const fn reverse_est_u128(&self, amt_after_apply: u128) -> Option<RangeInclusive<u128>> {
+                $R(self.0 .0).reverse_est_u128(amt_after_apply)
+            }
+        }
+
+        impl RatioApply for $R<NonZeroRatio<$N, $D>> {
+            #[inline]
+            fn apply(&self, amount: u64) -> Option<u64> {
+                Self::apply(self, amount)
+            }
+
+            #[inline]
+            fn reverse_est(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                Self::reverse_est(self, amt_after_apply)
+            }
+
+            #[inline]
+            fn reverse(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                Self::reverse(self, amt_after_apply)
+            }
+        }
+
+        impl $R<GteOneRatio<$N, $D>> {
+            #[doc = concat!("[`", stringify!($R), "::apply`].")]
+            ///
+            /// ## Special Case Returns
+            /// - `None` if `result > u64::MAX`
+            #[inline]
+            pub const fn apply(&self, amount: u64) -> Option<u64> {
+                $R(self.0 .0).apply(amount)
+            }
+
+            #[doc = concat!("[`", stringify!($R), "::apply_u128`]")]
+            #[inline]
+            pub const fn apply_u128(&self, amount: u128) -> Option<u128> {
+                $R(self.0 .0).apply_u128(amount)
+            }
+
+            #[doc = concat!("[`", stringify!($R), "::reverse`].")]
+            ///
+            /// Never overflows since the ratio is `>= 1.0`.
+            ///
+            /// ## Special Case Returns
+            ///
+            /// - `None` if min > max, i.e. `amt_after_apply` is not attainable.
+            ///   This can happen if the ratio is `> 1.0`
+            #[inline]
+            pub const fn reverse(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                $R(self.0 .0).reverse(amt_after_apply)
+            }
+
+            #[doc = concat!("[`", stringify!($R), "::reverse_est`], which never returns `None`")]
+            #[inline]
+            pub const fn reverse_est(&self, amt_after_apply: u64) -> RangeInclusive<u64> {
+                match $R(self.0 .0).reverse_est(amt_after_apply) {
+                    Some(r) => r,
+                    // ratio is nonzero and min <= amt_after_apply, so this is always Some
+                    None => unreachable!(),
+                }
+            }
+
+            #[doc = concat!("[`", stringify!($R), "::reverse_u128`]")]
+            #[inline]
+            pub const fn reverse_u128(&self, amt_after_apply: u128) -> Option<RangeInclusive<u128>> {
+                $R(self.0 .0).reverse_u128(amt_after_apply)
+            }
+
+            #[doc = concat!("[`", stringify!($R), "::reverse_est_u128`]")]
+            #[inline]
+            pub const fn reverse_est_u128(&self, amt_after_apply: u128) -> Option<RangeInclusive<u128>> {
+                $R(self.0 .0).reverse_est_u128(amt_after_apply)
+            }
+        }
+
+        impl RatioApply for $R<GteOneRatio<$N, $D>> {
+            #[inline]
+            fn apply(&self, amount: u64) -> Option<u64> {
+                Self::apply(self, amount)
+            }
+
+            #[inline]
+            fn reverse_est(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                Some(Self::reverse_est(self, amt_after_apply))
+            }
+
+            #[inline]
+            fn reverse(&self, amt_after_apply: u64) -> Option<RangeInclusive<u64>> {
+                Self::reverse(self, amt_after_apply)
+            }
+        }
+    };
+}
+
+impl_validated!(u8, u8);
+impl_validated!(u8, u16);
+impl_validated!(u8, u32);
+impl_validated!(u8, u64);
+
+impl_validated!(u16, u8);
+impl_validated!(u16, u16);
+impl_validated!(u16, u32);
+impl_validated!(u16, u64);
+
+impl_validated!(u32, u8);
+impl_validated!(u32, u16);
+impl_validated!(u32, u32);
+impl_validated!(u32, u64);
+
+impl_validated!(u64, u8);
+impl_validated!(u64, u16);
+impl_validated!(u64, u32);
+impl_validated!(u64, u64);
+
+impl_validated!(u8, u128);
+impl_validated!(u16, u128);
+impl_validated!(u32, u128);
+impl_validated!(u64, u128);
+
+impl_validated!(u128, u8);
+impl_validated!(u128, u16);
+impl_validated!(u128, u32);
+impl_validated!(u128, u64);
+impl_validated!(u128, u128);
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    macro_rules! check_div {
+        ($R:ident, $r:expr, $amt:expr, $aaf:expr) => {{
+            let r = $r;
+            let raw = $R(r.to_inner());
+            match raw.apply($amt) {
+                Some(x) => {
+                    prop_assert_eq!(RatioApply::apply(&$R(r), $amt), Some(x));
+                    prop_assert!(RatioApply::reverse(&$R(r), x).unwrap().contains(&$amt));
+                }
+                None => prop_assert_eq!(RatioApply::apply(&$R(r), $amt), None),
+            }
+            prop_assert_eq!(RatioApply::reverse(&$R(r), $aaf), raw.reverse($aaf));
+            prop_assert_eq!(RatioApply::reverse_est(&$R(r), $aaf), raw.reverse_est($aaf));
+            prop_assert_eq!(
+                $R(r).reverse_u128($aaf as u128),
+                raw.reverse_u128($aaf as u128)
+            );
+            prop_assert_eq!(
+                $R(r).reverse_est_u128($aaf as u128),
+                raw.reverse_est_u128($aaf as u128)
+            );
+        }};
+    }
+
+    macro_rules! test_suite {
+        ($N:ty, $D:ty, $test:ident) => {
+            proptest! {
+                #[test]
+                fn $test(n: $N, d: $D, amt: u64, aaf: u64) {
+                    type R = Ratio<$N, $D>;
+                    type Max = <R as ArithTypes>::Max;
+
+                    let r = R::new(n, d);
+                    let (nm, dm) = (n as Max, d as Max);
+
+                    let unit = UnitRatio::<$N, $D>::new(r);
+                    prop_assert_eq!(unit.is_some(), d != 0 && nm <= dm);
+                    if let Some(u) = unit {
+                        let Ratio { n: mn, d: md } = u.one_minus().to_inner();
+                        prop_assert_eq!((mn as Max + nm, md as Max), (dm, dm));
+                        match u.recip() {
+                            None => prop_assert_eq!(n, 0),
+                            Some(g) => prop_assert_eq!(g.recip(), u),
+                        }
+                        prop_assert_eq!(Floor(u).apply(amt), Floor(r).apply(amt).unwrap());
+                        prop_assert_eq!(Ceil(u).apply(amt), Ceil(r).apply(amt).unwrap());
+                        prop_assert_eq!(
+                            Floor(u).apply_u128(amt as u128),
+                            Floor(r).apply_u128(amt as u128).unwrap(),
+                        );
+                        // every output up to that of u64::MAX is attainable
+                        prop_assert_eq!(
+                            Floor(u).reverse(aaf).is_some(),
+                            aaf <= Floor(u).apply(u64::MAX),
+                        );
+                        prop_assert_eq!(
+                            Ceil(u).reverse(aaf).is_some(),
+                            aaf <= Ceil(u).apply(u64::MAX),
+                        );
+                        check_div!(Floor, u, amt, aaf);
+                        check_div!(Ceil, u, amt, aaf);
+                    }
+
+                    let nonzero = NonZeroRatio::<$N, $D>::new(r);
+                    prop_assert_eq!(nonzero.is_some(), d != 0);
+                    if let Some(z) = nonzero {
+                        match z.recip() {
+                            None => prop_assert_eq!(n, 0),
+                            Some(zr) => {
+                                prop_assert_eq!(zr.recip(), Some(z));
+                                let Ratio { n: rn, d: rd } = zr.to_inner();
+                                prop_assert_eq!((rn, rd), (d, n));
+                            }
+                        }
+                        check_div!(Floor, z, amt, aaf);
+                        check_div!(Ceil, z, amt, aaf);
+                    }
+
+                    let gte_one = GteOneRatio::<$N, $D>::new(r);
+                    prop_assert_eq!(gte_one.is_some(), d != 0 && nm >= dm);
+                    if let Some(g) = gte_one {
+                        prop_assert_eq!(g.recip().recip(), Some(g));
+                        prop_assert!(Floor(g).reverse_est(aaf).start() <= &aaf);
+                        prop_assert!(Ceil(g).reverse_est(aaf).start() <= &aaf);
+                        check_div!(Floor, g, amt, aaf);
+                        check_div!(Ceil, g, amt, aaf);
+                    }
+                }
+            }
+        };
+    }
+
+    test_suite!(u8, u8, validated_u8_u8);
+    test_suite!(u8, u16, validated_u8_u16);
+    test_suite!(u8, u32, validated_u8_u32);
+    test_suite!(u8, u64, validated_u8_u64);
+
+    test_suite!(u16, u8, validated_u16_u8);
+    test_suite!(u16, u16, validated_u16_u16);
+    test_suite!(u16, u32, validated_u16_u32);
+    test_suite!(u16, u64, validated_u16_u64);
+
+    test_suite!(u32, u8, validated_u32_u8);
+    test_suite!(u32, u16, validated_u32_u16);
+    test_suite!(u32, u32, validated_u32_u32);
+    test_suite!(u32, u64, validated_u32_u64);
+
+    test_suite!(u64, u8, validated_u64_u8);
+    test_suite!(u64, u16, validated_u64_u16);
+    test_suite!(u64, u32, validated_u64_u32);
+    test_suite!(u64, u64, validated_u64_u64);
+
+    test_suite!(u8, u128, validated_u8_u128);
+    test_suite!(u16, u128, validated_u16_u128);
+    test_suite!(u32, u128, validated_u32_u128);
+    test_suite!(u64, u128, validated_u64_u128);
+
+    test_suite!(u128, u8, validated_u128_u8);
+    test_suite!(u128, u16, validated_u128_u16);
+    test_suite!(u128, u32, validated_u128_u32);
+    test_suite!(u128, u64, validated_u128_u64);
+    test_suite!(u128, u128, validated_u128_u128);
+}